@@ -0,0 +1,92 @@
+// alert_output.rs - Ardura 2024
+// Clip and overload alerts out to the host as MIDI, so a controller pad or a DAW macro can
+// react during live use. Each alert holds for a moment so a single clipped sample still flashes.
+
+use nih_plug::prelude::Enum;
+
+/// Alerts that can be sent, each on its own note or CC counting up from the chosen one
+pub const ALERTS: usize = 2;
+
+/// The output went over 0 dBFS
+pub const CLIP: usize = 0;
+
+/// The safety limiter stepped in, stays on until the warning is dismissed
+pub const OVERLOAD: usize = 1;
+
+/// How long an alert stays on after its last trigger
+const HOLD_SECONDS: f32 = 0.25;
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertMessage {
+    /// Note on while the alert is on, note off after
+    #[name = "Note"]
+    Note,
+    /// CC at 127 while the alert is on, 0 after
+    #[name = "CC"]
+    Cc,
+}
+
+pub struct AlertOutput {
+    // Samples each alert has left to hold, 0 is off
+    holding: [u32; ALERTS],
+    hold_samples: u32,
+    // Message and number each alert went out on, the release has to match it even when the
+    // params changed while it was held
+    sent: [Option<(AlertMessage, u8)>; ALERTS],
+}
+
+impl AlertOutput {
+    pub fn new() -> Self {
+        let mut alert_output = Self {
+            holding: [0; ALERTS],
+            hold_samples: 0,
+            sent: [None; ALERTS],
+        };
+        alert_output.set_sample_rate(44100.0);
+        alert_output
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.hold_samples = (HOLD_SECONDS * sample_rate) as u32;
+    }
+
+    /// Turns every alert off. Anything already sent stays on record, so the next `changes()`
+    /// still releases it.
+    pub fn reset(&mut self) {
+        self.holding = [0; ALERTS];
+    }
+
+    /// Feed whether an alert triggered during a block
+    pub fn update(&mut self, alert: usize, triggered: bool, block_len: usize) {
+        self.holding[alert] = if triggered {
+            self.hold_samples.max(1)
+        } else {
+            self.holding[alert].saturating_sub(block_len as u32)
+        };
+    }
+
+    /// What has to go out for each alert since the last call: the message, its note or CC and
+    /// whether it's switching on. New alerts go out on `message` counting up from `number`,
+    /// releases on whatever the alert was sent with.
+    pub fn changes(
+        &mut self,
+        message: AlertMessage,
+        number: u8,
+    ) -> [Option<(AlertMessage, u8, bool)>; ALERTS] {
+        std::array::from_fn(|alert| {
+            let on = self.holding[alert] > 0;
+            match self.sent[alert] {
+                None if on => {
+                    let sent = (message, number + alert as u8);
+                    self.sent[alert] = Some(sent);
+                    Some((sent.0, sent.1, true))
+                }
+                Some((message, number)) if !on => {
+                    self.sent[alert] = None;
+                    Some((message, number, false))
+                }
+                _ => None,
+            }
+        })
+    }
+}
@@ -0,0 +1,76 @@
+// auto_gain.rs - Ardura 2024
+// Automatic gain compensation: compares the short term loudness going in with what the console
+// puts out and trims the processed signal back to the input level, for honest A/B-ing
+
+/// Short term loudness window
+const WINDOW_SECONDS: f32 = 0.4;
+
+/// How quickly the trim follows, slower than the window so it doesn't pump
+const TRIM_SECONDS: f32 = 1.0;
+
+/// Below this (-60 dBFS) the estimates hold so silence and tails don't swing the trim
+const GATE_RMS: f32 = 0.001;
+
+/// Most trim either way, +/-12 dB
+const MAX_TRIM: f32 = 3.981;
+
+pub struct AutoGain {
+    input_square: f32,
+    output_square: f32,
+    trim: f32,
+    window_coeff: f32,
+    trim_coeff: f32,
+}
+
+impl AutoGain {
+    pub fn new() -> Self {
+        Self {
+            input_square: 0.0,
+            output_square: 0.0,
+            trim: 1.0,
+            window_coeff: 0.0,
+            trim_coeff: 0.0,
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.window_coeff = (-1.0 / (WINDOW_SECONDS * sample_rate)).exp();
+        self.trim_coeff = (-1.0 / (TRIM_SECONDS * sample_rate)).exp();
+    }
+
+    /// Feed one stereo frame of input and the processed output it became, returns the trim for
+    /// the processed output
+    pub fn next(&mut self, input: [f32; 2], output: [f32; 2]) -> f32 {
+        let input_square = 0.5 * (input[0] * input[0] + input[1] * input[1]);
+        let output_square = 0.5 * (output[0] * output[0] + output[1] * output[1]);
+        if input_square > GATE_RMS * GATE_RMS || output_square > GATE_RMS * GATE_RMS {
+            self.input_square =
+                input_square + (self.input_square - input_square) * self.window_coeff;
+            self.output_square =
+                output_square + (self.output_square - output_square) * self.window_coeff;
+        }
+
+        let target = if self.input_square > GATE_RMS * GATE_RMS
+            && self.output_square > GATE_RMS * GATE_RMS
+        {
+            (self.input_square / self.output_square)
+                .sqrt()
+                .clamp(1.0 / MAX_TRIM, MAX_TRIM)
+        } else {
+            self.trim
+        };
+        self.trim = target + (self.trim - target) * self.trim_coeff;
+        self.trim
+    }
+
+    /// Current trim as a gain, for the GUI
+    pub fn trim(&self) -> f32 {
+        self.trim
+    }
+
+    pub fn reset(&mut self) {
+        self.input_square = 0.0;
+        self.output_square = 0.0;
+        self.trim = 1.0;
+    }
+}
@@ -0,0 +1,50 @@
+// auto_mult.rs - Ardura 2024
+// Program adaptive multiplier: follows the long term input loudness and scales the multiplier
+// the other way so quiet and loud sections get about the same amount of console
+
+/// Loudness the multiplier is left alone at (-18 dBFS RMS)
+const REFERENCE_RMS: f32 = 0.125;
+
+/// Long term loudness window
+const TIME_CONSTANT_SECONDS: f32 = 3.0;
+
+/// Below this (-60 dBFS) the follower holds so silence doesn't crank the multiplier
+const GATE_RMS: f32 = 0.001;
+
+/// Furthest the multiplier gets scaled either way
+const MAX_SCALE: f32 = 2.0;
+
+pub struct AutoMult {
+    mean_square: f32,
+    coeff: f32,
+}
+
+impl AutoMult {
+    pub fn new() -> Self {
+        Self {
+            mean_square: REFERENCE_RMS * REFERENCE_RMS,
+            coeff: 0.0,
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.coeff = (-1.0 / (TIME_CONSTANT_SECONDS * sample_rate)).exp();
+    }
+
+    /// Feed one stereo frame, returns the factor to scale the multiplier by
+    pub fn next(&mut self, left: f32, right: f32) -> f32 {
+        let square = 0.5 * (left * left + right * right);
+        if square > GATE_RMS * GATE_RMS {
+            self.mean_square = square + (self.mean_square - square) * self.coeff;
+        }
+
+        // Square root of the ratio so it leans on the multiplier rather than fully normalizing
+        (REFERENCE_RMS / self.mean_square.sqrt().max(GATE_RMS))
+            .sqrt()
+            .clamp(1.0 / MAX_SCALE, MAX_SCALE)
+    }
+
+    pub fn reset(&mut self) {
+        self.mean_square = REFERENCE_RMS * REFERENCE_RMS;
+    }
+}
@@ -0,0 +1,14 @@
+// gladedesk_cli.rs - Ardura 2024
+// Offline file processing through the Glade Desk engine:
+//   gladedesk_cli --process in.wav out.wav [--preset x.gladepreset]
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match GladeDesk::run_cli(&args) {
+        Ok(message) => println!("{message}"),
+        Err(message) => {
+            eprintln!("{message}");
+            std::process::exit(1);
+        }
+    }
+}
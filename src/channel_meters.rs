@@ -0,0 +1,191 @@
+// channel_meters.rs - Ardura 2024
+// Peak levels for every channel of the negotiated layout, written by the audio thread and drawn
+// as one bar per channel by the GUI. Levels are kept every few ms inside a block with the sample
+// they belong to, so one huge host buffer plays out smoothly instead of as a single jump.
+
+use atomic_float::AtomicF32;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Most channels metered, enough for 7.1 once the plugin offers surround layouts. Only mono
+/// and stereo are declared for now.
+pub const MAX_METER_CHANNELS: usize = 8;
+
+/// Timestamped levels kept for the GUI, 512 sub-blocks of 5 ms covers a 65536 sample buffer at
+/// 48 kHz
+const HISTORY: usize = 512;
+
+/// Length of a metering sub-block
+pub const SUB_BLOCK_SECONDS: f32 = 0.005;
+
+pub struct ChannelMeters {
+    levels: [AtomicF32; MAX_METER_CHANNELS],
+    channels: AtomicUsize,
+    // Ring of levels and the sample clock at the end of the sub-block each one covers
+    history: [[AtomicF32; MAX_METER_CHANNELS]; HISTORY],
+    stamps: [AtomicU64; HISTORY],
+    written: AtomicUsize,
+    // Host block length, how far behind the newest level the GUI plays
+    block_len: AtomicUsize,
+}
+
+impl Default for ChannelMeters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChannelMeters {
+    pub fn new() -> Self {
+        Self {
+            levels: std::array::from_fn(|_| AtomicF32::new(0.0)),
+            channels: AtomicUsize::new(2),
+            history: std::array::from_fn(|_| std::array::from_fn(|_| AtomicF32::new(0.0))),
+            stamps: std::array::from_fn(|_| AtomicU64::new(0)),
+            written: AtomicUsize::new(0),
+            block_len: AtomicUsize::new(0),
+        }
+    }
+
+    /// Called from initialize() with the layout the host picked
+    pub fn set_channels(&self, channels: usize) {
+        self.channels
+            .store(channels.clamp(1, MAX_METER_CHANNELS), Ordering::Relaxed);
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels.load(Ordering::Relaxed)
+    }
+
+    /// Linear peak level of one channel
+    pub fn level(&self, channel: usize) -> f32 {
+        self.levels[channel].load(Ordering::Relaxed)
+    }
+
+    /// Move every channel's level on by one block of `block_len` samples
+    pub fn update(&self, peaks: &BlockPeaks, decay_per_sample: f32, block_len: usize) {
+        for (channel, level) in self.levels.iter().enumerate().take(self.channels()) {
+            let current = level.load(Ordering::Relaxed);
+            level.store(
+                meter_level(current, peaks.peak(channel), decay_per_sample, block_len),
+                Ordering::Relaxed,
+            );
+        }
+    }
+
+    /// Same as `update()` for one sub-block, also keeping the result with `stamp`, the sample
+    /// clock at the end of the sub-block
+    pub fn push(&self, peaks: &BlockPeaks, decay_per_sample: f32, sub_block_len: usize, stamp: u64) {
+        self.update(peaks, decay_per_sample, sub_block_len);
+        let slot = self.written.load(Ordering::Relaxed) % HISTORY;
+        for (entry, level) in self.history[slot].iter().zip(self.levels.iter()) {
+            entry.store(level.load(Ordering::Relaxed), Ordering::Relaxed);
+        }
+        self.stamps[slot].store(stamp, Ordering::Relaxed);
+        self.written.fetch_add(1, Ordering::Release);
+    }
+
+    /// Called once per host block after the sub-blocks are in
+    pub fn set_block_len(&self, block_len: usize) {
+        self.block_len.store(block_len, Ordering::Relaxed);
+    }
+
+    /// Sample clock of the newest sub-block and the host block length
+    pub fn newest(&self) -> (u64, usize) {
+        let written = self.written.load(Ordering::Acquire);
+        let stamp = match written {
+            0 => 0,
+            _ => self.stamps[(written - 1) % HISTORY].load(Ordering::Relaxed),
+        };
+        (stamp, self.block_len.load(Ordering::Relaxed))
+    }
+
+    /// Level of a channel as of sample `position`, the newest sub-block that had ended by then.
+    /// Falls back to the oldest one kept, or the current level before anything was pushed.
+    pub fn level_at(&self, channel: usize, position: u64) -> f32 {
+        let written = self.written.load(Ordering::Acquire);
+        let mut found = None;
+        for back in 1..=written.min(HISTORY) {
+            let slot = (written - back) % HISTORY;
+            found = Some(slot);
+            if self.stamps[slot].load(Ordering::Relaxed) <= position {
+                break;
+            }
+        }
+        match found {
+            Some(slot) => self.history[slot][channel].load(Ordering::Relaxed),
+            None => self.level(channel),
+        }
+    }
+}
+
+/// GUI side guess of the sample that's playing right now, so the sub-block levels of a block get
+/// shown over the time they cover instead of all at once when the block arrives
+#[derive(Default)]
+pub struct MeterClock {
+    // Sample clock and GUI time it was last synced at
+    anchor: Option<(u64, f64)>,
+}
+
+impl MeterClock {
+    /// `newest` and `block_len` from `ChannelMeters::newest()`, `now` in seconds
+    pub fn position(&mut self, newest: u64, block_len: usize, now: f64, sample_rate: f32) -> u64 {
+        let block_len = block_len.max(1) as u64;
+        let estimate = self.anchor.map(|(stamp, time)| {
+            stamp + ((now - time).max(0.0) * sample_rate as f64) as u64
+        });
+        match estimate {
+            // Running at real time between one and two blocks behind the audio thread
+            Some(position) if position <= newest && newest - position <= 2 * block_len => position,
+            // Stalled, fell behind or just started: sync up one block behind the newest level
+            _ => {
+                let position = newest.saturating_sub(block_len);
+                self.anchor = Some((position, now));
+                position
+            }
+        }
+    }
+}
+
+/// Absolute peak of each channel over one block, fed sample by sample on the audio thread
+#[derive(Default)]
+pub struct BlockPeaks {
+    peaks: [f32; MAX_METER_CHANNELS],
+}
+
+impl BlockPeaks {
+    pub fn add(&mut self, channel: usize, sample: f32) {
+        if let Some(peak) = self.peaks.get_mut(channel) {
+            *peak = peak.max(sample.abs());
+        }
+    }
+
+    pub fn peak(&self, channel: usize) -> f32 {
+        self.peaks.get(channel).copied().unwrap_or(0.0)
+    }
+}
+
+/// Meter ballistics for one block: jump straight up to a louder peak, otherwise fall towards it
+/// by `decay_per_sample` for each of the `block_len` samples. Any block size lands on the same
+/// level for the same audio.
+pub fn meter_level(current: f32, block_peak: f32, decay_per_sample: f32, block_len: usize) -> f32 {
+    if block_peak > current {
+        block_peak
+    } else {
+        let decay = decay_per_sample.powi(block_len as i32);
+        current * decay + block_peak * (1.0 - decay)
+    }
+}
+
+/// Short name for a channel of a layout with `channels` channels
+pub fn channel_name(channels: usize, channel: usize) -> String {
+    let names: &[&str] = match channels {
+        1 => &["Mono"],
+        2 => &["L", "R"],
+        6 => &["L", "R", "C", "LFE", "Ls", "Rs"],
+        8 => &["L", "R", "C", "LFE", "Ls", "Rs", "Lb", "Rb"],
+        _ => &[],
+    };
+    names
+        .get(channel)
+        .map_or_else(|| (channel + 1).to_string(), |name| name.to_string())
+}
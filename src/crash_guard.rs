@@ -0,0 +1,111 @@
+// crash_guard.rs - Ardura 2024
+// Crash detection for safe mode: each process with an active instance keeps a locked marker
+// file in the temp dir from activation to deactivation. The OS drops the lock when a process
+// dies, so a marker nobody holds the lock on means that session died with an instance active,
+// while markers of other running hosts are left alone. Active is as close as the host lets us
+// get to processing, there's no start/stop processing callback to hang it on.
+
+use std::{
+    fs::{self, File},
+    path::PathBuf,
+    sync::Mutex,
+};
+
+const MARKER_PREFIX: &str = "gladedesk_processing_";
+const MARKER_EXTENSION: &str = ".marker";
+
+struct Guard {
+    // Active instances in this process, the marker goes away when the last one deactivates
+    active: usize,
+    // Held open and locked for as long as the marker exists
+    marker: Option<File>,
+    // Markers are scanned once per process, before our own marker is written
+    scanned: bool,
+    // A crash found by the scan that no instance has started in safe mode for yet
+    crash_pending: bool,
+}
+
+static GUARD: Mutex<Guard> = Mutex::new(Guard {
+    active: 0,
+    marker: None,
+    scanned: false,
+    crash_pending: false,
+});
+
+fn marker_path(suffix: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{MARKER_PREFIX}{}{suffix}", std::process::id()))
+}
+
+/// Looks for markers left unlocked by a process that's gone, clearing them out on the way
+fn find_crashed_markers() -> bool {
+    let Ok(entries) = fs::read_dir(std::env::temp_dir()) else {
+        return false;
+    };
+    let own = marker_path(MARKER_EXTENSION);
+    let mut crashed = false;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_marker = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                name.starts_with(MARKER_PREFIX) && name.ends_with(MARKER_EXTENSION)
+            });
+        if !is_marker || path == own {
+            continue;
+        }
+        let Ok(file) = File::open(&path) else {
+            continue;
+        };
+        if file.try_lock().is_err() {
+            // Another running host still holds its lock
+            continue;
+        }
+        // Nobody held the lock, the process that wrote it is gone
+        crashed = true;
+        drop(file);
+        let _ = fs::remove_file(&path);
+    }
+    crashed
+}
+
+/// Locked marker for this process. Written under a temporary name and renamed once locked so a
+/// scan from another process never sees it unlocked.
+fn create_marker() -> Option<File> {
+    let temp_path = marker_path(".tmp");
+    let file = File::create(&temp_path).ok()?;
+    file.lock().ok()?;
+    fs::rename(&temp_path, marker_path(MARKER_EXTENSION)).ok()?;
+    Some(file)
+}
+
+/// Call when an instance is activated. Returns true when a previous session crashed while an
+/// instance was still active, only to the first instance that asks so instances added later in
+/// the session start normally.
+pub fn activate() -> bool {
+    let mut guard = GUARD.lock().unwrap();
+    if !guard.scanned {
+        guard.scanned = true;
+        guard.crash_pending = find_crashed_markers();
+    }
+    let crashed = std::mem::take(&mut guard.crash_pending);
+    if guard.active == 0 {
+        // Best effort, without a marker we just can't offer safe mode next time
+        guard.marker = create_marker();
+    }
+    guard.active += 1;
+    crashed
+}
+
+/// Call when an instance is deactivated
+pub fn deactivate() {
+    let mut guard = GUARD.lock().unwrap();
+    guard.active = guard.active.saturating_sub(1);
+    if guard.active == 0 {
+        if let Some(marker) = guard.marker.take() {
+            // Unlock before removing, some platforms won't delete a file that's still open
+            drop(marker);
+            let _ = fs::remove_file(marker_path(MARKER_EXTENSION));
+        }
+    }
+}
@@ -0,0 +1,53 @@
+// crosstalk.rs - Ardura 2024
+// Left/right crosstalk: each side picks up a quiet copy of the other like neighbouring channels
+// on a real desk. The bleed there is mostly capacitive so it rises with frequency.
+
+use crate::filters::Biquad;
+
+/// The lowest setting of the Crosstalk param, treated as off
+pub const CROSSTALK_OFF_DB: f32 = -80.0;
+
+/// Extra bleed full Crosstalk Dynamics adds with the envelope at 0 dBFS
+pub const DYNAMIC_RANGE_DB: f32 = 12.0;
+
+// Capacitive coupling hardly passes the lows
+const BLEED_HIGHPASS_HZ: f32 = 1000.0;
+
+// Soft corner, no bump
+const BLEED_Q: f32 = 0.5;
+
+pub struct Crosstalk {
+    // Filters on what leaves each side, so `left_bleed` is what the right channel hears
+    left_bleed: Biquad,
+    right_bleed: Biquad,
+}
+
+impl Crosstalk {
+    pub fn new() -> Self {
+        let mut crosstalk = Self {
+            left_bleed: Biquad::new(),
+            right_bleed: Biquad::new(),
+        };
+        crosstalk.set_sample_rate(44100.0);
+        crosstalk
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.left_bleed
+            .set_highpass(BLEED_HIGHPASS_HZ, BLEED_Q, sample_rate);
+        self.right_bleed
+            .set_highpass(BLEED_HIGHPASS_HZ, BLEED_Q, sample_rate);
+    }
+
+    pub fn reset(&mut self) {
+        self.left_bleed.reset();
+        self.right_bleed.reset();
+    }
+
+    /// Add `gain` (linear) of each side's filtered signal to the other
+    pub fn process(&mut self, left: f32, right: f32, gain: f32) -> (f32, f32) {
+        let into_right = self.left_bleed.process(left) * gain;
+        let into_left = self.right_bleed.process(right) * gain;
+        (left + into_left, right + into_right)
+    }
+}
@@ -13,15 +13,28 @@ enum DBMeterText {
     Percentage,
 }
 
+/// Which way the bar fills
+#[derive(Clone, Copy, PartialEq)]
+pub enum MeterOrientation {
+    /// Left to right, the original full width strip
+    Horizontal,
+    /// Bottom to top, for meters standing side by side
+    Vertical,
+}
+
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
 pub struct DBMeter {
     level: f32,
+    orientation: MeterOrientation,
     desired_width: Option<f32>,
+    desired_height: Option<f32>,
     text: Option<DBMeterText>,
     animate: bool,
     border_color: Color32,
     bar_color: Color32,
     background_color: Color32,
+    stroke_width: f32,
+    text_size: f32,
 }
 
 #[allow(dead_code)]
@@ -30,12 +43,16 @@ impl DBMeter {
     pub fn new(level: f32) -> Self {
         Self {
             level: level.clamp(0.0, 1.0),
+            orientation: MeterOrientation::Horizontal,
             desired_width: None,
+            desired_height: None,
             text: None,
             animate: false,
             border_color: Color32::BLACK,
             bar_color: Color32::GREEN,
             background_color: Color32::GRAY,
+            stroke_width: 1.0,
+            text_size: 11.0,
         }
     }
 
@@ -45,6 +62,18 @@ impl DBMeter {
         self
     }
 
+    /// Fill left to right (the default) or bottom to top
+    pub fn orientation(mut self, orientation: MeterOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// The desired height of the bar, only used when vertical
+    pub fn desired_height(mut self, desired_height: f32) -> Self {
+        self.desired_height = Some(desired_height);
+        self
+    }
+
     /// A custom text to display on the progress bar.
     pub fn text(mut self, text: impl Into<WidgetText>) -> Self {
         self.text = Some(DBMeterText::Custom(text.into()));
@@ -65,6 +94,16 @@ impl DBMeter {
     pub fn set_background_color(&mut self, new_color: Color32) {
         self.background_color = new_color;
     }
+
+    /// Set the width of the outline and marker strokes
+    pub fn set_stroke_width(&mut self, width: f32) {
+        self.stroke_width = width;
+    }
+
+    /// Set the size of the meter text
+    pub fn set_text_size(&mut self, size: f32) {
+        self.text_size = size;
+    }
 }
 
 impl Widget for DBMeter {
@@ -72,21 +111,32 @@ impl Widget for DBMeter {
     fn ui(self, ui: &mut Ui) -> Response {
         let DBMeter {
             level,
+            orientation,
             desired_width,
+            desired_height,
             text,
             animate,
             border_color,
             bar_color,
             background_color,
+            stroke_width,
+            text_size,
         } = self;
 
         let animate = animate && level < 1.0;
 
-        let desired_width =
-            desired_width.unwrap_or_else(|| ui.available_size_before_wrap().x.at_least(96.0));
-        let height = ui.spacing().interact_size.y * 0.75;
-        let (outer_rect, response) =
-            ui.allocate_exact_size(vec2(desired_width, height), Sense::hover());
+        let thickness = ui.spacing().interact_size.y * 0.75;
+        let size = match orientation {
+            MeterOrientation::Horizontal => vec2(
+                desired_width.unwrap_or_else(|| ui.available_size_before_wrap().x.at_least(96.0)),
+                thickness,
+            ),
+            MeterOrientation::Vertical => vec2(
+                desired_width.unwrap_or(thickness * 2.0),
+                desired_height.unwrap_or(96.0),
+            ),
+        };
+        let (outer_rect, response) = ui.allocate_exact_size(size, Sense::hover());
 
         if ui.is_rect_visible(response.rect) {
             if animate {
@@ -101,15 +151,25 @@ impl Widget for DBMeter {
                 outer_rect,
                 rounding,
                 self.background_color,
-                Stroke::new(1.0, self.border_color),
+                Stroke::new(self.stroke_width, self.border_color),
             );
-            let inner_rect = Rect::from_min_size(
-                outer_rect.min,
-                vec2(
-                    (outer_rect.width() * level).at_least(outer_rect.height()),
-                    outer_rect.height(),
+            let inner_rect = match orientation {
+                MeterOrientation::Horizontal => Rect::from_min_size(
+                    outer_rect.min,
+                    vec2(
+                        (outer_rect.width() * level).at_least(outer_rect.height()),
+                        outer_rect.height(),
+                    ),
                 ),
-            );
+                MeterOrientation::Vertical => Rect::from_min_max(
+                    Pos2::new(
+                        outer_rect.left(),
+                        outer_rect.bottom()
+                            - (outer_rect.height() * level).at_least(outer_rect.width()),
+                    ),
+                    outer_rect.max,
+                ),
+            };
 
             ui.painter().rect(
                 inner_rect,
@@ -142,17 +202,36 @@ impl Widget for DBMeter {
             }
 
             // Markers
-            let marker_spacing = outer_rect.width() / 12.0;
-            let points_x = (outer_rect.left_bottom().x as i32..=outer_rect.right_bottom().x as i32)
-                .step_by(marker_spacing as usize);
-
-            for x in points_x {
-                let points: Vec<Pos2> = vec![
-                    Pos2::new(x as f32, outer_rect.left_bottom().y),
-                    Pos2::new(x as f32, outer_rect.left_bottom().y - 10.0),
-                ];
-                ui.painter()
-                    .add(Shape::line(points, Stroke::new(1.0, self.border_color)));
+            if orientation == MeterOrientation::Vertical {
+                let marker_spacing = outer_rect.height() / 12.0;
+                let points_y = (outer_rect.top() as i32..=outer_rect.bottom() as i32)
+                    .step_by(marker_spacing.at_least(1.0) as usize);
+                for y in points_y {
+                    let points: Vec<Pos2> = vec![
+                        Pos2::new(outer_rect.left(), y as f32),
+                        Pos2::new(outer_rect.left() + 6.0, y as f32),
+                    ];
+                    ui.painter().add(Shape::line(
+                        points,
+                        Stroke::new(self.stroke_width, self.border_color),
+                    ));
+                }
+            } else {
+                let marker_spacing = outer_rect.width() / 12.0;
+                let points_x = (outer_rect.left_bottom().x as i32
+                    ..=outer_rect.right_bottom().x as i32)
+                    .step_by(marker_spacing as usize);
+
+                for x in points_x {
+                    let points: Vec<Pos2> = vec![
+                        Pos2::new(x as f32, outer_rect.left_bottom().y),
+                        Pos2::new(x as f32, outer_rect.left_bottom().y - 10.0),
+                    ];
+                    ui.painter().add(Shape::line(
+                        points,
+                        Stroke::new(self.stroke_width, self.border_color),
+                    ));
+                }
             }
 
             if let Some(text_kind) = text {
@@ -161,14 +240,24 @@ impl Widget for DBMeter {
                     DBMeterText::Percentage => format!("{}%", (level * 100.0) as usize).into(),
                 };
                 let galley = text.into_galley(ui, Some(false), f32::INFINITY, TextStyle::Button);
-                let text_pos = outer_rect.left_center() + vec2(ui.spacing().item_spacing.x, 0.0);
+                // Vertical meters are too narrow for text down the side, it goes at the top
+                let (text_pos, align) = match orientation {
+                    MeterOrientation::Horizontal => (
+                        outer_rect.left_center() + vec2(ui.spacing().item_spacing.x, 0.0),
+                        Align2::LEFT_CENTER,
+                    ),
+                    MeterOrientation::Vertical => (
+                        outer_rect.center_top() + vec2(0.0, ui.spacing().item_spacing.y),
+                        Align2::CENTER_TOP,
+                    ),
+                };
                 let text_color = visuals.override_text_color.unwrap_or(self.border_color);
                 let temp: String = (galley.text()).to_string();
                 ui.painter().text(
                     text_pos,
-                    Align2::LEFT_CENTER,
+                    align,
                     temp,
-                    FontId::monospace(11.0),
+                    FontId::monospace(text_size),
                     text_color,
                 );
             }
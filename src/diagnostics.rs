@@ -0,0 +1,162 @@
+// diagnostics.rs - Ardura 2024
+// Optional diagnostic logging for "it exploded" reports. The audio thread only detects events
+// and hands them to the background executor, the actual nih_log! call happens off-thread.
+
+use nih_plug::nih_log;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Anything above this in the tap network is considered an extreme internal value (~+24 dB)
+const EXTREME_INTERNAL_LEVEL: f32 = 16.0;
+
+/// Don't report more often than this so a clipping session doesn't flood the log
+const MIN_REPORT_GAP_SECONDS: f32 = 0.25;
+
+pub enum DiagnosticEvent {
+    OutputClip {
+        sample_pos: i64,
+        channel: usize,
+        peak: f32,
+        suppressed: u32,
+    },
+    ExtremeValue {
+        sample_pos: i64,
+        channel: usize,
+        value: f32,
+        suppressed: u32,
+    },
+}
+
+/// The sound-shaping params at the moment of an event, copied on the audio thread so the log
+/// shows what was playing and not what the params are by the time the background task runs
+#[derive(Clone, Copy)]
+pub struct DiagnosticContext {
+    pub input_gain: f32,
+    pub push: f32,
+    pub multiplier: f32,
+    pub output_gain: f32,
+    pub dry_wet: f32,
+    pub coeffs: [f32; 8],
+    pub skews: [f32; 8],
+}
+
+impl std::fmt::Display for DiagnosticContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |values: &[f32; 8]| {
+            values
+                .iter()
+                .map(|value| format!("{value:.6}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        write!(
+            f,
+            "in {:.2} dB, push {:.4}, mult {:.4}, out {:.2} dB, wet {:.4}, coeffs [{}], skews [{}]",
+            nih_plug::util::gain_to_db(self.input_gain),
+            self.push,
+            self.multiplier,
+            nih_plug::util::gain_to_db(self.output_gain),
+            self.dry_wet,
+            join(&self.coeffs),
+            join(&self.skews)
+        )
+    }
+}
+
+pub struct DiagnosticsMonitor {
+    samples_since_report: u32,
+    min_report_gap: u32,
+    suppressed: u32,
+}
+
+impl DiagnosticsMonitor {
+    pub fn new() -> Self {
+        Self {
+            samples_since_report: u32::MAX,
+            min_report_gap: 11025,
+            suppressed: 0,
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.min_report_gap = (sample_rate * MIN_REPORT_GAP_SECONDS) as u32;
+    }
+
+    /// Check one frame. `internal` is the raw tap network output before the dry/wet sum.
+    pub fn check(
+        &mut self,
+        sample_pos: i64,
+        output: [f32; 2],
+        internal: [f32; 2],
+    ) -> Option<DiagnosticEvent> {
+        self.samples_since_report = self.samples_since_report.saturating_add(1);
+
+        let mut event = None;
+        for (channel, (&out, &inner)) in output.iter().zip(internal.iter()).enumerate() {
+            if !inner.is_finite() || inner.abs() > EXTREME_INTERNAL_LEVEL {
+                event = Some((channel, inner, true));
+                break;
+            }
+            if out.abs() > 1.0 {
+                event = Some((channel, out, false));
+            }
+        }
+
+        let (channel, value, extreme) = event?;
+        if self.samples_since_report < self.min_report_gap {
+            self.suppressed += 1;
+            return None;
+        }
+
+        let suppressed = self.suppressed;
+        self.suppressed = 0;
+        self.samples_since_report = 0;
+        Some(if extreme {
+            DiagnosticEvent::ExtremeValue {
+                sample_pos,
+                channel,
+                value,
+                suppressed,
+            }
+        } else {
+            DiagnosticEvent::OutputClip {
+                sample_pos,
+                channel,
+                peak: value.abs(),
+                suppressed,
+            }
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.samples_since_report = u32::MAX;
+        self.suppressed = 0;
+    }
+}
+
+/// Called from the background executor with the params captured when the event happened
+pub fn log_event(event: &DiagnosticEvent, param_context: &DiagnosticContext) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs_f64())
+        .unwrap_or(0.0);
+
+    match event {
+        DiagnosticEvent::OutputClip {
+            sample_pos,
+            channel,
+            peak,
+            suppressed,
+        } => nih_log!(
+            "[GladeDesk {timestamp:.3}] Output clip on channel {channel} at sample {sample_pos}: peak {:.2} dBFS ({suppressed} more since last report) | {param_context}",
+            20.0 * peak.log10()
+        ),
+        DiagnosticEvent::ExtremeValue {
+            sample_pos,
+            channel,
+            value,
+            suppressed,
+        } => nih_log!(
+            "[GladeDesk {timestamp:.3}] Extreme tap network value on channel {channel} at sample {sample_pos}: {value} ({suppressed} more since last report) | {param_context}"
+        ),
+    }
+}
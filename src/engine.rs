@@ -0,0 +1,716 @@
+// engine.rs - Ardura 2024
+// The console stage pulled out of process() so it can also be run from the GUI self test
+
+use nih_plug::prelude::Enum;
+use std::collections::VecDeque;
+
+/// Number of coefficient/skew sliders per bank, and the default tap count
+pub const TAPS: usize = 8;
+
+/// Range of the tap count param. Counts other than `TAPS` spread the sliders over the taps as
+/// a curve.
+pub const MIN_TAPS: usize = 4;
+pub const MAX_TAPS: usize = 32;
+
+/// Longest tap spacing param, ms
+pub const MAX_TAP_SPACING_MS: f32 = 0.5;
+
+/// Largest tap spacing in running samples, the longest spacing at 192 kHz with 8x oversampling
+pub const MAX_TAP_SPACING: usize = 768;
+
+/// The classic sign pattern the taps get summed with, the default for the polarity params
+pub const TAP_POLARITY: [f32; TAPS] = [1.0, 1.0, -1.0, 1.0, -1.0, 1.0, -1.0, 1.0];
+
+/// Sign one tap gets summed with in the console stage
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TapPolarity {
+    #[name = "+"]
+    Positive,
+    #[name = "-"]
+    Negative,
+}
+
+impl TapPolarity {
+    pub fn from_sign(sign: f32) -> Self {
+        if sign < 0.0 {
+            TapPolarity::Negative
+        } else {
+            TapPolarity::Positive
+        }
+    }
+
+    pub fn sign(self) -> f32 {
+        match self {
+            TapPolarity::Positive => 1.0,
+            TapPolarity::Negative => -1.0,
+        }
+    }
+
+    pub fn flipped(self) -> Self {
+        match self {
+            TapPolarity::Positive => TapPolarity::Negative,
+            TapPolarity::Negative => TapPolarity::Positive,
+        }
+    }
+}
+
+/// Largest sample the feedback send passes back, keeps high feedback ringing instead of
+/// running away
+pub const FEEDBACK_CLAMP: f32 = 1.0;
+
+/// Curve the Push stage blends in
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushCurve {
+    /// The original sine fold
+    #[name = "Sine"]
+    Sine,
+    #[name = "Tanh"]
+    Tanh,
+    #[name = "Arctan"]
+    Arctan,
+    /// x - x^3 / 3, flat past full scale
+    #[name = "Cubic"]
+    Cubic,
+    /// Triangle wavefolder, folds back at full scale instead of flattening
+    #[name = "Fold"]
+    Fold,
+}
+
+impl PushCurve {
+    pub fn shape(self, x: f64) -> f64 {
+        match self {
+            PushCurve::Sine => (x * 1.2).sin(),
+            PushCurve::Tanh => x.tanh(),
+            PushCurve::Arctan => x.atan(),
+            PushCurve::Cubic => {
+                if x.abs() <= 1.0 {
+                    x - x * x * x / 3.0
+                } else {
+                    x.signum() * 2.0 / 3.0
+                }
+            }
+            PushCurve::Fold => 1.0 - ((x + 1.0).rem_euclid(4.0) - 2.0).abs(),
+        }
+    }
+
+    /// Antiderivative of shape() for the ADAA path
+    fn antiderivative(self, x: f64) -> f64 {
+        match self {
+            PushCurve::Sine => -(x * 1.2).cos() / 1.2,
+            PushCurve::Tanh => ln_cosh(x),
+            PushCurve::Arctan => x * x.atan() - 0.5 * (x * x).ln_1p(),
+            PushCurve::Cubic => {
+                if x.abs() <= 1.0 {
+                    0.5 * x * x - x * x * x * x / 12.0
+                } else {
+                    2.0 / 3.0 * x.abs() - 0.25
+                }
+            }
+            // The fold is periodic with no DC so its antiderivative is too
+            PushCurve::Fold => {
+                let u = (x + 1.0).rem_euclid(4.0);
+                if u <= 2.0 {
+                    0.5 * u * u - u
+                } else {
+                    3.0 * u - 0.5 * u * u - 4.0
+                }
+            }
+        }
+    }
+}
+
+// ln(cosh(x)) written so it can't overflow
+fn ln_cosh(x: f64) -> f64 {
+    x.abs() + (-2.0 * x.abs()).exp().ln_1p() - std::f64::consts::LN_2
+}
+
+// How far off center the even path's tanh runs, like a single ended triode
+const EVEN_OFFSET: f64 = 0.5;
+
+// Asymmetric curve of the even path, zero at silence
+fn even_shape(x: f64) -> f64 {
+    (x + EVEN_OFFSET).tanh() - EVEN_OFFSET.tanh()
+}
+
+fn even_antiderivative(x: f64) -> f64 {
+    ln_cosh(x + EVEN_OFFSET) - EVEN_OFFSET.tanh() * x
+}
+
+/// Harmonics 2 to 8 in the Chebyshev designer
+pub const HARMONICS: usize = 7;
+
+/// Where the Chebyshev harmonic designer sits
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HarmonicMode {
+    #[name = "Off"]
+    Off,
+    /// Shapes the tap network output
+    #[name = "After Taps"]
+    After,
+    /// Skips the tap network, the wet signal is only the designed harmonics
+    #[name = "Replace Taps"]
+    Replace,
+}
+
+/// Harmonics a Chebyshev polynomial shaper adds to `x`. A full scale sine comes out with
+/// harmonic k at `levels[k - 2]`, the fundamental isn't included. Silence stays silent.
+pub fn chebyshev_harmonics(x: f32, levels: &[f32; HARMONICS]) -> f32 {
+    // The polynomials blow up outside -1..1
+    let x = x.clamp(-1.0, 1.0);
+    let mut previous = 1.0; // T0
+    let mut current = x; // T1
+    let mut sum = 0.0;
+    for (k, level) in (2..).zip(levels.iter()) {
+        let next = 2.0 * x * current - previous;
+        previous = current;
+        current = next;
+        // T_k(0) is cos(k * pi / 2), take it back out for the even orders
+        let at_zero = match k % 4 {
+            0 => 1.0,
+            2 => -1.0,
+            _ => 0.0,
+        };
+        sum += level * (current - at_zero);
+    }
+    sum
+}
+
+/// What the tap spacing follows
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpacingMode {
+    /// One host sample apart, the original sound which shifts with the sample rate
+    #[name = "Samples"]
+    Samples,
+    /// The spacing param in ms, the same texture at any rate
+    #[name = "ms"]
+    Milliseconds,
+}
+
+/// Host samples between taps. None keeps them one sample apart like the original desk.
+pub fn tap_spacing(spacing_ms: Option<f32>, sample_rate: f32) -> f32 {
+    spacing_ms.map_or(1.0, |ms| ms * 0.001 * sample_rate)
+}
+
+// History needed to read every tap at this spacing, plus one for the interpolation
+fn history_len(spacing: f32) -> usize {
+    ((MAX_TAPS - 1) as f32 * spacing).ceil() as usize + 2
+}
+
+/// Values for a single sample of processing, already smoothed by the caller
+#[derive(Clone, Copy)]
+pub struct ConsoleParams {
+    pub input_gain: f32,
+    pub push: f32,
+    pub multiplier: f32,
+    pub coeffs: [f32; TAPS],
+    pub skews: [f32; TAPS],
+    /// Sign per slider, `TAP_POLARITY` unless the user changed it
+    pub polarity: [f32; TAPS],
+    pub wet: f32,
+    pub output_gain: f32,
+    /// First order ADAA on the Push shaper
+    pub hq_shaper: bool,
+    pub push_curve: PushCurve,
+    /// Offset added before the Push shaper for even harmonics, 0 is symmetric
+    pub bias: f32,
+    /// Blend from the odd-symmetric Push curve (0) to the asymmetric even path (1)
+    pub even_odd: f32,
+    pub harmonic_mode: HarmonicMode,
+    /// Chebyshev designer levels for harmonics 2 to 8
+    pub harmonics: [f32; HARMONICS],
+    /// Taps actually summed, `MIN_TAPS` to `MAX_TAPS`
+    pub tap_count: usize,
+    /// Amount of the tap output sent back into the head of the history
+    pub feedback: f32,
+    /// One-pole lowpass on the feedback send, 0 is bright and higher darkens it
+    pub feedback_damping: f32,
+    /// One-pole lowpass on what the taps read, growing from none on the first tap to this on
+    /// the last, 0 leaves them all bright
+    pub tap_damping: f32,
+}
+
+impl ConsoleParams {
+    /// Same settings with per tap offsets added to the coefficients and skews
+    pub fn with_offsets(&self, coeff_offsets: &[f32; TAPS], skew_offsets: &[f32; TAPS]) -> Self {
+        let mut params = *self;
+        for (coeff, offset) in params.coeffs.iter_mut().zip(coeff_offsets.iter()) {
+            *coeff += offset;
+        }
+        for (skew, offset) in params.skews.iter_mut().zip(skew_offsets.iter()) {
+            *skew += offset;
+        }
+        params
+    }
+
+    /// The continuous settings part way from these to `target`, switches and modes are taken
+    /// from `target` straight away
+    pub fn blended(&self, target: &ConsoleParams, t: f32) -> Self {
+        let lerp = |from: f32, to: f32| from + (to - from) * t;
+        ConsoleParams {
+            input_gain: lerp(self.input_gain, target.input_gain),
+            push: lerp(self.push, target.push),
+            multiplier: lerp(self.multiplier, target.multiplier),
+            coeffs: std::array::from_fn(|i| lerp(self.coeffs[i], target.coeffs[i])),
+            skews: std::array::from_fn(|i| lerp(self.skews[i], target.skews[i])),
+            wet: lerp(self.wet, target.wet),
+            output_gain: lerp(self.output_gain, target.output_gain),
+            bias: lerp(self.bias, target.bias),
+            even_odd: lerp(self.even_odd, target.even_odd),
+            harmonics: std::array::from_fn(|i| lerp(self.harmonics[i], target.harmonics[i])),
+            feedback: lerp(self.feedback, target.feedback),
+            feedback_damping: lerp(self.feedback_damping, target.feedback_damping),
+            tap_damping: lerp(self.tap_damping, target.tap_damping),
+            ..*target
+        }
+    }
+
+    /// Coefficients and skews morphed from these towards `other` by `t` with the minimum
+    /// change law, everything else stays as it is here
+    pub fn morphed_towards(&self, other: &ConsoleParams, t: f32) -> Self {
+        let mut params = *self;
+        for (value, target) in params.coeffs.iter_mut().zip(other.coeffs.iter()) {
+            *value = minimum_change(*value, *target, t);
+        }
+        for (value, target) in params.skews.iter_mut().zip(other.skews.iter()) {
+            *value = minimum_change(*value, *target, t);
+        }
+        params
+    }
+
+    /// Signed coefficient and skew of one tap. With the default count that's just the slider
+    /// times its polarity, otherwise the eight signed sliders are read as a curve across the
+    /// taps and scaled so denser counts don't simply get louder.
+    pub fn tap_weight(&self, tap: usize) -> (f32, f32) {
+        if self.tap_count == TAPS {
+            return (
+                self.polarity[tap] * self.coeffs[tap],
+                self.polarity[tap] * self.skews[tap],
+            );
+        }
+        let position = tap as f32 * (TAPS - 1) as f32 / (self.tap_count - 1) as f32;
+        let index = (position as usize).min(TAPS - 2);
+        let frac = position - index as f32;
+        let signed = |values: &[f32; TAPS], i: usize| self.polarity[i] * values[i];
+        let lerp = |values: &[f32; TAPS]| {
+            signed(values, index) + (signed(values, index + 1) - signed(values, index)) * frac
+        };
+        let density = TAPS as f32 / self.tap_count as f32;
+        (lerp(&self.coeffs) * density, lerp(&self.skews) * density)
+    }
+
+    /// What one tap adds to the output when it reads `sample` from the history
+    pub fn tap_contribution(&self, tap: usize, sample: f32) -> f32 {
+        let (coeff, skew) = self.tap_weight(tap);
+        sample * (coeff * self.multiplier + skew * self.multiplier * sample.abs())
+    }
+
+    /// The Push stage: dry blended with the odd and even paths
+    fn shaper(&self, x: f64) -> f64 {
+        let push = self.push as f64;
+        let even_odd = self.even_odd as f64;
+        let curve = self.push_curve.shape(x) * (1.0 - even_odd) + even_shape(x) * even_odd;
+        (1.0 - push) * x + push * curve
+    }
+
+    /// Antiderivative of shaper() for the ADAA path
+    fn shaper_antiderivative(&self, x: f64) -> f64 {
+        let push = self.push as f64;
+        let even_odd = self.even_odd as f64;
+        let curve = self.push_curve.antiderivative(x) * (1.0 - even_odd)
+            + even_antiderivative(x) * even_odd;
+        (1.0 - push) * 0.5 * x * x + push * curve
+    }
+
+    /// Slider the tap's energy gets shown on in the heat map
+    pub fn energy_slot(&self, tap: usize) -> usize {
+        let spread = self.tap_count.max(2) - 1;
+        ((tap * (TAPS - 1) + spread / 2) / spread).min(TAPS - 1)
+    }
+}
+
+/// One channel of the console: push shaper followed by the tap network
+pub struct ConsoleChannel {
+    history: VecDeque<f32>,
+    // Samples between taps, fractional spacings are read with linear interpolation
+    spacing: f32,
+    // Last shaper input for the ADAA difference
+    previous_input: f64,
+    // Last dry sample, the dry steps back half a sample with the ADAA shaper
+    previous_dry: f32,
+    // Damped tap output waiting to be fed back in
+    feedback: f32,
+    // Squared contribution of each tap since the last take_tap_energy()
+    tap_energy: [f32; TAPS],
+    // Tap damping lowpass state, follows the undamped reads while damping is off
+    tap_lowpass: [f32; MAX_TAPS],
+}
+
+impl ConsoleChannel {
+    pub fn new() -> Self {
+        // Always long enough for the most taps so changing the count never allocates
+        let mut history = VecDeque::with_capacity(MAX_TAPS * MAX_TAP_SPACING);
+        history.resize(history_len(1.0), 0.0);
+        Self {
+            history,
+            spacing: 1.0,
+            previous_input: 0.0,
+            previous_dry: 0.0,
+            feedback: 0.0,
+            tap_energy: [0.0; TAPS],
+            tap_lowpass: [0.0; MAX_TAPS],
+        }
+    }
+
+    /// Change the distance between taps in running samples. The newest history is kept so it
+    /// can follow automation without clicking. Doesn't allocate.
+    pub fn set_spacing(&mut self, spacing: f32) {
+        let spacing = spacing.clamp(1.0, MAX_TAP_SPACING as f32);
+        if spacing != self.spacing {
+            self.spacing = spacing;
+            self.history.resize(history_len(spacing), 0.0);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.history.iter_mut().for_each(|sample| *sample = 0.0);
+        self.previous_input = 0.0;
+        self.previous_dry = 0.0;
+        self.feedback = 0.0;
+        self.tap_energy = [0.0; TAPS];
+        self.tap_lowpass = [0.0; MAX_TAPS];
+    }
+
+    /// Energy each tap contributed since the last call, then starts over
+    pub fn take_tap_energy(&mut self) -> [f32; TAPS] {
+        std::mem::replace(&mut self.tap_energy, [0.0; TAPS])
+    }
+
+    /// Run one input sample (after input gain) through the stage. Returns the denormal guarded
+    /// dry sample, lined up with the shaper, and the tap network output.
+    pub fn process_taps(&mut self, input: f32, params: &ConsoleParams) -> (f32, f32) {
+        // Normalize really small values
+        let mut input = input;
+        if input.abs() < 1.18e-23 {
+            input = 0.1 * 1.18e-17;
+        }
+
+        // Calculate our 'warmed' sample
+        let biased = input + params.bias;
+        let mut pushed = if params.hq_shaper {
+            self.push_adaa(biased, params)
+        } else {
+            params.shaper(biased as f64) as f32
+        };
+        // Take the bias's own offset back out so silence stays silent
+        if params.bias != 0.0 {
+            pushed -= params.shaper(params.bias as f64) as f32;
+        }
+        self.previous_input = biased as f64;
+
+        // Shift the buffer array, the feedback send goes in with the new sample
+        self.history
+            .push_front(pushed + self.feedback * params.feedback);
+        self.history.pop_back();
+
+        // Sequential process like the Airwindows Console emulations
+        let mut temp: f32 = 0.0;
+        let taps = if params.harmonic_mode == HarmonicMode::Replace {
+            0
+        } else {
+            params.tap_count
+        };
+        for tap in 0..taps {
+            let position = tap as f32 * self.spacing;
+            let index = position as usize;
+            let frac = position - index as f32;
+            let sample = if frac == 0.0 {
+                self.history[index]
+            } else {
+                self.history[index] + (self.history[index + 1] - self.history[index]) * frac
+            };
+            // Later taps lose more highs, like the longer paths of reflections
+            let lowpass = &mut self.tap_lowpass[tap];
+            let sample = if params.tap_damping > 0.0 {
+                let amount = params.tap_damping * tap as f32 / (params.tap_count - 1) as f32;
+                *lowpass += (sample - *lowpass) * (1.0 - amount);
+                *lowpass
+            } else {
+                *lowpass = sample;
+                sample
+            };
+            let contribution = params.tap_contribution(tap, sample);
+            self.tap_energy[params.energy_slot(tap)] += contribution * contribution;
+            temp += contribution;
+        }
+
+        match params.harmonic_mode {
+            HarmonicMode::Off => {}
+            HarmonicMode::After => temp += chebyshev_harmonics(temp, &params.harmonics),
+            HarmonicMode::Replace => temp = chebyshev_harmonics(pushed, &params.harmonics),
+        }
+
+        if params.feedback > 0.0 {
+            let send = temp.clamp(-FEEDBACK_CLAMP, FEEDBACK_CLAMP);
+            self.feedback += (send - self.feedback) * (1.0 - params.feedback_damping);
+        } else {
+            self.feedback = 0.0;
+        }
+
+        // The ADAA shaper runs half a sample late, hold the dry back to match
+        let dry = if params.hq_shaper {
+            0.5 * (input + self.previous_dry)
+        } else {
+            input
+        };
+        self.previous_dry = input;
+
+        (dry, temp)
+    }
+
+    /// First order antiderivative anti-aliasing of the push shaper. Trades a half sample of
+    /// delay and a touch of top end for much less foldback at high Push. Second order would need
+    /// a second antiderivative, which Tanh and the even path don't have in closed form.
+    fn push_adaa(&self, input: f32, params: &ConsoleParams) -> f32 {
+        let x = input as f64;
+        let x1 = self.previous_input;
+
+        let delta = x - x1;
+        if delta.abs() < 1.0e-5 {
+            // Too close to divide, use the shaper at the midpoint
+            params.shaper(0.5 * (x + x1)) as f32
+        } else {
+            ((params.shaper_antiderivative(x) - params.shaper_antiderivative(x1)) / delta) as f32
+        }
+    }
+
+    /// The full chain for offline use: gain, taps, dry + wet sum and output gain
+    pub fn process_sample(&mut self, input: f32, params: &ConsoleParams) -> f32 {
+        let (dry, taps) = self.process_taps(input * params.input_gain, params);
+        (dry + taps * params.wet) * params.output_gain
+    }
+}
+
+/// How the dual engine gets from bank A to bank B
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BankMorph {
+    /// Blend the two tap outputs, taps with opposite signs cancel half way
+    #[name = "Linear"]
+    Linear,
+    /// Blend each tap's size and flip its sign half way so no tap passes through zero
+    #[name = "Min Change"]
+    MinimumChange,
+}
+
+/// One value of a minimum change morph. Same signed values blend as usual, opposite signed ones
+/// keep their magnitude moving and jump sign at the midpoint instead of cancelling.
+pub fn minimum_change(a: f32, b: f32, t: f32) -> f32 {
+    if a * b >= 0.0 {
+        return a + (b - a) * t;
+    }
+    let magnitude = a.abs() + (b.abs() - a.abs()) * t;
+    if t < 0.5 {
+        magnitude.copysign(a)
+    } else {
+        magnitude.copysign(b)
+    }
+}
+
+/// Runs the same input through two channels (A and B banks) and blends them. `balance` 0 is
+/// all A, 1 is all B. The dry sample comes from A. With the minimum change morph A runs the
+/// morphed bank and B still runs its own so switching laws doesn't start it from stale state.
+pub fn process_dual_taps(
+    channel_a: &mut ConsoleChannel,
+    channel_b: &mut ConsoleChannel,
+    input: f32,
+    params_a: &ConsoleParams,
+    params_b: &ConsoleParams,
+    balance: f32,
+    morph: BankMorph,
+) -> (f32, f32) {
+    match morph {
+        BankMorph::Linear => {
+            let (dry, taps_a) = channel_a.process_taps(input, params_a);
+            let (_, taps_b) = channel_b.process_taps(input, params_b);
+            (dry, taps_a + (taps_b - taps_a) * balance)
+        }
+        BankMorph::MinimumChange => {
+            let morphed = params_a.morphed_towards(params_b, balance);
+            channel_b.process_taps(input, params_b);
+            channel_a.process_taps(input, &morphed)
+        }
+    }
+}
+
+/// How the dry and processed signals get combined
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MixLaw {
+    /// The original behavior: dry + taps * wet
+    #[name = "Additive"]
+    Additive,
+    /// Straight crossfade from the dry to the taps
+    #[name = "Linear"]
+    Linear,
+    /// Sine/cosine crossfade from the dry to the taps, constant power for uncorrelated signals
+    #[name = "Equal Power"]
+    EqualPower,
+}
+
+impl MixLaw {
+    /// `dry` is the input lined up with the tap network's latency, `taps` the tap network
+    /// output on its own. The crossfades never add the dry on top of the taps, so 0% is the
+    /// aligned dry and 100% is only the taps.
+    pub fn mix(self, dry: f32, taps: f32, amount: f32) -> f32 {
+        match self {
+            MixLaw::Additive => dry + taps * amount,
+            MixLaw::Linear => dry * (1.0 - amount) + taps * amount,
+            MixLaw::EqualPower => {
+                let angle = amount * std::f32::consts::FRAC_PI_2;
+                dry * angle.cos() + taps * angle.sin()
+            }
+        }
+    }
+}
+
+/// Reference level the nonlinear stages are lined up to. The console was voiced around
+/// -18 dBFS, hotter references pull the signal down going in and back up coming out.
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Calibration {
+    #[name = "-18 dBFS"]
+    Minus18,
+    #[name = "-14 dBFS"]
+    Minus14,
+    #[name = "-12 dBFS"]
+    Minus12,
+}
+
+impl Calibration {
+    /// Gain into the nonlinear stages, its inverse brings the level back afterwards
+    pub fn input_gain(self) -> f32 {
+        let reference_db: f32 = match self {
+            Calibration::Minus18 => -18.0,
+            Calibration::Minus14 => -14.0,
+            Calibration::Minus12 => -12.0,
+        };
+        10f32.powf((-18.0 - reference_db) / 20.0)
+    }
+}
+
+/// Channel and bus pairing like the Airwindows Console family: channel instances encode their
+/// output with a sine, the bus decodes the summed input with its arcsine. One channel straight
+/// into one bus comes back out at unity, summed channels pick up the console glue.
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleMode {
+    /// Neither, the original behavior
+    #[name = "Single"]
+    Single,
+    #[name = "Channel"]
+    Channel,
+    #[name = "Bus"]
+    Bus,
+}
+
+impl ConsoleMode {
+    /// Bus side, on the input before anything else
+    pub fn decode(self, sample: f32) -> f32 {
+        match self {
+            // The encoder never goes past +-1, anything past that is other audio on the bus
+            ConsoleMode::Bus => sample.clamp(-1.0, 1.0).asin(),
+            _ => sample,
+        }
+    }
+
+    /// Channel side, on the output after the output gain
+    pub fn encode(self, sample: f32) -> f32 {
+        match self {
+            // Held inside a quarter turn so the bus can still undo it
+            ConsoleMode::Channel => sample
+                .clamp(-std::f32::consts::FRAC_PI_2, std::f32::consts::FRAC_PI_2)
+                .sin(),
+            _ => sample,
+        }
+    }
+}
+
+/// Delay in host samples the ADAA shaper adds, half a sample at the rate the consoles run at
+pub fn adaa_delay(hq_shaper: bool, oversampling_factor: usize) -> f32 {
+    if hq_shaper {
+        0.5 / oversampling_factor as f32
+    } else {
+        0.0
+    }
+}
+
+/// Fractional delay of less than a sample for the dry path, so the dry the taps get summed
+/// against lines up with the ADAA shaper
+pub struct AdaaAligner {
+    previous: f32,
+}
+
+impl AdaaAligner {
+    pub fn new() -> Self {
+        Self { previous: 0.0 }
+    }
+
+    pub fn reset(&mut self) {
+        self.previous = 0.0;
+    }
+
+    pub fn process(&mut self, input: f32, delay: f32) -> f32 {
+        let output = input + (self.previous - input) * delay;
+        self.previous = input;
+        output
+    }
+}
+
+/// Results of running test signals through the console at fixed settings
+#[derive(Clone)]
+pub struct SelfTestReport {
+    pub impulse_finite: bool,
+    pub sine_finite: bool,
+    pub latency_samples: usize,
+    pub sine_peak_db: f32,
+    pub fir: Vec<f32>,
+}
+
+/// Run an impulse and a 1 kHz sine through a fresh channel with the given settings.
+/// `latency_samples` is what the plugin reports to the host, the impulse can't measure it since
+/// the dry path lands on sample 0 whatever the oversampling and lookahead add.
+/// This allocates so keep it off the audio thread.
+pub fn self_test(
+    params: &ConsoleParams,
+    sample_rate: f32,
+    latency_samples: usize,
+) -> SelfTestReport {
+    // Impulse response, small enough that the skew terms barely contribute
+    let impulse_level = 1.0e-3;
+    let mut channel = ConsoleChannel::new();
+    let impulse_response: Vec<f32> = (0..64)
+        .map(|i| {
+            let input = if i == 0 { impulse_level } else { 0.0 };
+            channel.process_sample(input, params)
+        })
+        .collect();
+
+    // Effective FIR of the wet path per tap, skews ignored at this level
+    let fir = (0..params.tap_count)
+        .map(|tap| params.tap_weight(tap).0 * params.multiplier)
+        .collect();
+
+    let mut channel = ConsoleChannel::new();
+    let mut sine_peak: f32 = 0.0;
+    let mut sine_finite = true;
+    for i in 0..(sample_rate as usize / 2) {
+        let input = 0.5 * (std::f32::consts::TAU * 1000.0 * i as f32 / sample_rate).sin();
+        let output = channel.process_sample(input, params);
+        sine_finite &= output.is_finite();
+        sine_peak = sine_peak.max(output.abs());
+    }
+
+    SelfTestReport {
+        impulse_finite: impulse_response.iter().all(|sample| sample.is_finite()),
+        sine_finite,
+        latency_samples,
+        sine_peak_db: 20.0 * sine_peak.max(1.0e-9).log10(),
+        fir,
+    }
+}
@@ -0,0 +1,58 @@
+// envelope.rs - Ardura 2024
+// Envelope follower on the console input for program dependent Push and skew, peak detection
+// with separate attack and release read out on a dB scale
+
+/// Levels at or below this read as 0, 0 dBFS reads as 1
+const FLOOR_DB: f32 = -60.0;
+
+pub struct EnvelopeFollower {
+    envelope: f32,
+    attack: f32,
+    release: f32,
+    sample_rate: f32,
+}
+
+impl EnvelopeFollower {
+    pub fn new() -> Self {
+        let mut follower = Self {
+            envelope: 0.0,
+            attack: 0.0,
+            release: 0.0,
+            sample_rate: 44100.0,
+        };
+        follower.set_times(5.0, 120.0);
+        follower
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
+    /// Cheap enough to call once per block
+    pub fn set_times(&mut self, attack_ms: f32, release_ms: f32) {
+        let coeff = |ms: f32| (-1.0 / (ms.max(0.01) * 0.001 * self.sample_rate)).exp();
+        self.attack = coeff(attack_ms);
+        self.release = coeff(release_ms);
+    }
+
+    pub fn reset(&mut self) {
+        self.envelope = 0.0;
+    }
+
+    /// Feed one stereo frame, returns the level from 0 at -60 dBFS to 1 at 0 dBFS
+    pub fn next(&mut self, left: f32, right: f32) -> f32 {
+        let peak = left.abs().max(right.abs());
+        let coeff = if peak > self.envelope {
+            self.attack
+        } else {
+            self.release
+        };
+        self.envelope = peak + (self.envelope - peak) * coeff;
+        self.value()
+    }
+
+    pub fn value(&self) -> f32 {
+        let db = 20.0 * self.envelope.max(1.0e-6).log10();
+        ((db - FLOOR_DB) / -FLOOR_DB).clamp(0.0, 1.0)
+    }
+}
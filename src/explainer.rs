@@ -0,0 +1,175 @@
+// explainer.rs - Ardura 2024
+// Teaching view: one impulse walks through the tap history and each tap's weighted
+// contribution is drawn as it lands in the output
+
+use nih_plug_egui::egui::{
+    vec2, Align2, Color32, FontId, Pos2, Rect, Response, Rounding, Sense, Stroke, Ui, Vec2, Widget,
+};
+
+use crate::engine::ConsoleParams;
+
+/// How long the impulse sits on each tap
+pub const SECONDS_PER_TAP: f64 = 0.8;
+
+/// Where the impulse is for an animation clock in seconds, in taps and looping with a short
+/// pause after the last one
+pub fn impulse_position(time: f64, tap_count: usize) -> f32 {
+    let cycle = (tap_count + 2) as f64;
+    ((time / SECONDS_PER_TAP) % cycle) as f32
+}
+
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct TapExplainer<'a> {
+    params: &'a ConsoleParams,
+    // In taps, 0 is the impulse arriving at tap 1
+    position: f32,
+    desired_size: Vec2,
+    color: Color32,
+    background_color: Color32,
+    font_scale: f32,
+}
+
+impl<'a> TapExplainer<'a> {
+    pub fn new(params: &'a ConsoleParams, position: f32) -> Self {
+        Self {
+            params,
+            position,
+            desired_size: vec2(256.0, 160.0),
+            color: Color32::YELLOW,
+            background_color: Color32::BLACK,
+            font_scale: 1.0,
+        }
+    }
+
+    pub fn desired_size(mut self, desired_size: Vec2) -> Self {
+        self.desired_size = desired_size;
+        self
+    }
+
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn background_color(mut self, color: Color32) -> Self {
+        self.background_color = color;
+        self
+    }
+
+    pub fn font_scale(mut self, font_scale: f32) -> Self {
+        self.font_scale = font_scale;
+        self
+    }
+}
+
+impl<'a> Widget for TapExplainer<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let (rect, response) = ui.allocate_exact_size(self.desired_size, Sense::hover());
+
+        if ui.is_rect_visible(rect) {
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, Rounding::from(2.0), self.background_color);
+
+            let taps = self.params.tap_count;
+            // A unit impulse reads 1.0 wherever it sits, so each tap adds (coeff + skew) * mult
+            let contributions: Vec<f32> = (0..taps)
+                .map(|tap| self.params.tap_contribution(tap, 1.0))
+                .collect();
+            let largest = contributions
+                .iter()
+                .fold(0.0f32, |largest, value| largest.max(value.abs()))
+                .max(1.0e-6);
+            let reached = (self.position.floor() as usize + 1).min(taps);
+            let current = self.position as usize;
+
+            let font = FontId::proportional(10.0 * self.font_scale);
+            let dim = self.color.linear_multiply(0.3);
+            let cell_width = (rect.width() - 8.0) / taps as f32;
+            let cell_x = |tap: usize| rect.left() + 4.0 + tap as f32 * cell_width;
+
+            // Top row: the history, the impulse slides from cell to cell
+            let history_rect = Rect::from_min_size(
+                rect.min + vec2(0.0, 4.0),
+                vec2(rect.width(), rect.height() * 0.2),
+            );
+            for tap in 0..taps {
+                let cell = Rect::from_min_size(
+                    Pos2::new(cell_x(tap) + 1.0, history_rect.top()),
+                    vec2(cell_width - 2.0, history_rect.height()),
+                );
+                painter.rect_stroke(cell, Rounding::from(2.0), Stroke::new(1.0, dim));
+                if taps <= 16 {
+                    painter.text(
+                        cell.center_bottom() + vec2(0.0, 1.0),
+                        Align2::CENTER_TOP,
+                        format!("{}", tap + 1),
+                        font.clone(),
+                        dim,
+                    );
+                }
+            }
+            if current < taps {
+                let x = cell_x(0) + (self.position.min(taps as f32 - 0.5) + 0.5) * cell_width;
+                painter.circle_filled(
+                    Pos2::new(x, history_rect.center().y),
+                    (history_rect.height() * 0.3).min(cell_width * 0.4),
+                    self.color,
+                );
+            }
+
+            // Middle: what each tap the impulse has passed adds, signed around a zero line
+            let bars_rect = Rect::from_min_max(
+                Pos2::new(rect.left(), history_rect.bottom() + 16.0 * self.font_scale),
+                Pos2::new(rect.right(), rect.bottom() - 22.0 * self.font_scale),
+            );
+            painter.line_segment(
+                [bars_rect.left_center(), bars_rect.right_center()],
+                Stroke::new(1.0, dim),
+            );
+            for (tap, contribution) in contributions.iter().enumerate().take(reached) {
+                let height = contribution / largest * (bars_rect.height() / 2.0 - 2.0);
+                let bar = Rect::from_two_pos(
+                    Pos2::new(cell_x(tap) + cell_width * 0.2, bars_rect.center().y),
+                    Pos2::new(
+                        cell_x(tap) + cell_width * 0.8,
+                        bars_rect.center().y - height,
+                    ),
+                );
+                let fill = if tap == current {
+                    self.color
+                } else {
+                    self.color.linear_multiply(0.5)
+                };
+                painter.rect_filled(bar, Rounding::ZERO, fill);
+            }
+
+            // Bottom: the running sum, which is the console's gain at DC once every tap is in
+            let sum: f32 = contributions.iter().take(reached).sum();
+            let status = if current < taps {
+                let (coeff, skew) = self.params.tap_weight(current);
+                format!(
+                    "Tap {}: ({:+.3} {:+.3}) x {:.2} = {:+.4}    Sum {:+.4}",
+                    current + 1,
+                    coeff,
+                    skew,
+                    self.params.multiplier,
+                    contributions[current],
+                    sum
+                )
+            } else {
+                format!("All {taps} taps summed: {sum:+.4} added to the dry signal")
+            };
+            painter.text(
+                Pos2::new(rect.left() + 4.0, rect.bottom() - 4.0),
+                Align2::LEFT_BOTTOM,
+                status,
+                font,
+                self.color,
+            );
+
+            painter.rect_stroke(rect, Rounding::from(2.0), Stroke::new(1.0, Color32::BLACK));
+        }
+
+        response
+    }
+}
@@ -0,0 +1,250 @@
+// filters.rs - Ardura 2024
+// Small utility filters used around the console stage
+
+use nih_plug::prelude::Enum;
+
+/// One pole DC blocking high-pass: y[n] = x[n] - x[n-1] + R * y[n-1]
+pub struct DcBlocker {
+    previous_input: f32,
+    previous_output: f32,
+    r: f32,
+}
+
+impl DcBlocker {
+    pub fn new() -> Self {
+        Self {
+            previous_input: 0.0,
+            previous_output: 0.0,
+            r: 0.995,
+        }
+    }
+
+    pub fn set_cutoff(&mut self, cutoff_hz: f32, sample_rate: f32) {
+        self.r = (-std::f32::consts::TAU * cutoff_hz / sample_rate).exp();
+    }
+
+    pub fn process(&mut self, input: f32) -> f32 {
+        let output = input - self.previous_input + self.r * self.previous_output;
+        self.previous_input = input;
+        self.previous_output = output;
+        output
+    }
+
+    pub fn reset(&mut self) {
+        self.previous_input = 0.0;
+        self.previous_output = 0.0;
+    }
+}
+
+/// Second order section from the RBJ cookbook, transposed direct form II
+#[derive(Clone, Copy)]
+pub struct Biquad {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    z1: f32,
+    z2: f32,
+}
+
+/// Q of a second order Butterworth section, two in a row make a Linkwitz-Riley crossover
+pub const BUTTERWORTH_Q: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+impl Biquad {
+    /// Starts out passing everything through untouched
+    pub fn new() -> Self {
+        Self {
+            b0: 1.0,
+            b1: 0.0,
+            b2: 0.0,
+            a1: 0.0,
+            a2: 0.0,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    // Shared part of the cookbook formulas: (cos w0, alpha)
+    fn prewarp(cutoff_hz: f32, q: f32, sample_rate: f32) -> (f32, f32) {
+        let w0 = std::f32::consts::TAU * cutoff_hz.clamp(10.0, sample_rate * 0.45) / sample_rate;
+        (w0.cos(), w0.sin() / (2.0 * q))
+    }
+
+    fn set_coefficients(&mut self, b: [f32; 3], a: [f32; 3]) {
+        self.b0 = b[0] / a[0];
+        self.b1 = b[1] / a[0];
+        self.b2 = b[2] / a[0];
+        self.a1 = a[1] / a[0];
+        self.a2 = a[2] / a[0];
+    }
+
+    pub fn set_lowpass(&mut self, cutoff_hz: f32, q: f32, sample_rate: f32) {
+        let (cos, alpha) = Self::prewarp(cutoff_hz, q, sample_rate);
+        self.set_coefficients(
+            [(1.0 - cos) * 0.5, 1.0 - cos, (1.0 - cos) * 0.5],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        );
+    }
+
+    pub fn set_highpass(&mut self, cutoff_hz: f32, q: f32, sample_rate: f32) {
+        let (cos, alpha) = Self::prewarp(cutoff_hz, q, sample_rate);
+        self.set_coefficients(
+            [(1.0 + cos) * 0.5, -(1.0 + cos), (1.0 + cos) * 0.5],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        );
+    }
+
+    pub fn set_allpass(&mut self, cutoff_hz: f32, q: f32, sample_rate: f32) {
+        let (cos, alpha) = Self::prewarp(cutoff_hz, q, sample_rate);
+        self.set_coefficients(
+            [1.0 - alpha, -2.0 * cos, 1.0 + alpha],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        );
+    }
+
+    pub fn process(&mut self, input: f32) -> f32 {
+        let output = self.b0 * input + self.z1;
+        self.z1 = self.b1 * input - self.a1 * output + self.z2;
+        self.z2 = self.b2 * input - self.a2 * output;
+        output
+    }
+
+    pub fn reset(&mut self) {
+        self.z1 = 0.0;
+        self.z2 = 0.0;
+    }
+}
+
+/// Steepness of the input filters
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterSlope {
+    #[name = "Off"]
+    Off,
+    #[name = "12 dB/oct"]
+    Db12,
+    #[name = "24 dB/oct"]
+    Db24,
+}
+
+// Section Qs of a fourth order Butterworth, so 24 dB is still -3 dB at the cutoff
+const BUTTERWORTH_4_Q: [f32; 2] = [0.541_196_1, 1.306_563];
+
+/// Butterworth high or low pass of up to two sections, for cleaning up the input
+pub struct SlopeFilter {
+    sections: [Biquad; 2],
+    highpass: bool,
+    slope: FilterSlope,
+    cutoff_hz: f32,
+    sample_rate: f32,
+}
+
+impl SlopeFilter {
+    pub fn new(highpass: bool) -> Self {
+        Self {
+            sections: [Biquad::new(); 2],
+            highpass,
+            slope: FilterSlope::Off,
+            cutoff_hz: 0.0,
+            sample_rate: 0.0,
+        }
+    }
+
+    /// Only redesigns when something actually moved, switching the slope clears the state
+    pub fn set(&mut self, slope: FilterSlope, cutoff_hz: f32, sample_rate: f32) {
+        if slope != self.slope {
+            self.reset();
+        }
+        if (slope, cutoff_hz, sample_rate) == (self.slope, self.cutoff_hz, self.sample_rate) {
+            return;
+        }
+        self.slope = slope;
+        self.cutoff_hz = cutoff_hz;
+        self.sample_rate = sample_rate;
+        let qs = match slope {
+            FilterSlope::Off => return,
+            FilterSlope::Db12 => [BUTTERWORTH_Q, BUTTERWORTH_Q],
+            FilterSlope::Db24 => BUTTERWORTH_4_Q,
+        };
+        for (section, q) in self.sections.iter_mut().zip(qs) {
+            if self.highpass {
+                section.set_highpass(cutoff_hz, q, sample_rate);
+            } else {
+                section.set_lowpass(cutoff_hz, q, sample_rate);
+            }
+        }
+    }
+
+    pub fn process(&mut self, input: f32) -> f32 {
+        match self.slope {
+            FilterSlope::Off => input,
+            FilterSlope::Db12 => self.sections[0].process(input),
+            FilterSlope::Db24 => self
+                .sections
+                .iter_mut()
+                .fold(input, |sample, section| section.process(sample)),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        for section in self.sections.iter_mut() {
+            section.reset();
+        }
+    }
+}
+
+/// Where the tilt EQ sits relative to the console
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TiltPlacement {
+    /// Shapes what the shaper and taps see
+    #[name = "Pre Console"]
+    Pre,
+    /// Shapes the result
+    #[name = "Post Console"]
+    Post,
+}
+
+/// First order tilt: everything below the pivot goes down by half the tilt and everything above
+/// goes up by the other half, or the other way round for negative tilts
+pub struct TiltEq {
+    // One pole lowpass splitting at the pivot
+    coeff: f32,
+    low: f32,
+    tilt_db: f32,
+    low_gain: f32,
+    high_gain: f32,
+}
+
+impl TiltEq {
+    pub fn new() -> Self {
+        Self {
+            coeff: 0.0,
+            low: 0.0,
+            tilt_db: 0.0,
+            low_gain: 1.0,
+            high_gain: 1.0,
+        }
+    }
+
+    pub fn set_pivot(&mut self, pivot_hz: f32, sample_rate: f32) {
+        self.coeff = 1.0 - (-std::f32::consts::TAU * pivot_hz / sample_rate).exp();
+    }
+
+    /// Tilt in dB, positive brightens
+    pub fn set_tilt(&mut self, tilt_db: f32) {
+        if tilt_db != self.tilt_db {
+            self.tilt_db = tilt_db;
+            self.low_gain = 10f32.powf(-tilt_db / 40.0);
+            self.high_gain = 10f32.powf(tilt_db / 40.0);
+        }
+    }
+
+    pub fn process(&mut self, input: f32) -> f32 {
+        self.low += (input - self.low) * self.coeff;
+        self.low * self.low_gain + (input - self.low) * self.high_gain
+    }
+
+    pub fn reset(&mut self) {
+        self.low = 0.0;
+    }
+}
@@ -0,0 +1,169 @@
+// gain_match.rs - Ardura 2024
+// One-shot gain match: measures input and output loudness for a couple of seconds and hands the
+// GUI the output gain change that levels them, which it then sets like any other param move
+
+use atomic_float::AtomicF32;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Seconds of signal to average over
+const MEASURE_SECONDS: f32 = 2.0;
+
+/// Give up if there still wasn't enough signal after this long
+const TIMEOUT_SECONDS: f32 = 10.0;
+
+/// Frames below -60 dBFS on both sides don't count towards the measurement
+const GATE_RMS: f32 = 0.001;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum GainMatchState {
+    Idle,
+    // Set by the GUI, picked up at the start of the next block
+    Requested,
+    Measuring,
+    // Correction is ready to be applied
+    Done,
+    // Timed out without enough signal
+    NoSignal,
+}
+
+impl GainMatchState {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => GainMatchState::Requested,
+            2 => GainMatchState::Measuring,
+            3 => GainMatchState::Done,
+            4 => GainMatchState::NoSignal,
+            _ => GainMatchState::Idle,
+        }
+    }
+}
+
+/// Shared between the GUI and the audio thread
+pub struct GainMatch {
+    state: AtomicU8,
+    correction_db: AtomicF32,
+}
+
+impl GainMatch {
+    pub fn new() -> Self {
+        Self {
+            state: AtomicU8::new(GainMatchState::Idle as u8),
+            correction_db: AtomicF32::new(0.0),
+        }
+    }
+
+    pub fn state(&self) -> GainMatchState {
+        GainMatchState::from_u8(self.state.load(Ordering::Relaxed))
+    }
+
+    /// Start a new measurement, restarts one already running
+    pub fn request(&self) {
+        self.state
+            .store(GainMatchState::Requested as u8, Ordering::Relaxed);
+    }
+
+    /// The output gain change in dB once a measurement finished, only handed out once
+    pub fn take_correction(&self) -> Option<f32> {
+        self.state
+            .compare_exchange(
+                GainMatchState::Done as u8,
+                GainMatchState::Idle as u8,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .ok()
+            .map(|_| self.correction_db.load(Ordering::Relaxed))
+    }
+
+    /// Audio thread side: true if the GUI asked for a measurement since the last block
+    pub fn take_request(&self) -> bool {
+        self.state
+            .compare_exchange(
+                GainMatchState::Requested as u8,
+                GainMatchState::Measuring as u8,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+    }
+
+    fn finish(&self, correction_db: Option<f32>) {
+        match correction_db {
+            Some(db) => {
+                self.correction_db.store(db, Ordering::Relaxed);
+                self.state
+                    .store(GainMatchState::Done as u8, Ordering::Relaxed);
+            }
+            None => self
+                .state
+                .store(GainMatchState::NoSignal as u8, Ordering::Relaxed),
+        }
+    }
+}
+
+/// Audio thread half, sums the energy while a measurement runs
+pub struct GainMatchMeter {
+    running: bool,
+    input_square: f64,
+    output_square: f64,
+    counted: u32,
+    needed: u32,
+    elapsed: u32,
+    timeout: u32,
+}
+
+impl GainMatchMeter {
+    pub fn new() -> Self {
+        Self {
+            running: false,
+            input_square: 0.0,
+            output_square: 0.0,
+            counted: 0,
+            needed: 0,
+            elapsed: 0,
+            timeout: 0,
+        }
+    }
+
+    pub fn start(&mut self, sample_rate: f32) {
+        self.running = true;
+        self.input_square = 0.0;
+        self.output_square = 0.0;
+        self.counted = 0;
+        self.elapsed = 0;
+        self.needed = (MEASURE_SECONDS * sample_rate) as u32;
+        self.timeout = (TIMEOUT_SECONDS * sample_rate) as u32;
+    }
+
+    /// Dropped without a result, like when playback stops
+    pub fn cancel(&mut self, shared: &GainMatch) {
+        if self.running {
+            self.running = false;
+            shared.finish(None);
+        }
+    }
+
+    /// Feed the untouched input frame and the final output it became
+    pub fn next(&mut self, input: [f32; 2], output: [f32; 2], shared: &GainMatch) {
+        if !self.running {
+            return;
+        }
+        let input_square = 0.5 * (input[0] * input[0] + input[1] * input[1]);
+        let output_square = 0.5 * (output[0] * output[0] + output[1] * output[1]);
+        if input_square > GATE_RMS * GATE_RMS || output_square > GATE_RMS * GATE_RMS {
+            self.input_square += input_square as f64;
+            self.output_square += output_square as f64;
+            self.counted += 1;
+        }
+        self.elapsed += 1;
+
+        if self.counted >= self.needed {
+            self.running = false;
+            let correction = (self.input_square > 0.0 && self.output_square > 0.0)
+                .then(|| (10.0 * (self.input_square / self.output_square).log10()) as f32);
+            shared.finish(correction);
+        } else if self.elapsed >= self.timeout {
+            self.cancel(shared);
+        }
+    }
+}
@@ -0,0 +1,290 @@
+// gui_settings.rs - Ardura 2024
+// Editor preferences that get saved with the plugin state
+
+use nih_plug_egui::egui::Color32;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Range for the global font scale, anything bigger runs out of room in the window
+pub const FONT_SCALE_RANGE: std::ops::RangeInclusive<f32> = 1.0..=1.5;
+
+/// Range for how many decimals the coefficient/skew sliders show
+pub const SLIDER_DECIMALS_RANGE: std::ops::RangeInclusive<usize> = 2..=6;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GuiSettings {
+    /// Black background, bright accents and thicker strokes
+    pub high_contrast: bool,
+    /// No meter ballistics and no moving visualizations
+    pub reduced_motion: bool,
+    /// Multiplier on every font size in the editor
+    pub font_scale: f32,
+    /// Decimal places shown on the coefficient/skew sliders
+    pub slider_decimals: usize,
+    pub coeff_display: CoeffDisplay,
+    pub meter_layout: MeterLayout,
+    /// How often the meters and scopes redraw
+    pub frame_rate: FrameRate,
+}
+
+impl Default for GuiSettings {
+    fn default() -> Self {
+        Self {
+            high_contrast: false,
+            reduced_motion: false,
+            font_scale: 1.0,
+            slider_decimals: 6,
+            coeff_display: CoeffDisplay::Raw,
+            meter_layout: MeterLayout::Stacked,
+            frame_rate: FrameRate::Fps60,
+        }
+    }
+}
+
+/// How the input and output meters are laid out
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MeterLayout {
+    /// Full width bars stacked under each other, the original look
+    Stacked,
+    /// Input and output standing side by side
+    Vertical,
+}
+
+impl MeterLayout {
+    pub const ALL: [MeterLayout; 2] = [MeterLayout::Stacked, MeterLayout::Vertical];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MeterLayout::Stacked => "Stacked bars",
+            MeterLayout::Vertical => "Vertical pair",
+        }
+    }
+}
+
+/// Editor refresh rate, lower saves battery at the cost of smoother meters
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FrameRate {
+    Fps15,
+    Fps30,
+    Fps60,
+}
+
+impl FrameRate {
+    pub const ALL: [FrameRate; 3] = [FrameRate::Fps15, FrameRate::Fps30, FrameRate::Fps60];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FrameRate::Fps15 => "15 fps",
+            FrameRate::Fps30 => "30 fps",
+            FrameRate::Fps60 => "60 fps",
+        }
+    }
+
+    /// Time between scheduled repaints
+    pub fn interval(self) -> std::time::Duration {
+        let fps = match self {
+            FrameRate::Fps15 => 15,
+            FrameRate::Fps30 => 30,
+            FrameRate::Fps60 => 60,
+        };
+        std::time::Duration::from_secs(1) / fps
+    }
+}
+
+/// How the coefficient/skew sliders show their values
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CoeffDisplay {
+    /// The plain multiplier, what the DSP sees
+    Raw,
+    /// The multiplier as a percentage, 0.12 is 12%
+    Percent,
+    /// Gain of the tap's contribution, with a polarity mark when it's negative
+    Decibels,
+}
+
+impl CoeffDisplay {
+    pub const ALL: [CoeffDisplay; 3] =
+        [CoeffDisplay::Raw, CoeffDisplay::Percent, CoeffDisplay::Decibels];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CoeffDisplay::Raw => "Raw",
+            CoeffDisplay::Percent => "Percent",
+            CoeffDisplay::Decibels => "dB",
+        }
+    }
+}
+
+/// The slider readout settings mirrored into atomics, the param formatters can't take the
+/// settings lock
+pub struct SliderReadout {
+    decimals: AtomicUsize,
+    display: AtomicUsize,
+}
+
+impl SliderReadout {
+    pub fn new() -> Self {
+        Self {
+            decimals: AtomicUsize::new(6),
+            display: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn store(&self, settings: &GuiSettings) {
+        self.decimals
+            .store(settings.slider_decimals, Ordering::Relaxed);
+        let display = CoeffDisplay::ALL
+            .iter()
+            .position(|display| *display == settings.coeff_display)
+            .unwrap_or(0);
+        self.display.store(display, Ordering::Relaxed);
+    }
+
+    pub fn format(&self, value: f32) -> String {
+        let decimals = self.decimals.load(Ordering::Relaxed);
+        match CoeffDisplay::ALL[self.display.load(Ordering::Relaxed).min(2)] {
+            CoeffDisplay::Raw => format!("{value:.decimals$}"),
+            CoeffDisplay::Percent => {
+                format!("{:.*}%", decimals.saturating_sub(2), value * 100.0)
+            }
+            CoeffDisplay::Decibels => {
+                if value == 0.0 {
+                    return "-inf dB".to_string();
+                }
+                let polarity = if value < 0.0 { "Ø " } else { "" };
+                format!("{polarity}{:.2} dB", 20.0 * value.abs().log10())
+            }
+        }
+    }
+}
+
+/// Reads a slider value typed in any of the display formats: `0.12`, `12%`, `-18.4 dB` or
+/// `Ø -18.4 dB` for a negative coefficient
+pub fn parse_coeff(text: &str) -> Option<f32> {
+    let text = text.trim();
+    if let Some(percent) = text.strip_suffix('%') {
+        return percent.trim().parse::<f32>().ok().map(|value| value / 100.0);
+    }
+    if let Some(db) = text
+        .strip_suffix("dB")
+        .or_else(|| text.strip_suffix("db"))
+    {
+        let (sign, db) = match db.trim().strip_prefix(['Ø', 'ø']) {
+            Some(db) => (-1.0, db.trim()),
+            None => (1.0, db.trim()),
+        };
+        if db == "-inf" {
+            return Some(0.0);
+        }
+        return db
+            .parse::<f32>()
+            .ok()
+            .map(|db| sign * 10f32.powf(db / 20.0));
+    }
+    text.parse::<f32>().ok()
+}
+
+/// The colors and stroke widths the editor draws with
+#[derive(Clone, Copy)]
+pub struct Theme {
+    pub background: Color32,
+    pub accent: Color32,
+    pub inside: Color32,
+    pub stroke_width: f32,
+    pub knob_line_scale: f32,
+}
+
+impl GuiSettings {
+    /// Picks between the normal theme passed in and the high contrast one
+    pub fn theme(&self, normal: Theme) -> Theme {
+        if self.high_contrast {
+            Theme {
+                background: Color32::BLACK,
+                accent: Color32::from_rgb(255, 214, 0),
+                inside: Color32::WHITE,
+                stroke_width: normal.stroke_width * 2.0,
+                knob_line_scale: normal.knob_line_scale * 1.5,
+            }
+        } else {
+            normal
+        }
+    }
+}
+
+/// Name and color shown in the header so instances can be told apart
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InstanceTag {
+    pub name: String,
+    /// sRGB color of the tag, None leaves the header untinted
+    pub color: Option<[u8; 3]>,
+}
+
+impl InstanceTag {
+    /// Default color when a tag color gets switched on
+    pub const DEFAULT_COLOR: [u8; 3] = [64, 160, 255];
+
+    pub fn is_empty(&self) -> bool {
+        self.name.trim().is_empty() && self.color.is_none()
+    }
+
+    pub fn color32(&self) -> Option<Color32> {
+        self.color.map(|[r, g, b]| Color32::from_rgb(r, g, b))
+    }
+}
+
+/// Channel strip header naming where the signal comes from and goes to, organizational only
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoutingLabels {
+    pub show: bool,
+    pub from: String,
+    pub to: String,
+}
+
+impl RoutingLabels {
+    /// Header text, None when it's off or both labels are blank
+    pub fn header(&self) -> Option<String> {
+        let (from, to) = (self.from.trim(), self.to.trim());
+        if !self.show || (from.is_empty() && to.is_empty()) {
+            return None;
+        }
+        Some(match (from.is_empty(), to.is_empty()) {
+            (false, false) => format!("From: {from}  \u{2192}  To: {to}"),
+            (false, true) => format!("From: {from}"),
+            _ => format!("To: {to}"),
+        })
+    }
+}
+
+/// Black or white, whichever reads better on top of `background`
+pub fn contrasting_text(background: Color32) -> Color32 {
+    let luma = 0.299 * background.r() as f32
+        + 0.587 * background.g() as f32
+        + 0.114 * background.b() as f32;
+    if luma > 140.0 {
+        Color32::BLACK
+    } else {
+        Color32::WHITE
+    }
+}
+
+/// How many knobs the top row holds
+pub const TOP_KNOB_COUNT: usize = 5;
+
+/// Param ids pinned to the top row knobs, left to right
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TopKnobs {
+    pub ids: [String; TOP_KNOB_COUNT],
+}
+
+impl Default for TopKnobs {
+    fn default() -> Self {
+        Self {
+            ids: ["free_gain", "Push", "Multiplier", "output_gain", "dry_wet"]
+                .map(|id| id.to_string()),
+        }
+    }
+}
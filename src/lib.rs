@@ -1,6 +1,47 @@
 #![allow(non_snake_case)]
+mod alert_output;
+mod auto_gain;
+mod auto_mult;
+mod channel_meters;
+mod crash_guard;
+mod crosstalk;
 mod db_meter;
+mod diagnostics;
+mod engine;
+mod envelope;
+mod explainer;
+mod filters;
+mod gain_match;
+mod gui_settings;
+mod lofi;
+mod macros;
+mod modulation;
+mod mod_matrix;
+mod mod_output;
+mod mod_scope;
+mod morph_capture;
+mod multiband;
+mod note_gate;
+mod null_test;
+mod numeric_entry;
+mod offline;
+mod oversampling;
+mod presence;
+mod quality;
+mod preset_import;
+mod relative_drag;
+mod safety_limiter;
+mod status_bar;
+mod tap_grid;
+mod tap_heatmap;
+mod tape;
+mod transformer;
+mod true_peak;
 mod ui_knob;
+mod user_config;
+mod viz_buffers;
+mod wav;
+mod wow_flutter;
 use atomic_float::AtomicF32;
 use nih_plug::prelude::*;
 use nih_plug_egui::{
@@ -8,7 +49,13 @@ use nih_plug_egui::{
     egui::{self, Color32, FontId, Pos2, Rect, RichText, Rounding},
     widgets, EguiState,
 };
-use std::{collections::VecDeque, ops::RangeInclusive, sync::Arc};
+use std::{
+    ops::RangeInclusive,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize},
+        Arc, RwLock,
+    },
+};
 
 /***************************************************************************
  * Glade Desk by Ardura
@@ -21,9 +68,33 @@ const A_KNOB_OUTSIDE_COLOR: Color32 = Color32::from_rgb(252, 163, 17);
 const A_BACKGROUND_COLOR: Color32 = Color32::from_rgb(20, 33, 61);
 const A_KNOB_INSIDE_COLOR: Color32 = Color32::from_rgb(100, 100, 100);
 
+const NORMAL_THEME: gui_settings::Theme = gui_settings::Theme {
+    background: A_BACKGROUND_COLOR,
+    accent: A_KNOB_OUTSIDE_COLOR,
+    inside: A_KNOB_INSIDE_COLOR,
+    stroke_width: 1.0,
+    knob_line_scale: 1.0,
+};
+
 // Plugin sizing
 const WIDTH: u32 = 532;
-const HEIGHT: u32 = 400;
+const HEIGHT: u32 = 510;
+
+/// Starting state for the random modulation generator until a state picks its own
+const RANDOM_MOD_SEED: u32 = 0x6C61_6465;
+
+// Fresh seed for the "New seed" button, any bits that differ between presses do
+fn new_seed() -> u32 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    (now.subsec_nanos() ^ (now.as_secs() as u32).rotate_left(16)).max(1)
+}
+
+// Analog drift: most a coefficient wanders at full amount (+/-10%), and how fast each tap's
+// wander moves in Hz, all different so the taps never drift together
+const MAX_DRIFT: f32 = 0.1;
+const DRIFT_RATES: [f32; engine::TAPS] = [0.05, 0.071, 0.062, 0.083, 0.057, 0.094, 0.066, 0.078];
 
 /// The time it takes for the peak meter to decay by 12 dB after switching to complete silence.
 const PEAK_METER_DECAY_MS: f64 = 100.0;
@@ -34,13 +105,269 @@ pub struct GladeDesk {
     // normalize the peak meter's response based on the sample rate with this
     out_meter_decay_weight: f32,
 
-    // Buffers
-    left_vec: VecDeque<f32>,
-    right_vec: VecDeque<f32>,
+    // Console stage per channel
+    left_console: engine::ConsoleChannel,
+    right_console: engine::ConsoleChannel,
+
+    // The current data for the different meters, one level per channel
+    out_meter: Arc<channel_meters::ChannelMeters>,
+    in_meter: Arc<channel_meters::ChannelMeters>,
+    // Sample clock the meter sub-blocks are stamped with, and the sub-block length
+    meter_clock: u64,
+    meter_sub_block: usize,
+    // Signal present LEDs and auto standby
+    presence: presence::PresenceDetector,
+    signal_presence: Arc<presence::SignalPresence>,
+
+    // MIDI note gate for the wet signal
+    note_gate: note_gate::NoteGate,
+
+    // Clip/extreme value detection for the diagnostic log
+    diagnostics: diagnostics::DiagnosticsMonitor,
+
+    sample_rate: f32,
+    session_sample_rate: Arc<AtomicF32>,
+    // Shown in the session info row
+    max_buffer_size: Arc<AtomicUsize>,
+    latency_samples: Arc<AtomicUsize>,
+
+    // Mirrored from the GUI settings, skips meter ballistics when set
+    reduced_motion: Arc<AtomicBool>,
+    // Mirrored from the user config, trims the output by the state's measured loudness
+    normalize_loudness: Arc<AtomicBool>,
+
+    // Random modulation source
+    random_mod: modulation::RandomSource,
+    // Per tap wander for analog drift, never written back to the params
+    drift: [modulation::RandomSource; engine::TAPS],
+    // Seed the random sources last started from, and whether the transport was rolling last
+    // block so they start over with it
+    active_seed: u32,
+    was_playing: bool,
+    // Tempo syncable LFO
+    lfo: modulation::Lfo,
+    // Modulation sent out to the host as MIDI CCs
+    mod_output: mod_output::ModOutput,
+    // Clip and overload alerts sent out as MIDI. Resetting only switches them off, the note
+    // offs go out with the next block
+    alert_output: alert_output::AlertOutput,
+
+    // Per tap energy of the last block for the heat map
+    tap_energy: Arc<[AtomicF32; engine::TAPS]>,
+
+    // Latest random mod output after depth for the scope
+    mod_scope_value: Arc<AtomicF32>,
+
+    // Heat map and scope histories, only allocated while the editor is open
+    viz_buffers: Arc<viz_buffers::VizStore>,
+    editor_was_open: bool,
+
+    // Engages by itself on a clip storm, the GUI shows a warning and can dismiss it
+    safety_limiter: safety_limiter::SafetyLimiter,
+    safety_engaged: Arc<AtomicBool>,
+    safety_dismiss: Arc<AtomicBool>,
+
+    // Scene crossfade: the console settings the last sample ran with, and the ones a state
+    // load is fading away from
+    last_console_params: Option<engine::ConsoleParams>,
+    scene_fade_from: Option<engine::ConsoleParams>,
+    scene_fade_pos: u32,
+    scene_fade_len: u32,
+
+    // Set when the last session crashed mid-processing, bypasses the shaper and feedback until
+    // the user restores the saved state from the banner
+    safe_mode: Arc<AtomicBool>,
+    crash_guard_active: bool,
+
+    // Lo-fi rate reduction around the tap network
+    left_lofi: lofi::RateReducer,
+    right_lofi: lofi::RateReducer,
+
+    // Bank B consoles for the dual engine, only run while it's on
+    left_console_b: engine::ConsoleChannel,
+    right_console_b: engine::ConsoleChannel,
+    dual_was_on: bool,
+
+    // Three band consoles, only run in multiband mode
+    left_multiband: multiband::MultibandConsole,
+    right_multiband: multiband::MultibandConsole,
+    multiband_was_on: bool,
+
+    // Input high and low pass ahead of the console
+    left_hpf: filters::SlopeFilter,
+    right_hpf: filters::SlopeFilter,
+    left_lpf: filters::SlopeFilter,
+    right_lpf: filters::SlopeFilter,
+
+    // Tilt EQ before or after the console
+    left_tilt: filters::TiltEq,
+    right_tilt: filters::TiltEq,
+
+    // Transport wobble ahead of the tape stage
+    left_wow_flutter: wow_flutter::WowFlutter,
+    right_wow_flutter: wow_flutter::WowFlutter,
+    // Run at zero depth on the dry Delta takes away, so it stays lined up with the wow's delay
+    left_wow_dry: wow_flutter::WowFlutter,
+    right_wow_dry: wow_flutter::WowFlutter,
+    // Decided per block since its delay counts towards the latency
+    wow_flutter_on: bool,
+
+    // Tape hysteresis after the console
+    left_tape: tape::TapeHysteresis,
+    right_tape: tape::TapeHysteresis,
+    tape_was_on: bool,
+
+    // Bleed between left and right after the console sum
+    crosstalk: crosstalk::Crosstalk,
+    crosstalk_was_on: bool,
+
+    // Output transformer after the tape stage
+    left_transformer: transformer::Transformer,
+    right_transformer: transformer::Transformer,
+    transformer_was_on: bool,
+
+    // Oversampling around the console stage
+    left_oversampler: oversampling::Oversampler,
+    right_oversampler: oversampling::Oversampler,
+    oversampling: oversampling::Oversampling,
+
+    // True-peak limiter at the end of the chain, its lookahead counts towards the latency
+    true_peak: true_peak::TruePeakLimiter,
+    true_peak_lookahead: bool,
+
+    // Host is bouncing faster than realtime, picks the Render profile in Auto quality
+    offline_render: bool,
+
+    // Long term loudness follower for Auto Mult
+    auto_mult: auto_mult::AutoMult,
+    // Fast input follower for program dependent Push and skews
+    envelope: envelope::EnvelopeFollower,
 
-    // The current data for the different meters
-    out_meter: Arc<AtomicF32>,
-    in_meter: Arc<AtomicF32>,
+    // Level match of the processed output against the input, and its trim for the GUI
+    auto_gain: auto_gain::AutoGain,
+    auto_gain_trim: Arc<AtomicF32>,
+
+    // One-shot gain match the GUI asks for, measured here and applied to Output Gain by the GUI
+    gain_match: Arc<gain_match::GainMatch>,
+    gain_match_meter: gain_match::GainMatchMeter,
+
+    // Result of the last offline render for the GUI
+    render_status: Arc<RwLock<String>>,
+
+    // Dry path delay for the ADAA shaper
+    left_adaa_aligner: engine::AdaaAligner,
+    right_adaa_aligner: engine::AdaaAligner,
+
+    left_dc_blocker: filters::DcBlocker,
+    right_dc_blocker: filters::DcBlocker,
+
+    // Output vs bypassed input difference meter, switched from the GUI
+    null_test: null_test::NullTest,
+    null_test_shared: Arc<null_test::NullTestShared>,
+}
+
+/// Work handed to the background executor
+pub enum Task {
+    Diagnostic(diagnostics::DiagnosticEvent, diagnostics::DiagnosticContext),
+    RenderClip(offline::RenderJob),
+    // The editor closed, drop its analyzer histories
+    ReleaseVisuals,
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+enum EditorTab {
+    #[default]
+    Console,
+    Tools,
+    Visuals,
+    // Impulse walking through the taps for people new to the sliders
+    Explain,
+    // Mod matrix routes
+    Modulation,
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+enum EditBank {
+    #[default]
+    A,
+    B,
+    // Right channel offsets for unlinked mode
+    R,
+}
+
+// GUI only state that lives while the editor is open
+#[derive(Default)]
+struct EditorUiState {
+    tab: EditorTab,
+    // Which coefficient bank the Console tab edits
+    edit_bank: EditBank,
+    // Source WAV for offline renders, empty renders the demo clip
+    render_input: String,
+    // Saved state of another Ardura plugin to import and how that went
+    import_path: String,
+    import_status: String,
+    show_settings: bool,
+    show_self_test: bool,
+    // Analyzer popped out into its own floating, resizable window
+    analyzer_detached: bool,
+    self_test_report: Option<engine::SelfTestReport>,
+    morph_capture: morph_capture::MorphCapture,
+    meter_clock: channel_meters::MeterClock,
+    // What the last gain match did
+    gain_match_status: String,
+    // The running gain match measures the preset loudness instead of setting Output Gain
+    measuring_loudness: bool,
+    // Font scale the context style was last built for
+    applied_font_scale: Option<f32>,
+}
+
+impl viz_buffers::VizBuffers {
+    /// Tap heat map over the random mod scope, split over the height of `size`. Drawn in the
+    /// Visuals tab or the popped out analyzer window.
+    #[allow(clippy::too_many_arguments)]
+    fn analyzer_ui(
+        &self,
+        ui: &mut egui::Ui,
+        theme: &gui_settings::Theme,
+        font_scale: f32,
+        reduced_motion: bool,
+        mod_target: &str,
+        mod_value: f32,
+        size: egui::Vec2,
+    ) {
+        // Leave room for the two captions
+        let plot_height = (size.y - 40.0 * font_scale).max(40.0);
+        ui.label(
+            RichText::new("Tap Energy")
+                .font(FontId::proportional(12.0 * font_scale))
+                .color(theme.accent),
+        );
+        ui.add(
+            tap_heatmap::TapHeatmap::new(&self.tap_energy_history)
+                .desired_size(egui::vec2(size.x, plot_height * 0.6))
+                .color(theme.accent)
+                .background_color(theme.inside.linear_multiply(0.3)),
+        )
+        .on_hover_text("How the input energy lands on each tap over time, tap 1 on top");
+
+        ui.label(
+            RichText::new(format!("Random Mod -> {}", mod_target))
+                .font(FontId::proportional(12.0 * font_scale))
+                .color(theme.accent),
+        );
+        if reduced_motion {
+            ui.label(format!("{:+.3} (scope paused for reduced motion)", mod_value));
+        } else {
+            ui.add(
+                mod_scope::ModScope::new(&self.mod_history)
+                    .desired_size(egui::vec2(size.x, plot_height * 0.4))
+                    .line_color(theme.accent)
+                    .line_width(theme.stroke_width * 1.5)
+                    .background_color(theme.inside.linear_multiply(0.3)),
+            )
+            .on_hover_text("Random mod output after depth, newest on the right");
+        }
+    }
 }
 
 #[derive(Params)]
@@ -50,6 +377,68 @@ struct GladeDeskParams {
     #[persist = "editor-state"]
     editor_state: Arc<EguiState>,
 
+    /// Accessibility and display preferences for the editor
+    #[persist = "gui-settings"]
+    gui_settings: Arc<RwLock<gui_settings::GuiSettings>>,
+
+    /// Name and color tag for this instance
+    #[persist = "instance-tag"]
+    instance_tag: Arc<RwLock<gui_settings::InstanceTag>>,
+
+    /// Source and destination labels for the channel strip header
+    #[persist = "routing-labels"]
+    routing_labels: Arc<RwLock<gui_settings::RoutingLabels>>,
+
+    /// Which params the top row knobs show
+    #[persist = "top-knobs"]
+    top_knobs: Arc<RwLock<gui_settings::TopKnobs>>,
+
+    // Mirrored from the GUI settings so the slider readouts can follow the chosen precision
+    // and display format
+    slider_readout: Arc<gui_settings::SliderReadout>,
+
+    /// Bank sliders that skip their smoother and jump straight to new values, see `snap_bit`
+    #[persist = "snap-flags"]
+    snap_flags: Arc<RwLock<u64>>,
+
+    // Mirrored from `snap_flags` so the audio thread never touches the lock
+    snap_mask: Arc<AtomicU64>,
+
+    /// Seed for the random mod and drift, saved so renders of this state always match
+    #[persist = "random-seed"]
+    random_seed: Arc<RwLock<u32>>,
+
+    // Mirrored from `random_seed` for the audio thread
+    seed: Arc<AtomicU32>,
+
+    /// Oversampling and shaper per quality profile
+    #[persist = "quality-profiles"]
+    quality_profiles: Arc<RwLock<quality::QualityProfiles>>,
+
+    // Mirrored from `quality_profiles`, see `QualityProfiles::pack`
+    quality_mask: Arc<AtomicU32>,
+
+    /// Modulation routes from the Mod tab
+    #[persist = "mod-matrix"]
+    mod_matrix: Arc<RwLock<mod_matrix::ModMatrix>>,
+
+    // Mirrored from `mod_matrix` for the audio thread
+    mod_routes: Arc<mod_matrix::SharedMatrix>,
+
+    /// What each macro knob moves, from the Mod tab
+    #[persist = "macro-map"]
+    macro_map: Arc<RwLock<macros::MacroMap>>,
+
+    // Mirrored from `macro_map` for the audio thread
+    macro_targets: Arc<macros::SharedMacros>,
+
+    /// Output against input loudness in dB measured for this state, None until measured
+    #[persist = "loudness-delta"]
+    loudness_delta: Arc<RwLock<Option<f32>>>,
+
+    // Mirrored from `loudness_delta` for the audio thread, 0 when unmeasured
+    loudness_delta_db: Arc<AtomicF32>,
+
     #[id = "free_gain"]
     pub free_gain: FloatParam,
 
@@ -112,6 +501,434 @@ struct GladeDeskParams {
 
     #[id = "dry_wet"]
     pub dry_wet: FloatParam,
+
+    #[id = "note_gate"]
+    pub note_gate: BoolParam,
+
+    #[id = "gate_attack"]
+    pub gate_attack: FloatParam,
+
+    #[id = "gate_release"]
+    pub gate_release: FloatParam,
+
+    #[id = "diagnostic_log"]
+    pub diagnostic_log: BoolParam,
+
+    #[id = "dc_trim_l"]
+    pub dc_trim_l: FloatParam,
+
+    #[id = "dc_trim_r"]
+    pub dc_trim_r: FloatParam,
+
+    #[id = "mod_mode"]
+    pub mod_mode: EnumParam<modulation::RandomMode>,
+
+    #[id = "mod_rate"]
+    pub mod_rate: FloatParam,
+
+    #[id = "mod_smooth"]
+    pub mod_smooth: FloatParam,
+
+    #[id = "mod_depth"]
+    pub mod_depth: FloatParam,
+
+    #[id = "drift"]
+    pub drift: FloatParam,
+
+    #[id = "mod_target"]
+    pub mod_target: EnumParam<modulation::ModTarget>,
+
+    #[id = "lfo_shape"]
+    pub lfo_shape: EnumParam<modulation::LfoShape>,
+
+    #[id = "lfo_sync"]
+    pub lfo_sync: EnumParam<modulation::LfoSync>,
+
+    #[id = "lfo_rate"]
+    pub lfo_rate: FloatParam,
+
+    #[id = "lfo_depth"]
+    pub lfo_depth: FloatParam,
+
+    #[id = "lfo_target"]
+    pub lfo_target: EnumParam<modulation::LfoTarget>,
+
+    #[id = "env_attack"]
+    pub env_attack: FloatParam,
+
+    #[id = "env_release"]
+    pub env_release: FloatParam,
+
+    #[id = "env_sidechain"]
+    pub env_sidechain: BoolParam,
+
+    #[id = "env_push"]
+    pub env_push: FloatParam,
+
+    #[id = "env_skew"]
+    pub env_skew: FloatParam,
+
+    #[id = "push_dynamics"]
+    pub push_dynamics: FloatParam,
+
+    #[id = "macro_1"]
+    pub macro_1: FloatParam,
+
+    #[id = "macro_2"]
+    pub macro_2: FloatParam,
+
+    #[id = "macro_3"]
+    pub macro_3: FloatParam,
+
+    #[id = "macro_4"]
+    pub macro_4: FloatParam,
+
+    #[id = "mod_output"]
+    pub mod_output: BoolParam,
+
+    #[id = "mod_output_cc"]
+    pub mod_output_cc: IntParam,
+
+    #[id = "alert_output"]
+    pub alert_output: BoolParam,
+
+    #[id = "alert_message"]
+    pub alert_message: EnumParam<alert_output::AlertMessage>,
+
+    #[id = "alert_number"]
+    pub alert_number: IntParam,
+
+    #[id = "lofi_ratio"]
+    pub lofi_ratio: EnumParam<lofi::LofiRatio>,
+
+    #[id = "lofi_interp"]
+    pub lofi_interp: EnumParam<lofi::LofiInterp>,
+
+    #[id = "crush_bits"]
+    pub crush_bits: FloatParam,
+
+    #[id = "crush_mix"]
+    pub crush_mix: FloatParam,
+
+    #[id = "oversampling"]
+    pub oversampling: EnumParam<oversampling::Oversampling>,
+
+    #[id = "auto_standby"]
+    pub auto_standby: BoolParam,
+
+    #[id = "scene_fade"]
+    pub scene_fade: FloatParam,
+
+    #[id = "zero_latency"]
+    pub zero_latency: BoolParam,
+
+    #[id = "quality_mode"]
+    pub quality_mode: EnumParam<quality::QualityMode>,
+
+    #[id = "auto_mult"]
+    pub auto_mult: BoolParam,
+
+    #[id = "auto_gain"]
+    pub auto_gain: BoolParam,
+
+    #[id = "output_invert"]
+    pub output_invert: BoolParam,
+
+    #[id = "delta"]
+    pub delta: BoolParam,
+
+    #[id = "hq_shaper"]
+    pub hq_shaper: BoolParam,
+
+    #[id = "push_curve"]
+    pub push_curve: EnumParam<engine::PushCurve>,
+
+    #[id = "bias"]
+    pub bias: FloatParam,
+
+    #[id = "even_odd"]
+    pub even_odd: FloatParam,
+
+    // Chebyshev harmonic designer
+    #[id = "harmonic_mode"]
+    pub harmonic_mode: EnumParam<engine::HarmonicMode>,
+
+    #[id = "harmonic_2"]
+    pub harmonic_2: FloatParam,
+
+    #[id = "harmonic_3"]
+    pub harmonic_3: FloatParam,
+
+    #[id = "harmonic_4"]
+    pub harmonic_4: FloatParam,
+
+    #[id = "harmonic_5"]
+    pub harmonic_5: FloatParam,
+
+    #[id = "harmonic_6"]
+    pub harmonic_6: FloatParam,
+
+    #[id = "harmonic_7"]
+    pub harmonic_7: FloatParam,
+
+    #[id = "harmonic_8"]
+    pub harmonic_8: FloatParam,
+
+    #[id = "mix_law"]
+    pub mix_law: EnumParam<engine::MixLaw>,
+
+    #[id = "calibration"]
+    pub calibration: EnumParam<engine::Calibration>,
+
+    #[id = "console_mode"]
+    pub console_mode: EnumParam<engine::ConsoleMode>,
+
+    #[id = "hpf_slope"]
+    pub hpf_slope: EnumParam<filters::FilterSlope>,
+
+    #[id = "hpf_cutoff"]
+    pub hpf_cutoff: FloatParam,
+
+    #[id = "lpf_slope"]
+    pub lpf_slope: EnumParam<filters::FilterSlope>,
+
+    #[id = "lpf_cutoff"]
+    pub lpf_cutoff: FloatParam,
+
+    #[id = "tilt"]
+    pub tilt: FloatParam,
+
+    #[id = "tilt_pivot"]
+    pub tilt_pivot: FloatParam,
+
+    #[id = "tilt_placement"]
+    pub tilt_placement: EnumParam<filters::TiltPlacement>,
+
+    #[id = "wow_depth"]
+    pub wow_depth: FloatParam,
+
+    #[id = "wow_rate"]
+    pub wow_rate: FloatParam,
+
+    #[id = "tape"]
+    pub tape: BoolParam,
+
+    #[id = "tape_drive"]
+    pub tape_drive: FloatParam,
+
+    #[id = "tape_bias"]
+    pub tape_bias: FloatParam,
+
+    #[id = "crosstalk"]
+    pub crosstalk: FloatParam,
+
+    #[id = "crosstalk_dynamics"]
+    pub crosstalk_dynamics: FloatParam,
+
+    #[id = "transformer"]
+    pub transformer: BoolParam,
+
+    #[id = "transformer_drive"]
+    pub transformer_drive: FloatParam,
+
+    #[id = "transformer_core"]
+    pub transformer_core: FloatParam,
+
+    #[id = "dc_block"]
+    pub dc_block: BoolParam,
+
+    #[id = "true_peak"]
+    pub true_peak: BoolParam,
+
+    #[id = "true_peak_ceiling"]
+    pub true_peak_ceiling: FloatParam,
+
+    #[id = "true_peak_release"]
+    pub true_peak_release: FloatParam,
+
+    #[id = "dc_block_cutoff"]
+    pub dc_block_cutoff: FloatParam,
+
+    #[id = "dual_engine"]
+    pub dual_engine: BoolParam,
+
+    #[id = "engine_balance"]
+    pub engine_balance: FloatParam,
+
+    #[id = "bank_morph"]
+    pub bank_morph: EnumParam<engine::BankMorph>,
+
+    // Second coefficient bank for the dual engine
+    #[id = "B1_Coeff"]
+    pub slider_b1_coeff: FloatParam,
+
+    #[id = "B1_Skew"]
+    pub slider_b1_skew: FloatParam,
+
+    #[id = "B2_Coeff"]
+    pub slider_b2_coeff: FloatParam,
+
+    #[id = "B2_Skew"]
+    pub slider_b2_skew: FloatParam,
+
+    #[id = "B3_Coeff"]
+    pub slider_b3_coeff: FloatParam,
+
+    #[id = "B3_Skew"]
+    pub slider_b3_skew: FloatParam,
+
+    #[id = "B4_Coeff"]
+    pub slider_b4_coeff: FloatParam,
+
+    #[id = "B4_Skew"]
+    pub slider_b4_skew: FloatParam,
+
+    #[id = "B5_Coeff"]
+    pub slider_b5_coeff: FloatParam,
+
+    #[id = "B5_Skew"]
+    pub slider_b5_skew: FloatParam,
+
+    #[id = "B6_Coeff"]
+    pub slider_b6_coeff: FloatParam,
+
+    #[id = "B6_Skew"]
+    pub slider_b6_skew: FloatParam,
+
+    #[id = "B7_Coeff"]
+    pub slider_b7_coeff: FloatParam,
+
+    #[id = "B7_Skew"]
+    pub slider_b7_skew: FloatParam,
+
+    #[id = "B8_Coeff"]
+    pub slider_b8_coeff: FloatParam,
+
+    #[id = "B8_Skew"]
+    pub slider_b8_skew: FloatParam,
+
+    #[id = "link"]
+    pub link: BoolParam,
+
+    // Right channel offsets for unlinked (dual-mono) operation
+
+    #[id = "R1_Coeff_Offset"]
+    pub r1_coeff_offset: FloatParam,
+
+    #[id = "R1_Skew_Offset"]
+    pub r1_skew_offset: FloatParam,
+
+    #[id = "R2_Coeff_Offset"]
+    pub r2_coeff_offset: FloatParam,
+
+    #[id = "R2_Skew_Offset"]
+    pub r2_skew_offset: FloatParam,
+
+    #[id = "R3_Coeff_Offset"]
+    pub r3_coeff_offset: FloatParam,
+
+    #[id = "R3_Skew_Offset"]
+    pub r3_skew_offset: FloatParam,
+
+    #[id = "R4_Coeff_Offset"]
+    pub r4_coeff_offset: FloatParam,
+
+    #[id = "R4_Skew_Offset"]
+    pub r4_skew_offset: FloatParam,
+
+    #[id = "R5_Coeff_Offset"]
+    pub r5_coeff_offset: FloatParam,
+
+    #[id = "R5_Skew_Offset"]
+    pub r5_skew_offset: FloatParam,
+
+    #[id = "R6_Coeff_Offset"]
+    pub r6_coeff_offset: FloatParam,
+
+    #[id = "R6_Skew_Offset"]
+    pub r6_skew_offset: FloatParam,
+
+    #[id = "R7_Coeff_Offset"]
+    pub r7_coeff_offset: FloatParam,
+
+    #[id = "R7_Skew_Offset"]
+    pub r7_skew_offset: FloatParam,
+
+    #[id = "R8_Coeff_Offset"]
+    pub r8_coeff_offset: FloatParam,
+
+    #[id = "R8_Skew_Offset"]
+    pub r8_skew_offset: FloatParam,
+
+    #[id = "multiband"]
+    pub multiband: BoolParam,
+
+    #[id = "crossover_low"]
+    pub crossover_low: FloatParam,
+
+    #[id = "crossover_high"]
+    pub crossover_high: FloatParam,
+
+    #[id = "low_drive"]
+    pub low_drive: FloatParam,
+
+    #[id = "mid_drive"]
+    pub mid_drive: FloatParam,
+
+    #[id = "high_drive"]
+    pub high_drive: FloatParam,
+
+    #[id = "low_mix"]
+    pub low_mix: FloatParam,
+
+    #[id = "mid_mix"]
+    pub mid_mix: FloatParam,
+
+    #[id = "high_mix"]
+    pub high_mix: FloatParam,
+
+    #[id = "tap_count"]
+    pub tap_count: IntParam,
+
+    #[id = "feedback"]
+    pub feedback: FloatParam,
+
+    #[id = "feedback_damping"]
+    pub feedback_damping: FloatParam,
+
+    #[id = "tap_damping"]
+    pub tap_damping: FloatParam,
+
+    #[id = "spacing_mode"]
+    pub spacing_mode: EnumParam<engine::SpacingMode>,
+
+    #[id = "tap_spacing"]
+    pub tap_spacing: FloatParam,
+
+    // Sign each tap is summed with, defaults to the classic console pattern
+    #[id = "Polarity_1"]
+    pub tap_1_polarity: EnumParam<engine::TapPolarity>,
+
+    #[id = "Polarity_2"]
+    pub tap_2_polarity: EnumParam<engine::TapPolarity>,
+
+    #[id = "Polarity_3"]
+    pub tap_3_polarity: EnumParam<engine::TapPolarity>,
+
+    #[id = "Polarity_4"]
+    pub tap_4_polarity: EnumParam<engine::TapPolarity>,
+
+    #[id = "Polarity_5"]
+    pub tap_5_polarity: EnumParam<engine::TapPolarity>,
+
+    #[id = "Polarity_6"]
+    pub tap_6_polarity: EnumParam<engine::TapPolarity>,
+
+    #[id = "Polarity_7"]
+    pub tap_7_polarity: EnumParam<engine::TapPolarity>,
+
+    #[id = "Polarity_8"]
+    pub tap_8_polarity: EnumParam<engine::TapPolarity>,
 }
 
 impl Default for GladeDesk {
@@ -119,18 +936,118 @@ impl Default for GladeDesk {
         Self {
             params: Arc::new(GladeDeskParams::default()),
             out_meter_decay_weight: 1.0,
-            out_meter: Arc::new(AtomicF32::new(util::MINUS_INFINITY_DB)),
-            in_meter: Arc::new(AtomicF32::new(util::MINUS_INFINITY_DB)),
-            left_vec: VecDeque::from(vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]),
-            right_vec: VecDeque::from(vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]),
+            out_meter: Arc::new(channel_meters::ChannelMeters::new()),
+            in_meter: Arc::new(channel_meters::ChannelMeters::new()),
+            meter_clock: 0,
+            meter_sub_block: 220,
+            presence: presence::PresenceDetector::new(),
+            signal_presence: Arc::new(presence::SignalPresence::new()),
+            left_console: engine::ConsoleChannel::new(),
+            right_console: engine::ConsoleChannel::new(),
+            note_gate: note_gate::NoteGate::new(),
+            diagnostics: diagnostics::DiagnosticsMonitor::new(),
+            sample_rate: 44100.0,
+            session_sample_rate: Arc::new(AtomicF32::new(44100.0)),
+            max_buffer_size: Arc::new(AtomicUsize::new(0)),
+            latency_samples: Arc::new(AtomicUsize::new(0)),
+            reduced_motion: Arc::new(AtomicBool::new(false)),
+            normalize_loudness: Arc::new(AtomicBool::new(user_config::normalize_presets())),
+            tap_energy: Arc::new(std::array::from_fn(|_| AtomicF32::new(0.0))),
+            random_mod: modulation::RandomSource::new(RANDOM_MOD_SEED),
+            lfo: modulation::Lfo::new(),
+            mod_output: mod_output::ModOutput::new(),
+            alert_output: alert_output::AlertOutput::new(),
+            drift: std::array::from_fn(|tap| {
+                modulation::RandomSource::new(RANDOM_MOD_SEED.rotate_left(tap as u32 + 1))
+            }),
+            active_seed: RANDOM_MOD_SEED,
+            was_playing: false,
+            mod_scope_value: Arc::new(AtomicF32::new(0.0)),
+            viz_buffers: Arc::new(viz_buffers::VizStore::new()),
+            editor_was_open: false,
+            safety_limiter: safety_limiter::SafetyLimiter::new(),
+            safety_engaged: Arc::new(AtomicBool::new(false)),
+            safety_dismiss: Arc::new(AtomicBool::new(false)),
+            last_console_params: None,
+            scene_fade_from: None,
+            scene_fade_pos: 0,
+            scene_fade_len: 0,
+            safe_mode: Arc::new(AtomicBool::new(false)),
+            crash_guard_active: false,
+            left_lofi: lofi::RateReducer::new(),
+            right_lofi: lofi::RateReducer::new(),
+            left_console_b: engine::ConsoleChannel::new(),
+            right_console_b: engine::ConsoleChannel::new(),
+            dual_was_on: false,
+            left_multiband: multiband::MultibandConsole::new(),
+            right_multiband: multiband::MultibandConsole::new(),
+            multiband_was_on: false,
+            left_hpf: filters::SlopeFilter::new(true),
+            right_hpf: filters::SlopeFilter::new(true),
+            left_lpf: filters::SlopeFilter::new(false),
+            right_lpf: filters::SlopeFilter::new(false),
+            left_tilt: filters::TiltEq::new(),
+            right_tilt: filters::TiltEq::new(),
+            left_wow_flutter: wow_flutter::WowFlutter::new(),
+            right_wow_flutter: wow_flutter::WowFlutter::new(),
+            left_wow_dry: wow_flutter::WowFlutter::new(),
+            right_wow_dry: wow_flutter::WowFlutter::new(),
+            wow_flutter_on: false,
+            left_tape: tape::TapeHysteresis::new(),
+            right_tape: tape::TapeHysteresis::new(),
+            tape_was_on: false,
+            crosstalk: crosstalk::Crosstalk::new(),
+            crosstalk_was_on: false,
+            left_transformer: transformer::Transformer::new(),
+            right_transformer: transformer::Transformer::new(),
+            transformer_was_on: false,
+            left_oversampler: oversampling::Oversampler::new(),
+            right_oversampler: oversampling::Oversampler::new(),
+            oversampling: oversampling::Oversampling::X1,
+            true_peak: true_peak::TruePeakLimiter::new(),
+            true_peak_lookahead: false,
+            offline_render: false,
+            auto_mult: auto_mult::AutoMult::new(),
+            envelope: envelope::EnvelopeFollower::new(),
+            auto_gain: auto_gain::AutoGain::new(),
+            auto_gain_trim: Arc::new(AtomicF32::new(1.0)),
+            gain_match: Arc::new(gain_match::GainMatch::new()),
+            gain_match_meter: gain_match::GainMatchMeter::new(),
+            render_status: Arc::new(RwLock::new(String::new())),
+            left_adaa_aligner: engine::AdaaAligner::new(),
+            right_adaa_aligner: engine::AdaaAligner::new(),
+            left_dc_blocker: filters::DcBlocker::new(),
+            right_dc_blocker: filters::DcBlocker::new(),
+            null_test: null_test::NullTest::new(),
+            null_test_shared: Arc::new(null_test::NullTestShared::new()),
         }
     }
 }
 
 impl Default for GladeDeskParams {
     fn default() -> Self {
+        let slider_readout = Arc::new(gui_settings::SliderReadout::new());
         Self {
+            slider_readout: slider_readout.clone(),
             editor_state: EguiState::from_size(WIDTH, HEIGHT),
+            gui_settings: Arc::new(RwLock::new(gui_settings::GuiSettings::default())),
+            instance_tag: Arc::new(RwLock::new(gui_settings::InstanceTag::default())),
+            routing_labels: Arc::new(RwLock::new(gui_settings::RoutingLabels::default())),
+            top_knobs: Arc::new(RwLock::new(gui_settings::TopKnobs::default())),
+            snap_flags: Arc::new(RwLock::new(0)),
+            random_seed: Arc::new(RwLock::new(RANDOM_MOD_SEED)),
+            seed: Arc::new(AtomicU32::new(RANDOM_MOD_SEED)),
+            snap_mask: Arc::new(AtomicU64::new(0)),
+            quality_profiles: Arc::new(RwLock::new(quality::QualityProfiles::default())),
+            mod_matrix: Arc::new(RwLock::new(mod_matrix::ModMatrix::default())),
+            mod_routes: Arc::new(mod_matrix::SharedMatrix::new()),
+            macro_map: Arc::new(RwLock::new(macros::MacroMap::default())),
+            macro_targets: Arc::new(macros::SharedMacros::new()),
+            loudness_delta: Arc::new(RwLock::new(None)),
+            loudness_delta_db: Arc::new(AtomicF32::new(0.0)),
+            quality_mask: Arc::new(AtomicU32::new(
+                quality::QualityProfiles::default().pack(),
+            )),
 
             // Input gain dB parameter (free as in unrestricted nums)
             free_gain: FloatParam::new(
@@ -163,7 +1080,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Skew parameter 1
             slider_1_skew: FloatParam::new(
@@ -175,7 +1093,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Coeff parameter 2
             slider_2_coeff: FloatParam::new(
@@ -187,7 +1106,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Skew parameter 2
             slider_2_skew: FloatParam::new(
@@ -199,7 +1119,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Coeff parameter 3
             slider_3_coeff: FloatParam::new(
@@ -211,7 +1132,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Skew parameter 3
             slider_3_skew: FloatParam::new(
@@ -223,7 +1145,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Coeff parameter 4
             slider_4_coeff: FloatParam::new(
@@ -235,7 +1158,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Skew parameter 4
             slider_4_skew: FloatParam::new(
@@ -247,7 +1171,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Coeff parameter 5
             slider_5_coeff: FloatParam::new(
@@ -259,7 +1184,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Skew parameter 5
             slider_5_skew: FloatParam::new(
@@ -271,7 +1197,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Coeff parameter 6
             slider_6_coeff: FloatParam::new(
@@ -283,7 +1210,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Skew parameter 6
             slider_6_skew: FloatParam::new(
@@ -295,7 +1223,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Coeff parameter 7
             slider_7_coeff: FloatParam::new(
@@ -307,7 +1236,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Skew parameter 7
             slider_7_skew: FloatParam::new(
@@ -319,7 +1249,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Coeff parameter 8
             slider_8_coeff: FloatParam::new(
@@ -331,7 +1262,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Skew parameter 8
             slider_8_skew: FloatParam::new(
@@ -343,7 +1275,8 @@ impl Default for GladeDeskParams {
                 },
             )
             .with_smoother(SmoothingStyle::Linear(30.0))
-            .with_value_to_string(formatters::v2s_f32_rounded(6)),
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
 
             // Multiplier
             multiplier: FloatParam::new(
@@ -380,50 +1313,1778 @@ impl Default for GladeDeskParams {
                 .with_unit("% Wet")
                 .with_value_to_string(formatters::v2s_f32_percentage(2))
                 .with_string_to_value(formatters::s2v_f32_percentage()),
-        }
-    }
-}
 
-impl Plugin for GladeDesk {
-    const NAME: &'static str = "Glade Desk";
-    const VENDOR: &'static str = "Ardura";
-    const URL: &'static str = "https://github.com/ardura";
-    const EMAIL: &'static str = "azviscarra@gmail.com";
+            // Only blend in the wet signal while a MIDI note is held
+            note_gate: BoolParam::new("Note Gate", false),
 
-    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+            // Note gate attack in ms
+            gate_attack: FloatParam::new(
+                "Gate Attack",
+                5.0,
+                FloatRange::Skewed {
+                    min: 0.1,
+                    max: 500.0,
+                    factor: 0.3,
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
 
-    // This looks like it's flexible for running the plugin in mono or stereo
-    const AUDIO_IO_LAYOUTS: &'static [AudioIOLayout] = &[
-        AudioIOLayout {
-            main_input_channels: NonZeroU32::new(2),
-            main_output_channels: NonZeroU32::new(2),
-            ..AudioIOLayout::const_default()
-        },
-        AudioIOLayout {
-            main_input_channels: NonZeroU32::new(1),
-            main_output_channels: NonZeroU32::new(1),
-            ..AudioIOLayout::const_default()
-        },
-    ];
+            // Note gate release in ms
+            gate_release: FloatParam::new(
+                "Gate Release",
+                80.0,
+                FloatRange::Skewed {
+                    min: 1.0,
+                    max: 2000.0,
+                    factor: 0.3,
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
 
-    const SAMPLE_ACCURATE_AUTOMATION: bool = true;
+            // Log output clips and extreme internal values to the host console
+            diagnostic_log: BoolParam::new("Diagnostic Log", false).non_automatable(),
 
-    type SysExMessage = ();
-    type BackgroundTask = ();
+            // DC offset added to each channel before the shaper
+            dc_trim_l: FloatParam::new(
+                "DC Trim L",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.01,
+                    max: 0.01,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(formatters::v2s_f32_rounded(5)),
 
-    fn params(&self) -> Arc<dyn Params> {
-        self.params.clone()
-    }
+            dc_trim_r: FloatParam::new(
+                "DC Trim R",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.01,
+                    max: 0.01,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(formatters::v2s_f32_rounded(5)),
 
-    fn editor(&mut self, _async_executor: AsyncExecutor<Self>) -> Option<Box<dyn Editor>> {
-        let params = self.params.clone();
-        let in_meter = self.in_meter.clone();
+            // Random modulation source
+            mod_mode: EnumParam::new("Mod Mode", modulation::RandomMode::SampleHold),
+
+            mod_rate: FloatParam::new(
+                "Mod Rate",
+                2.0,
+                FloatRange::Skewed {
+                    min: 0.05,
+                    max: 20.0,
+                    factor: 0.3,
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_rounded(2)),
+
+            mod_smooth: FloatParam::new("Mod Smooth", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_unit("% Smooth")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            mod_depth: FloatParam::new("Mod Depth", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("% Depth")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            mod_target: EnumParam::new("Mod Target", modulation::ModTarget::Push),
+
+            // LFO onto Push, Multiplier or the skews, Rate only counts while Sync is Free
+            lfo_shape: EnumParam::new("LFO Shape", modulation::LfoShape::Sine),
+
+            lfo_sync: EnumParam::new("LFO Sync", modulation::LfoSync::Free),
+
+            lfo_rate: FloatParam::new(
+                "LFO Rate",
+                1.0,
+                FloatRange::Skewed {
+                    min: 0.02,
+                    max: 20.0,
+                    factor: 0.3,
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_rounded(2)),
+
+            lfo_depth: FloatParam::new("LFO Depth", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("% Depth")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            lfo_target: EnumParam::new("LFO Target", modulation::LfoTarget::Push),
+
+            // Input envelope onto Push and the skews, the amounts go either way
+            env_attack: FloatParam::new(
+                "Env Attack",
+                5.0,
+                FloatRange::Skewed {
+                    min: 0.1,
+                    max: 100.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+
+            env_release: FloatParam::new(
+                "Env Release",
+                120.0,
+                FloatRange::Skewed {
+                    min: 5.0,
+                    max: 1000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(0)),
+
+            // Follow the sidechain input instead of the main one, e.g. a kick driving a bass bus
+            env_sidechain: BoolParam::new("Env Sidechain", false),
+
+            env_push: FloatParam::new("Env > Push", 0.0, FloatRange::Linear { min: -1.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            env_skew: FloatParam::new("Env > Skew", 0.0, FloatRange::Linear { min: -1.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            // Scales Push by the envelope, positive eases off on loud parts like a compressor
+            push_dynamics: FloatParam::new("Push Dynamics", 0.0, FloatRange::Linear { min: -1.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            // Macro knobs, what they move is mapped on the Mod tab
+            macro_1: macro_param("Macro 1"),
+            macro_2: macro_param("Macro 2"),
+            macro_3: macro_param("Macro 3"),
+            macro_4: macro_param("Macro 4"),
+
+            // Sends the modulation to the host as MIDI CCs for other plugins to follow
+            mod_output: BoolParam::new("Mod Output", false),
+
+            mod_output_cc: IntParam::new(
+                "Mod Output CC",
+                20,
+                IntRange::Linear {
+                    min: 0,
+                    max: (120 - mod_output::SOURCES) as i32,
+                },
+            ),
+
+            // Clip and overload alerts out as MIDI for controllers and DAW macros
+            alert_output: BoolParam::new("Alert Output", false),
+
+            alert_message: EnumParam::new("Alert Message", alert_output::AlertMessage::Note),
+
+            alert_number: IntParam::new(
+                "Alert Number",
+                60,
+                IntRange::Linear {
+                    min: 0,
+                    max: (128 - alert_output::ALERTS) as i32,
+                },
+            ),
+
+            // Slow wander of the coefficients, like component tolerances
+            drift: FloatParam::new("Drift", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            lofi_ratio: EnumParam::new("Lo-fi Rate", lofi::LofiRatio::Off),
+
+            lofi_interp: EnumParam::new("Lo-fi Interp", lofi::LofiInterp::Hold),
+
+            crush_bits: FloatParam::new("Crush Bits", 12.0, FloatRange::Linear { min: 2.0, max: 16.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit(" bits")
+                .with_value_to_string(formatters::v2s_f32_rounded(1)),
+
+            crush_mix: FloatParam::new("Crush Mix", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("% Crush")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            oversampling: EnumParam::new("Oversampling", oversampling::Oversampling::X1),
+
+            // Overrides everything that adds latency, for tracking and live monitoring
+            // Winds the random modulation down while there's no input
+            auto_standby: BoolParam::new("Auto Standby", false),
+
+            // Crossfade time when the host loads a different preset or scene
+            scene_fade: FloatParam::new("Scene Fade", 0.0, FloatRange::Linear { min: 0.0, max: 2000.0 })
+                .with_unit(" ms")
+                .with_step_size(1.0),
+
+            zero_latency: BoolParam::new("Zero Latency", false),
+
+            quality_mode: EnumParam::new("Quality", quality::QualityMode::Manual),
+
+            auto_mult: BoolParam::new("Auto Mult", false),
+
+            auto_gain: BoolParam::new("Auto Gain", false),
+
+            output_invert: BoolParam::new("Invert Output", false),
+
+            // Listen to only what the console adds, wet minus the lined up dry
+            delta: BoolParam::new("Delta", false),
+
+            hq_shaper: BoolParam::new("HQ Shaper", false),
+
+            push_curve: EnumParam::new("Push Curve", engine::PushCurve::Sine),
+
+            // Offsets the signal into the Push shaper, the offset itself is taken back out after
+            bias: FloatParam::new("Bias", 0.0, FloatRange::Linear { min: 0.0, max: 0.5 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_value_to_string(formatters::v2s_f32_rounded(2)),
+
+            // 0 keeps the Push curve as it is, 1 swaps it for the asymmetric even path
+            even_odd: FloatParam::new("Even/Odd", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("% Even")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            // Levels a full scale sine comes out with at each harmonic
+            harmonic_mode: EnumParam::new("Harmonic Mode", engine::HarmonicMode::Off),
+
+            harmonic_2: FloatParam::new("Harmonic 2", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            harmonic_3: FloatParam::new("Harmonic 3", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            harmonic_4: FloatParam::new("Harmonic 4", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            harmonic_5: FloatParam::new("Harmonic 5", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            harmonic_6: FloatParam::new("Harmonic 6", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            harmonic_7: FloatParam::new("Harmonic 7", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            harmonic_8: FloatParam::new("Harmonic 8", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            // Additive by default so old sessions sound the same
+            mix_law: EnumParam::new("Mix Law", engine::MixLaw::Additive),
+
+            // Where 0 VU sits, so hot and quiet gain staging hit the console the same
+            calibration: EnumParam::new("Calibration", engine::Calibration::Minus18),
+
+            // Channel instances encode, a bus instance after the sum decodes
+            console_mode: EnumParam::new("Console Mode", engine::ConsoleMode::Single),
+
+            // Input filters ahead of everything nonlinear
+            hpf_slope: EnumParam::new("HPF Slope", filters::FilterSlope::Off),
+
+            hpf_cutoff: FloatParam::new(
+                "HPF",
+                30.0,
+                FloatRange::Skewed {
+                    min: 10.0,
+                    max: 1000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_rounded(0)),
+
+            lpf_slope: EnumParam::new("LPF Slope", filters::FilterSlope::Off),
+
+            lpf_cutoff: FloatParam::new(
+                "LPF",
+                18000.0,
+                FloatRange::Skewed {
+                    min: 1000.0,
+                    max: 22000.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_rounded(0)),
+
+            // Tilt EQ, before the console to change what it reacts to or after to shape it
+            tilt: FloatParam::new("Tilt", 0.0, FloatRange::Linear { min: -6.0, max: 6.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit(" dB")
+                .with_value_to_string(formatters::v2s_f32_rounded(1)),
+
+            tilt_pivot: FloatParam::new(
+                "Tilt Pivot",
+                1000.0,
+                FloatRange::Skewed {
+                    min: 200.0,
+                    max: 5000.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_rounded(0)),
+
+            tilt_placement: EnumParam::new("Tilt Placement", filters::TiltPlacement::Pre),
+
+            // Pitch wobble from a modulated delay, zero depth is off
+            wow_depth: FloatParam::new("Wow & Flutter", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            wow_rate: FloatParam::new(
+                "Wow Rate",
+                0.8,
+                FloatRange::Skewed {
+                    min: 0.1,
+                    max: 5.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_rounded(2)),
+
+            // Hysteresis stage after the console, before the DC blocker
+            tape: BoolParam::new("Tape", false),
+
+            tape_drive: FloatParam::new("Tape Drive", 0.5, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            tape_bias: FloatParam::new("Tape Bias", 0.5, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            // Left/right bleed, the bottom of the range is off
+            crosstalk: FloatParam::new(
+                "Crosstalk",
+                crosstalk::CROSSTALK_OFF_DB,
+                FloatRange::Linear {
+                    min: crosstalk::CROSSTALK_OFF_DB,
+                    max: -20.0,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_unit(" dB")
+            .with_value_to_string(Arc::new(|value: f32| {
+                if value <= crosstalk::CROSSTALK_OFF_DB {
+                    String::from("Off")
+                } else {
+                    format!("{value:.1}")
+                }
+            }))
+            .with_string_to_value(Arc::new(|string: &str| {
+                let string = string.trim();
+                if string.eq_ignore_ascii_case("off") {
+                    Some(crosstalk::CROSSTALK_OFF_DB)
+                } else {
+                    string.trim_end_matches("dB").trim().parse().ok()
+                }
+            })),
+
+            // Louder passages bleed more, driven by the envelope follower
+            crosstalk_dynamics: FloatParam::new(
+                "Crosstalk Dynamics",
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_unit("%")
+            .with_value_to_string(formatters::v2s_f32_percentage(0))
+            .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            // Output transformer, lows saturate harder than highs
+            transformer: BoolParam::new("Transformer", false),
+
+            transformer_drive: FloatParam::new("Transformer Drive", 0.3, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            transformer_core: FloatParam::new("Core Size", 0.5, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            dc_block: BoolParam::new("DC Block", false),
+
+            // Oversampled true-peak limiter as the very last stage
+            true_peak: BoolParam::new("True Peak Limiter", false),
+
+            true_peak_ceiling: FloatParam::new("Ceiling", -1.0, FloatRange::Linear { min: -12.0, max: 0.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit(" dBTP")
+                .with_value_to_string(formatters::v2s_f32_rounded(1)),
+
+            true_peak_release: FloatParam::new(
+                "Limiter Release",
+                100.0,
+                FloatRange::Skewed {
+                    min: 10.0,
+                    max: 1000.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(0)),
+
+            dc_block_cutoff: FloatParam::new(
+                "DC Block Cutoff",
+                10.0,
+                FloatRange::Skewed {
+                    min: 5.0,
+                    max: 40.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+
+            dual_engine: BoolParam::new("Dual Engine", false),
+
+            engine_balance: FloatParam::new("A/B Balance", 0.5, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("% B")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            bank_morph: EnumParam::new("Bank Morph", engine::BankMorph::Linear),
+
+            // Bank B coeff parameter 1
+            slider_b1_coeff: FloatParam::new(
+                "B1",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B skew parameter 1
+            slider_b1_skew: FloatParam::new(
+                "B1 Skew",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B coeff parameter 2
+            slider_b2_coeff: FloatParam::new(
+                "B2",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B skew parameter 2
+            slider_b2_skew: FloatParam::new(
+                "B2 Skew",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B coeff parameter 3
+            slider_b3_coeff: FloatParam::new(
+                "B3",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B skew parameter 3
+            slider_b3_skew: FloatParam::new(
+                "B3 Skew",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B coeff parameter 4
+            slider_b4_coeff: FloatParam::new(
+                "B4",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B skew parameter 4
+            slider_b4_skew: FloatParam::new(
+                "B4 Skew",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B coeff parameter 5
+            slider_b5_coeff: FloatParam::new(
+                "B5",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B skew parameter 5
+            slider_b5_skew: FloatParam::new(
+                "B5 Skew",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B coeff parameter 6
+            slider_b6_coeff: FloatParam::new(
+                "B6",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B skew parameter 6
+            slider_b6_skew: FloatParam::new(
+                "B6 Skew",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B coeff parameter 7
+            slider_b7_coeff: FloatParam::new(
+                "B7",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B skew parameter 7
+            slider_b7_skew: FloatParam::new(
+                "B7 Skew",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B coeff parameter 8
+            slider_b8_coeff: FloatParam::new(
+                "B8",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Bank B skew parameter 8
+            slider_b8_skew: FloatParam::new(
+                "B8 Skew",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.5,
+                    max: 0.5,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            // Both channels share the banks by default
+            link: BoolParam::new("Link", true),
+
+            r1_coeff_offset: FloatParam::new(
+                "R1 Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r1_skew_offset: FloatParam::new(
+                "R1 Skew Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r2_coeff_offset: FloatParam::new(
+                "R2 Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r2_skew_offset: FloatParam::new(
+                "R2 Skew Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r3_coeff_offset: FloatParam::new(
+                "R3 Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r3_skew_offset: FloatParam::new(
+                "R3 Skew Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r4_coeff_offset: FloatParam::new(
+                "R4 Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r4_skew_offset: FloatParam::new(
+                "R4 Skew Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r5_coeff_offset: FloatParam::new(
+                "R5 Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r5_skew_offset: FloatParam::new(
+                "R5 Skew Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r6_coeff_offset: FloatParam::new(
+                "R6 Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r6_skew_offset: FloatParam::new(
+                "R6 Skew Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r7_coeff_offset: FloatParam::new(
+                "R7 Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r7_skew_offset: FloatParam::new(
+                "R7 Skew Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r8_coeff_offset: FloatParam::new(
+                "R8 Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            r8_skew_offset: FloatParam::new(
+                "R8 Skew Offset",
+                0.0,
+                FloatRange::Linear {
+                    min: -0.05,
+                    max: 0.05,
+                },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_value_to_string(slider_value_to_string(slider_readout.clone()))
+            .with_string_to_value(slider_string_to_value()),
+
+            multiband: BoolParam::new("Multiband", false),
+
+            crossover_low: FloatParam::new(
+                "Low Crossover",
+                200.0,
+                FloatRange::Skewed {
+                    min: 40.0,
+                    max: 1000.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_rounded(0)),
+
+            crossover_high: FloatParam::new(
+                "High Crossover",
+                3000.0,
+                FloatRange::Skewed {
+                    min: 1000.0,
+                    max: 12000.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_rounded(0)),
+
+            low_drive: FloatParam::new("Low Drive", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("% Pushed")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            mid_drive: FloatParam::new("Mid Drive", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("% Pushed")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            high_drive: FloatParam::new("High Drive", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("% Pushed")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            low_mix: FloatParam::new("Low Mix", 1.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("% Wet")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            mid_mix: FloatParam::new("Mid Mix", 1.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("% Wet")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            high_mix: FloatParam::new("High Mix", 1.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("% Wet")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            // Anything but 8 spreads the sliders over the taps as a curve
+            tap_count: IntParam::new(
+                "Tap Count",
+                engine::TAPS as i32,
+                IntRange::Linear {
+                    min: engine::MIN_TAPS as i32,
+                    max: engine::MAX_TAPS as i32,
+                },
+            )
+            .with_unit(" taps"),
+
+            feedback: FloatParam::new("Feedback", 0.0, FloatRange::Linear { min: 0.0, max: 0.9 })
+                .with_smoother(SmoothingStyle::Linear(30.0))
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            feedback_damping: FloatParam::new(
+                "Feedback Damping",
+                0.5,
+                FloatRange::Linear { min: 0.0, max: 0.95 },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_unit("%")
+            .with_value_to_string(formatters::v2s_f32_percentage(0))
+            .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            // Darkens the later taps, off by default so the classic sound stays untouched
+            tap_damping: FloatParam::new(
+                "Tap Damping",
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 0.95 },
+            )
+            .with_smoother(SmoothingStyle::Linear(30.0))
+            .with_unit("%")
+            .with_value_to_string(formatters::v2s_f32_percentage(0))
+            .with_string_to_value(formatters::s2v_f32_percentage()),
+
+            spacing_mode: EnumParam::new("Spacing Mode", engine::SpacingMode::Samples),
+
+            // Only heard in ms mode, defaults to one sample at 44.1 kHz
+            tap_spacing: FloatParam::new(
+                "Tap Spacing",
+                1000.0 / 44100.0,
+                FloatRange::Skewed {
+                    min: 0.01,
+                    max: engine::MAX_TAP_SPACING_MS,
+                    factor: FloatRange::skew_factor(-1.5),
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(3)),
+
+            tap_1_polarity: EnumParam::new(
+                "Tap 1 Polarity",
+                engine::TapPolarity::from_sign(engine::TAP_POLARITY[0]),
+            ),
+
+            tap_2_polarity: EnumParam::new(
+                "Tap 2 Polarity",
+                engine::TapPolarity::from_sign(engine::TAP_POLARITY[1]),
+            ),
+
+            tap_3_polarity: EnumParam::new(
+                "Tap 3 Polarity",
+                engine::TapPolarity::from_sign(engine::TAP_POLARITY[2]),
+            ),
+
+            tap_4_polarity: EnumParam::new(
+                "Tap 4 Polarity",
+                engine::TapPolarity::from_sign(engine::TAP_POLARITY[3]),
+            ),
+
+            tap_5_polarity: EnumParam::new(
+                "Tap 5 Polarity",
+                engine::TapPolarity::from_sign(engine::TAP_POLARITY[4]),
+            ),
+
+            tap_6_polarity: EnumParam::new(
+                "Tap 6 Polarity",
+                engine::TapPolarity::from_sign(engine::TAP_POLARITY[5]),
+            ),
+
+            tap_7_polarity: EnumParam::new(
+                "Tap 7 Polarity",
+                engine::TapPolarity::from_sign(engine::TAP_POLARITY[6]),
+            ),
+
+            tap_8_polarity: EnumParam::new(
+                "Tap 8 Polarity",
+                engine::TapPolarity::from_sign(engine::TAP_POLARITY[7]),
+            ),
+        }
+    }
+}
+
+// Coefficient/skew readout with a precision and format that follow the GUI settings
+fn slider_value_to_string(
+    readout: Arc<gui_settings::SliderReadout>,
+) -> Arc<dyn Fn(f32) -> String + Send + Sync> {
+    Arc::new(move |value| readout.format(value))
+}
+
+// Typed coefficient/skew values, accepts any of the display formats
+fn slider_string_to_value() -> Arc<dyn Fn(&str) -> Option<f32> + Send + Sync> {
+    Arc::new(gui_settings::parse_coeff)
+}
+
+// Next value of a smoothed param, snapped params jump to the target and drag the smoother
+// along so switching snap off later doesn't replay a stale ramp
+fn next_value(param: &FloatParam, snap: bool) -> f32 {
+    if snap {
+        param.smoothed.reset(param.value());
+    }
+    param.smoothed.next()
+}
+
+// Next values of a whole bank, `mask` holds one snap bit per slot in tap order
+fn next_bank(bank: [&FloatParam; 8], mask: u64) -> [f32; 8] {
+    let mut tap = 0;
+    bank.map(|param| {
+        let snap = mask & (1 << tap) != 0;
+        tap += 1;
+        next_value(param, snap)
+    })
+}
+
+// Macro knobs are 0-1 and default to the bottom of their mapped ranges
+fn macro_param(name: &str) -> FloatParam {
+    FloatParam::new(name, 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+        .with_smoother(SmoothingStyle::Linear(30.0))
+        .with_unit("%")
+        .with_value_to_string(formatters::v2s_f32_percentage(0))
+        .with_string_to_value(formatters::s2v_f32_percentage())
+}
+
+// Mapped destinations jump to the macro positions. With `bank_b` only the sliders are touched,
+// bank B already shares everything else with A
+fn apply_macros(
+    params: &GladeDeskParams,
+    console: &mut engine::ConsoleParams,
+    positions: &[Option<f32>; mod_matrix::ModDestination::COUNT],
+    bank_b: bool,
+) {
+    for (index, position) in positions.iter().enumerate() {
+        let Some(destination) = mod_matrix::ModDestination::from_index(index) else {
+            continue;
+        };
+        let slider = matches!(
+            destination,
+            mod_matrix::ModDestination::Coeff(_) | mod_matrix::ModDestination::Skew(_)
+        );
+        if let Some(position) = position.filter(|_| slider || !bank_b) {
+            *destination.value_mut(console) =
+                params.destination_param(destination, bank_b).preview_plain(position);
+        }
+    }
+}
+
+// `plain` moved by a normalized mod matrix offset, staying inside the param's range
+fn offset_plain(param: &FloatParam, plain: f32, offset: f32) -> f32 {
+    if offset == 0.0 {
+        plain
+    } else {
+        param.preview_plain(param.preview_normalized(plain) + offset)
+    }
+}
+
+// Status bar tracking plus a right click menu that pins the slider's param to a top row knob
+fn pin_to_top(
+    response: egui::Response,
+    params: &GladeDeskParams,
+    setter: &ParamSetter,
+    id: &str,
+) -> egui::Response {
+    let Some(param) = params.pinned_param(id) else {
+        return response;
+    };
+    let response = status_bar::track(response, param);
+    response.context_menu(|ui| {
+        user_config::favorites_menu(ui, param, id, setter);
+        ui.separator();
+        ui.label("Pin to top, replacing");
+        let pinned = params.top_knobs.read().unwrap().clone();
+        for (slot, pinned_id) in pinned.ids.iter().enumerate() {
+            let name = params
+                .pinned_param(pinned_id)
+                .map_or(pinned_id.as_str(), |param| param.name());
+            if ui.button(name).clicked() {
+                params.top_knobs.write().unwrap().ids[slot] = id.to_string();
+                ui.close_menu();
+            }
+        }
+    });
+    response
+}
+
+impl GladeDeskParams {
+    /// Bit in the snap mask for one bank slot. Each bank (A, B, R) gets 16 bits, coefficients
+    /// first and skews after.
+    fn snap_bit(bank: EditBank, skew: bool, tap: usize) -> u64 {
+        let bank = match bank {
+            EditBank::A => 0,
+            EditBank::B => 1,
+            EditBank::R => 2,
+        };
+        1 << (bank * 16 + if skew { 8 } else { 0 } + tap)
+    }
+
+    /// Snap mask bits of one bank half, shifted down so slot 0 is bit 0
+    fn snap_slots(&self, bank: EditBank, skew: bool) -> u64 {
+        let mask = self.snap_mask.load(std::sync::atomic::Ordering::Relaxed);
+        (mask >> Self::snap_bit(bank, skew, 0).trailing_zeros()) & 0xFF
+    }
+
+    fn is_snapped(&self, bit: u64) -> bool {
+        self.snap_mask.load(std::sync::atomic::Ordering::Relaxed) & bit != 0
+    }
+
+    fn set_snapped(&self, bit: u64, snap: bool) {
+        let mut flags = self.snap_flags.write().unwrap();
+        if snap {
+            *flags |= bit;
+        } else {
+            *flags &= !bit;
+        }
+        self.snap_mask
+            .store(*flags, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Float params that can be pinned to the top row, by param id
+    pub fn pinnable(&self) -> [(&'static str, &FloatParam); 55] {
+        [
+            ("free_gain", &self.free_gain),
+            ("Push", &self.push_amount),
+            ("Multiplier", &self.multiplier),
+            ("output_gain", &self.output_gain),
+            ("dry_wet", &self.dry_wet),
+            ("gate_attack", &self.gate_attack),
+            ("gate_release", &self.gate_release),
+            ("mod_rate", &self.mod_rate),
+            ("mod_smooth", &self.mod_smooth),
+            ("mod_depth", &self.mod_depth),
+            ("crush_bits", &self.crush_bits),
+            ("crush_mix", &self.crush_mix),
+            ("dc_block_cutoff", &self.dc_block_cutoff),
+            ("true_peak_ceiling", &self.true_peak_ceiling),
+            ("true_peak_release", &self.true_peak_release),
+            ("engine_balance", &self.engine_balance),
+            ("crossover_low", &self.crossover_low),
+            ("crossover_high", &self.crossover_high),
+            ("low_drive", &self.low_drive),
+            ("mid_drive", &self.mid_drive),
+            ("high_drive", &self.high_drive),
+            ("low_mix", &self.low_mix),
+            ("mid_mix", &self.mid_mix),
+            ("high_mix", &self.high_mix),
+            ("tap_spacing", &self.tap_spacing),
+            ("feedback", &self.feedback),
+            ("feedback_damping", &self.feedback_damping),
+            ("bias", &self.bias),
+            ("even_odd", &self.even_odd),
+            ("scene_fade", &self.scene_fade),
+            ("hpf_cutoff", &self.hpf_cutoff),
+            ("lpf_cutoff", &self.lpf_cutoff),
+            ("tilt", &self.tilt),
+            ("tilt_pivot", &self.tilt_pivot),
+            ("tape_drive", &self.tape_drive),
+            ("tape_bias", &self.tape_bias),
+            ("transformer_drive", &self.transformer_drive),
+            ("transformer_core", &self.transformer_core),
+            ("crosstalk", &self.crosstalk),
+            ("crosstalk_dynamics", &self.crosstalk_dynamics),
+            ("tap_damping", &self.tap_damping),
+            ("drift", &self.drift),
+            ("wow_depth", &self.wow_depth),
+            ("wow_rate", &self.wow_rate),
+            ("env_attack", &self.env_attack),
+            ("env_release", &self.env_release),
+            ("env_push", &self.env_push),
+            ("env_skew", &self.env_skew),
+            ("push_dynamics", &self.push_dynamics),
+            ("lfo_rate", &self.lfo_rate),
+            ("lfo_depth", &self.lfo_depth),
+            ("macro_1", &self.macro_1),
+            ("macro_2", &self.macro_2),
+            ("macro_3", &self.macro_3),
+            ("macro_4", &self.macro_4),
+        ]
+    }
+
+    /// Settings of the quality profile in use, None in Manual
+    fn quality_profile(&self, offline_render: bool) -> Option<quality::ProfileSettings> {
+        self.quality_mode
+            .value()
+            .profile(offline_render, self.zero_latency.value())
+            .map(|profile| {
+                quality::unpack(
+                    self.quality_mask.load(std::sync::atomic::Ordering::Relaxed),
+                    profile,
+                )
+            })
+    }
+
+    fn set_quality_profile(
+        &self,
+        profile: quality::QualityProfile,
+        settings: quality::ProfileSettings,
+    ) {
+        let mut profiles = self.quality_profiles.write().unwrap();
+        *profiles.get_mut(profile) = settings;
+        self.quality_mask
+            .store(profiles.pack(), std::sync::atomic::Ordering::Relaxed);
+    }
+
+    fn set_mod_matrix(&self, matrix: mod_matrix::ModMatrix) {
+        self.mod_routes.store(&matrix);
+        *self.mod_matrix.write().unwrap() = matrix;
+    }
+
+    fn set_random_seed(&self, seed: u32) {
+        self.seed.store(seed, std::sync::atomic::Ordering::Relaxed);
+        *self.random_seed.write().unwrap() = seed;
+    }
+
+    fn set_loudness_delta(&self, delta_db: Option<f32>) {
+        self.loudness_delta_db
+            .store(delta_db.unwrap_or(0.0), std::sync::atomic::Ordering::Relaxed);
+        *self.loudness_delta.write().unwrap() = delta_db;
+    }
+
+    fn set_macro_map(&self, map: macros::MacroMap) {
+        self.macro_targets.store(&map);
+        *self.macro_map.write().unwrap() = map;
+    }
+
+    /// Oversampling that actually runs. A quality profile takes over from the Oversampling
+    /// param, and Zero Latency holds it at 1x except while the host renders offline
+    pub fn active_oversampling(&self, offline_render: bool) -> oversampling::Oversampling {
+        match self.quality_profile(offline_render) {
+            Some(profile) if offline_render => profile.oversampling(),
+            _ if self.zero_latency.value() => oversampling::Oversampling::X1,
+            Some(profile) => profile.oversampling(),
+            None => self.oversampling.value(),
+        }
+    }
+
+    /// HQ Shaper param, or the quality profile's when one is in use
+    pub fn active_hq_shaper(&self, offline_render: bool) -> bool {
+        self.quality_profile(offline_render)
+            .map_or(self.hq_shaper.value(), |profile| profile.hq_shaper)
+    }
+
+    /// Tap spacing in ms, None when the taps sit one sample apart
+    pub fn tap_spacing_ms(&self) -> Option<f32> {
+        (self.spacing_mode.value() == engine::SpacingMode::Milliseconds)
+            .then(|| self.tap_spacing.value())
+    }
+
+    fn pinned_param(&self, id: &str) -> Option<&FloatParam> {
+        self.pinnable()
+            .into_iter()
+            .find(|(pin_id, _)| *pin_id == id)
+            .map(|(_, param)| param)
+    }
+
+    /// Chebyshev designer levels, harmonic 2 first
+    pub fn harmonic_bank(&self) -> [&FloatParam; engine::HARMONICS] {
+        [
+            &self.harmonic_2,
+            &self.harmonic_3,
+            &self.harmonic_4,
+            &self.harmonic_5,
+            &self.harmonic_6,
+            &self.harmonic_7,
+            &self.harmonic_8,
+        ]
+    }
+
+    /// The per tap polarity switches in tap order
+    pub fn polarity_bank(&self) -> [&EnumParam<engine::TapPolarity>; 8] {
+        [
+            &self.tap_1_polarity,
+            &self.tap_2_polarity,
+            &self.tap_3_polarity,
+            &self.tap_4_polarity,
+            &self.tap_5_polarity,
+            &self.tap_6_polarity,
+            &self.tap_7_polarity,
+            &self.tap_8_polarity,
+        ]
+    }
+
+    /// The eight coefficient sliders in tap order
+    pub fn coeff_bank(&self) -> [&FloatParam; 8] {
+        [
+            &self.slider_1_coeff,
+            &self.slider_2_coeff,
+            &self.slider_3_coeff,
+            &self.slider_4_coeff,
+            &self.slider_5_coeff,
+            &self.slider_6_coeff,
+            &self.slider_7_coeff,
+            &self.slider_8_coeff,
+        ]
+    }
+
+    /// Current unsmoothed values in the shape the console engine wants
+    pub fn console_params(&self) -> engine::ConsoleParams {
+        engine::ConsoleParams {
+            input_gain: self.free_gain.value(),
+            push: self.push_amount.value(),
+            multiplier: self.multiplier.value(),
+            coeffs: self.coeff_bank().map(|param| param.value()),
+            skews: self.skew_bank().map(|param| param.value()),
+            wet: self.dry_wet.value(),
+            output_gain: self.output_gain.value(),
+            hq_shaper: self.hq_shaper.value(),
+            push_curve: self.push_curve.value(),
+            bias: self.bias.value(),
+            even_odd: self.even_odd.value(),
+            harmonic_mode: self.harmonic_mode.value(),
+            harmonics: self.harmonic_bank().map(|param| param.value()),
+            tap_count: self.tap_count.value() as usize,
+            polarity: self.polarity_bank().map(|param| param.value().sign()),
+            feedback: self.feedback.value(),
+            feedback_damping: self.feedback_damping.value(),
+            tap_damping: self.tap_damping.value(),
+        }
+    }
+
+    /// Snapshot of everything the offline renderer uses
+    pub fn offline_settings(&self) -> offline::OfflineSettings {
+        let mut console = self.console_params();
+        let macro_positions = macros::positions(
+            &self.macro_targets.load(),
+            &self.macro_bank().map(|param| param.value()),
+        );
+        apply_macros(self, &mut console, &macro_positions, false);
+        let mut console_b = engine::ConsoleParams {
+            coeffs: self.coeff_bank_b().map(|param| param.value()),
+            skews: self.skew_bank_b().map(|param| param.value()),
+            ..console
+        };
+        apply_macros(self, &mut console_b, &macro_positions, true);
+        let settings = offline::OfflineSettings {
+            console,
+            console_b: self
+                .dual_engine
+                .value()
+                .then_some((console_b, self.engine_balance.value())),
+            oversampling: self.oversampling.value(),
+            lofi_ratio: self.lofi_ratio.value(),
+            lofi_interp: self.lofi_interp.value(),
+            crush_bits: self.crush_bits.value(),
+            crush_mix: self.crush_mix.value(),
+            dc_trim: [self.dc_trim_l.value(), self.dc_trim_r.value()],
+            mix_law: self.mix_law.value(),
+            calibration: self.calibration.value(),
+            console_mode: self.console_mode.value(),
+            bank_morph: self.bank_morph.value(),
+            right_offsets: (!self.link.value()).then(|| {
+                (
+                    self.r_coeff_offsets().map(|param| param.value()),
+                    self.r_skew_offsets().map(|param| param.value()),
+                )
+            }),
+            dc_block_cutoff: self.dc_block.value().then_some(self.dc_block_cutoff.value()),
+            auto_gain: self.auto_gain.value(),
+            output_invert: self.output_invert.value(),
+            delta: self.delta.value(),
+            true_peak: self
+                .true_peak
+                .value()
+                .then(|| (self.true_peak_ceiling.value(), self.true_peak_release.value())),
+            multiband: self.multiband.value().then(|| multiband::MultibandSettings {
+                crossovers: (self.crossover_low.value(), self.crossover_high.value()),
+                drive: [
+                    self.low_drive.value(),
+                    self.mid_drive.value(),
+                    self.high_drive.value(),
+                ],
+                mix: [self.low_mix.value(), self.mid_mix.value(), self.high_mix.value()],
+            }),
+            tap_spacing_ms: self.tap_spacing_ms(),
+            wow_flutter: (self.wow_depth.value() > 0.0)
+                .then(|| (self.wow_rate.value(), self.wow_depth.value())),
+            tape: self
+                .tape
+                .value()
+                .then(|| (self.tape_drive.value(), self.tape_bias.value())),
+            hpf: (self.hpf_slope.value(), self.hpf_cutoff.value()),
+            tilt: (
+                self.tilt.value(),
+                self.tilt_pivot.value(),
+                self.tilt_placement.value(),
+            ),
+            lpf: (self.lpf_slope.value(), self.lpf_cutoff.value()),
+            transformer: self
+                .transformer
+                .value()
+                .then(|| (self.transformer_drive.value(), self.transformer_core.value())),
+        };
+        // The Render button is a render too
+        match self.quality_profile(true) {
+            Some(profile) => settings.with_quality(profile),
+            None => settings,
+        }
+    }
+
+    /// Coefficients followed by skews, the values a morph capture records
+    pub fn morph_bank(&self) -> [&FloatParam; morph_capture::MORPH_VALUES] {
+        [
+            &self.slider_1_coeff,
+            &self.slider_2_coeff,
+            &self.slider_3_coeff,
+            &self.slider_4_coeff,
+            &self.slider_5_coeff,
+            &self.slider_6_coeff,
+            &self.slider_7_coeff,
+            &self.slider_8_coeff,
+            &self.slider_1_skew,
+            &self.slider_2_skew,
+            &self.slider_3_skew,
+            &self.slider_4_skew,
+            &self.slider_5_skew,
+            &self.slider_6_skew,
+            &self.slider_7_skew,
+            &self.slider_8_skew,
+        ]
+    }
+
+    /// Bank B coefficients for the dual engine, in tap order
+    pub fn coeff_bank_b(&self) -> [&FloatParam; 8] {
+        [
+            &self.slider_b1_coeff,
+            &self.slider_b2_coeff,
+            &self.slider_b3_coeff,
+            &self.slider_b4_coeff,
+            &self.slider_b5_coeff,
+            &self.slider_b6_coeff,
+            &self.slider_b7_coeff,
+            &self.slider_b8_coeff,
+        ]
+    }
+
+    /// Bank B skews for the dual engine, in tap order
+    pub fn skew_bank_b(&self) -> [&FloatParam; 8] {
+        [
+            &self.slider_b1_skew,
+            &self.slider_b2_skew,
+            &self.slider_b3_skew,
+            &self.slider_b4_skew,
+            &self.slider_b5_skew,
+            &self.slider_b6_skew,
+            &self.slider_b7_skew,
+            &self.slider_b8_skew,
+        ]
+    }
+
+    /// The four macro knobs in order
+    pub fn macro_bank(&self) -> [&FloatParam; macros::MACROS] {
+        [&self.macro_1, &self.macro_2, &self.macro_3, &self.macro_4]
+    }
+
+    /// The param behind a mod matrix or macro destination, bank B's sliders when asked
+    pub fn destination_param(
+        &self,
+        destination: mod_matrix::ModDestination,
+        bank_b: bool,
+    ) -> &FloatParam {
+        match destination {
+            mod_matrix::ModDestination::Push => &self.push_amount,
+            mod_matrix::ModDestination::Multiplier => &self.multiplier,
+            mod_matrix::ModDestination::DryWet => &self.dry_wet,
+            mod_matrix::ModDestination::Bias => &self.bias,
+            mod_matrix::ModDestination::EvenOdd => &self.even_odd,
+            mod_matrix::ModDestination::Feedback => &self.feedback,
+            mod_matrix::ModDestination::FeedbackDamping => &self.feedback_damping,
+            mod_matrix::ModDestination::TapDamping => &self.tap_damping,
+            mod_matrix::ModDestination::Coeff(tap) if bank_b => {
+                self.coeff_bank_b()[tap.min(engine::TAPS - 1)]
+            }
+            mod_matrix::ModDestination::Coeff(tap) => self.coeff_bank()[tap.min(engine::TAPS - 1)],
+            mod_matrix::ModDestination::Skew(tap) if bank_b => {
+                self.skew_bank_b()[tap.min(engine::TAPS - 1)]
+            }
+            mod_matrix::ModDestination::Skew(tap) => self.skew_bank()[tap.min(engine::TAPS - 1)],
+        }
+    }
+
+    /// Right channel coefficient offsets used while unlinked
+    pub fn r_coeff_offsets(&self) -> [&FloatParam; 8] {
+        [
+            &self.r1_coeff_offset,
+            &self.r2_coeff_offset,
+            &self.r3_coeff_offset,
+            &self.r4_coeff_offset,
+            &self.r5_coeff_offset,
+            &self.r6_coeff_offset,
+            &self.r7_coeff_offset,
+            &self.r8_coeff_offset,
+        ]
+    }
+
+    /// Right channel skew offsets used while unlinked
+    pub fn r_skew_offsets(&self) -> [&FloatParam; 8] {
+        [
+            &self.r1_skew_offset,
+            &self.r2_skew_offset,
+            &self.r3_skew_offset,
+            &self.r4_skew_offset,
+            &self.r5_skew_offset,
+            &self.r6_skew_offset,
+            &self.r7_skew_offset,
+            &self.r8_skew_offset,
+        ]
+    }
+
+    /// The eight skew sliders in tap order
+    pub fn skew_bank(&self) -> [&FloatParam; 8] {
+        [
+            &self.slider_1_skew,
+            &self.slider_2_skew,
+            &self.slider_3_skew,
+            &self.slider_4_skew,
+            &self.slider_5_skew,
+            &self.slider_6_skew,
+            &self.slider_7_skew,
+            &self.slider_8_skew,
+        ]
+    }
+
+    /// The sound-shaping params for the diagnostic log, fixed size so the audio thread can copy it
+    pub fn diagnostic_context(&self) -> diagnostics::DiagnosticContext {
+        diagnostics::DiagnosticContext {
+            input_gain: self.free_gain.value(),
+            push: self.push_amount.value(),
+            multiplier: self.multiplier.value(),
+            output_gain: self.output_gain.value(),
+            dry_wet: self.dry_wet.value(),
+            coeffs: self.coeff_bank().map(|param| param.value()),
+            skews: self.skew_bank().map(|param| param.value()),
+        }
+    }
+}
+
+impl GladeDesk {
+    /// Everything that delays the output: the oversampling filters, the limiter lookahead and
+    /// the wow and flutter centre
+    fn total_latency(&self) -> u32 {
+        let lookahead = if self.true_peak_lookahead {
+            true_peak::TruePeakLimiter::lookahead_samples(self.sample_rate)
+        } else {
+            0
+        };
+        let wow_flutter = if self.wow_flutter_on {
+            self.left_wow_flutter.latency_samples()
+        } else {
+            0
+        };
+        self.oversampling.latency_samples() + lookahead + wow_flutter
+    }
+
+    /// Wow and flutter runs while it has depth, Safe Mode and Zero Latency turn it off
+    fn wow_flutter_active(&self) -> bool {
+        self.params.wow_depth.value() > 0.0
+            && !self.params.zero_latency.value()
+            && !self.safe_mode.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Random mod and drift back to the start of `seed`'s sequence
+    fn reseed(&mut self, seed: u32) {
+        self.active_seed = seed;
+        self.random_mod.reset(seed);
+        for (tap, source) in self.drift.iter_mut().enumerate() {
+            source.reset(seed.rotate_left(tap as u32 + 1));
+        }
+    }
+
+    /// Point the oversamplers and the crossovers at the new rate. Tap spacing follows in
+    /// process() since it can be automated.
+    fn set_oversampling(&mut self, oversampling: oversampling::Oversampling) {
+        self.oversampling = oversampling;
+        self.latency_samples.store(
+            self.total_latency() as usize,
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        self.left_oversampler.set_oversampling(oversampling);
+        self.right_oversampler.set_oversampling(oversampling);
+        // The crossovers are designed at the rate the bands actually run at
+        let band_rate = self.sample_rate * oversampling.factor() as f32;
+        for multiband in [&mut self.left_multiband, &mut self.right_multiband] {
+            multiband.set_sample_rate(band_rate);
+        }
+    }
+}
+
+impl Plugin for GladeDesk {
+    const NAME: &'static str = "Glade Desk";
+    const VENDOR: &'static str = "Ardura";
+    const URL: &'static str = "https://github.com/ardura";
+    const EMAIL: &'static str = "azviscarra@gmail.com";
+
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    // This looks like it's flexible for running the plugin in mono or stereo. The meters can show
+    // up to 8 channels but the processing is written for two, so no surround layouts are offered
+    // until the console runs per channel
+    const AUDIO_IO_LAYOUTS: &'static [AudioIOLayout] = &[
+        AudioIOLayout {
+            main_input_channels: NonZeroU32::new(2),
+            main_output_channels: NonZeroU32::new(2),
+            aux_input_ports: &[new_nonzero_u32(2)],
+            aux_output_ports: &[new_nonzero_u32(2)],
+            names: PortNames {
+                aux_inputs: &["Sidechain"],
+                aux_outputs: &["Delta"],
+                ..PortNames::const_default()
+            },
+            ..AudioIOLayout::const_default()
+        },
+        AudioIOLayout {
+            main_input_channels: NonZeroU32::new(1),
+            main_output_channels: NonZeroU32::new(1),
+            aux_input_ports: &[new_nonzero_u32(1)],
+            aux_output_ports: &[new_nonzero_u32(1)],
+            names: PortNames {
+                aux_inputs: &["Sidechain"],
+                aux_outputs: &["Delta"],
+                ..PortNames::const_default()
+            },
+            ..AudioIOLayout::const_default()
+        },
+    ];
+
+    const SAMPLE_ACCURATE_AUTOMATION: bool = true;
+
+    type SysExMessage = ();
+    type BackgroundTask = Task;
+
+    fn params(&self) -> Arc<dyn Params> {
+        self.params.clone()
+    }
+
+    fn editor(&mut self, async_executor: AsyncExecutor<Self>) -> Option<Box<dyn Editor>> {
+        let params = self.params.clone();
+        let in_meter = self.in_meter.clone();
         let out_meter = self.out_meter.clone();
+        let session_sample_rate = self.session_sample_rate.clone();
+        let max_buffer_size = self.max_buffer_size.clone();
+        let latency_samples = self.latency_samples.clone();
+        let render_status = self.render_status.clone();
+        let null_test_shared = self.null_test_shared.clone();
+        let reduced_motion = self.reduced_motion.clone();
+        let normalize_loudness = self.normalize_loudness.clone();
+        let tap_energy = self.tap_energy.clone();
+        let mod_scope_value = self.mod_scope_value.clone();
+        let safety_engaged = self.safety_engaged.clone();
+        let safety_dismiss = self.safety_dismiss.clone();
+        let safe_mode = self.safe_mode.clone();
+        let auto_gain_trim = self.auto_gain_trim.clone();
+        let gain_match = self.gain_match.clone();
+        let signal_presence = self.signal_presence.clone();
+        let slider_readout = self.params.slider_readout.clone();
+        let viz_buffers = self.viz_buffers.clone();
+        let viz_buffers_open = self.viz_buffers.clone();
         create_egui_editor(
             self.params.editor_state.clone(),
-            (),
-            |_, _| {},
-            move |egui_ctx, setter, _state| {
+            EditorUiState::default(),
+            move |_, _| viz_buffers_open.open(),
+            move |egui_ctx, setter, ui_state| {
+                let settings = params.gui_settings.read().unwrap().clone();
+                let instance_tag = params.instance_tag.read().unwrap().clone();
+                let routing_labels = params.routing_labels.read().unwrap().clone();
+                let theme = settings.theme(NORMAL_THEME);
+                let font_scale = settings.font_scale;
+                slider_readout.store(&settings);
+                // Normally there since the window opened, the fallback covers a release that
+                // landed after a quick close and reopen
+                let mut viz_guard = viz_buffers.lock();
+                let viz = viz_guard.get_or_insert_with(viz_buffers::VizBuffers::new);
+                // Meters and scopes move on their own, so schedule the next frame at the chosen
+                // rate. Input still repaints right away.
+                egui_ctx.request_repaint_after(settings.frame_rate.interval());
+
+                // Scale the built in text styles too so ParamSliders and buttons follow along
+                if ui_state.applied_font_scale != Some(font_scale) {
+                    let mut style = egui::Style::default();
+                    for font_id in style.text_styles.values_mut() {
+                        font_id.size *= font_scale;
+                    }
+                    egui_ctx.set_style(style);
+                    ui_state.applied_font_scale = Some(font_scale);
+                }
+                // The audio thread only looks at this for meter ballistics
+                reduced_motion.store(settings.reduced_motion, std::sync::atomic::Ordering::Relaxed);
+                // Another instance may have changed it, the config is shared
+                normalize_loudness.store(
+                    user_config::normalize_presets(),
+                    std::sync::atomic::Ordering::Relaxed,
+                );
+
+                // Name, value and range of whatever was hovered last frame, doubles as a
+                // screen reader friendly readout
+                let hover_status = status_bar::take(egui_ctx);
+                egui::TopBottomPanel::bottom("status_bar")
+                    .frame(
+                        egui::Frame::none()
+                            .fill(theme.background)
+                            .inner_margin(egui::Margin::symmetric(8.0, 2.0)),
+                    )
+                    .show(egui_ctx, |ui| {
+                        ui.label(
+                            RichText::new(
+                                hover_status
+                                    .unwrap_or_else(|| "Hover a control for its details".to_string()),
+                            )
+                            .font(FontId::monospace(11.0 * font_scale))
+                            .color(theme.accent),
+                        );
+                    });
+
                 egui::CentralPanel::default().show(egui_ctx, |ui| {
                     // Change colors - there's probably a better way to do this
                     let style_var = ui.style_mut().clone();
@@ -435,9 +3096,21 @@ impl Plugin for GladeDesk {
                             RangeInclusive::new(0.0, HEIGHT as f32),
                         ),
                         Rounding::from(16.0),
-                        A_BACKGROUND_COLOR,
+                        theme.background,
                     );
 
+                    // Header strip in the instance tag color
+                    if let Some(tag_color) = instance_tag.color32() {
+                        ui.painter().rect_filled(
+                            Rect::from_x_y_ranges(
+                                RangeInclusive::new(0.0, WIDTH as f32),
+                                RangeInclusive::new(0.0, 4.0),
+                            ),
+                            Rounding::ZERO,
+                            tag_color,
+                        );
+                    }
+
                     // Screws for that vintage look
                     let screw_space = 10.0;
                     ui.painter().circle_filled(
@@ -455,223 +3128,2280 @@ impl Plugin for GladeDesk {
 
                     // GUI Structure
                     ui.vertical(|ui| {
-                        // Spacing :)
-                        ui.label(
-                            RichText::new("    Glade Desk")
-                                .font(FontId::proportional(14.0))
-                                .color(A_KNOB_OUTSIDE_COLOR),
-                        )
-                        .on_hover_text("by Ardura!");
-
-                        // Peak Meters
-                        let in_meter =
-                            util::gain_to_db(in_meter.load(std::sync::atomic::Ordering::Relaxed));
-                        let in_meter_text = if in_meter > util::MINUS_INFINITY_DB {
-                            format!("{in_meter:.1} dBFS Input")
-                        } else {
-                            String::from("-inf dBFS Input")
-                        };
-                        let in_meter_normalized = (in_meter + 60.0) / 60.0;
-                        ui.allocate_space(egui::Vec2::splat(2.0));
-                        let mut in_meter_obj =
-                            db_meter::DBMeter::new(in_meter_normalized).text(in_meter_text);
-                        in_meter_obj.set_background_color(A_KNOB_OUTSIDE_COLOR);
-                        in_meter_obj.set_bar_color(A_KNOB_INSIDE_COLOR);
-                        in_meter_obj.set_border_color(Color32::BLACK);
-                        ui.add(in_meter_obj);
-
-                        let out_meter =
-                            util::gain_to_db(out_meter.load(std::sync::atomic::Ordering::Relaxed));
-                        let out_meter_text = if out_meter > util::MINUS_INFINITY_DB {
-                            format!("{out_meter:.1} dBFS Output")
-                        } else {
-                            String::from("-inf dBFS Output")
-                        };
-                        let out_meter_normalized = (out_meter + 60.0) / 60.0;
-                        ui.allocate_space(egui::Vec2::splat(2.0));
-                        let mut out_meter_obj =
-                            db_meter::DBMeter::new(out_meter_normalized).text(out_meter_text);
-                        out_meter_obj.set_background_color(A_KNOB_OUTSIDE_COLOR);
-                        out_meter_obj.set_bar_color(A_KNOB_INSIDE_COLOR);
-                        out_meter_obj.set_border_color(Color32::BLACK);
-                        ui.add(out_meter_obj);
-
-                        // Knobs and labels
-                        ui.horizontal(|ui| {
-                            let knob_size = 37.0;
-                            let gain_knob = ui_knob::ArcKnob::for_param(
-                                &params.free_gain,
-                                setter,
-                                knob_size,
-                                ui_knob::KnobLayout::Vertical,
-                            )
-                                .preset_style(ui_knob::KnobStyle::Preset1)
-                                .set_text_size(11.0)
-                                .set_fill_color(A_KNOB_INSIDE_COLOR)
-                                .set_line_color(A_KNOB_OUTSIDE_COLOR);
-                            ui.add(gain_knob);
-
-                            let push_knob = ui_knob::ArcKnob::for_param(
-                                &params.push_amount,
-                                setter,
-                                knob_size,
-                                ui_knob::KnobLayout::Vertical,
-                            )
-                                .preset_style(ui_knob::KnobStyle::Preset1)
-                                .set_text_size(11.0)
-                                .set_fill_color(A_KNOB_INSIDE_COLOR)
-                                .set_line_color(A_KNOB_OUTSIDE_COLOR);
-                            ui.add(push_knob);
-
-                            let multiplier_knob = ui_knob::ArcKnob::for_param(
-                                &params.multiplier,
-                                setter,
-                                knob_size,
-                                ui_knob::KnobLayout::Vertical,
-                            )
-                                .preset_style(ui_knob::KnobStyle::Preset1)
-                                .set_text_size(11.0)
-                                .set_fill_color(A_KNOB_INSIDE_COLOR)
-                                .set_line_color(A_KNOB_OUTSIDE_COLOR);
-                            ui.add(multiplier_knob);
-
-                            let output_knob = ui_knob::ArcKnob::for_param(
-                                &params.output_gain,
-                                setter,
-                                knob_size,
-                                ui_knob::KnobLayout::Vertical,
-                            )
-                                .preset_style(ui_knob::KnobStyle::Preset1)
-                                .set_text_size(11.0)
-                                .set_fill_color(A_KNOB_INSIDE_COLOR)
-                                .set_line_color(A_KNOB_OUTSIDE_COLOR);
-                            ui.add(output_knob);
-
-                            let dry_wet_knob = ui_knob::ArcKnob::for_param(
-                                &params.dry_wet,
-                                setter,
-                                knob_size,
-                                ui_knob::KnobLayout::Vertical,
-                            )
-                                .preset_style(ui_knob::KnobStyle::Preset1)
-                                .set_text_size(11.0)
-                                .set_fill_color(A_KNOB_INSIDE_COLOR)
-                                .set_line_color(A_KNOB_OUTSIDE_COLOR);
-                            ui.add(dry_wet_knob);
+                        ui.horizontal(|ui| {
+                            // Spacing :)
+                            let title = ui
+                                .add(
+                                    egui::Label::new(
+                                        RichText::new("    Glade Desk")
+                                            .font(FontId::proportional(14.0 * font_scale))
+                                            .color(theme.accent),
+                                    )
+                                    .sense(egui::Sense::click()),
+                                )
+                                .on_hover_text("by Ardura!");
+                            // Instance tag pill
+                            if !instance_tag.is_empty() {
+                                let tag_color = instance_tag.color32().unwrap_or(theme.inside);
+                                let tag_text = if instance_tag.name.trim().is_empty() {
+                                    "   ".to_string()
+                                } else {
+                                    format!(" {} ", instance_tag.name.trim())
+                                };
+                                let tag = ui
+                                    .add(
+                                        egui::Label::new(
+                                            RichText::new(tag_text)
+                                                .font(FontId::proportional(12.0 * font_scale))
+                                                .color(gui_settings::contrasting_text(tag_color))
+                                                .background_color(tag_color),
+                                        )
+                                        .sense(egui::Sense::click()),
+                                    )
+                                    .on_hover_text("Instance tag, click to edit");
+                                if tag.clicked() {
+                                    ui_state.show_settings = true;
+                                }
+                            }
+                            // Channel strip routing
+                            if let Some(routing) = routing_labels.header() {
+                                let routing = ui
+                                    .add(
+                                        egui::Label::new(
+                                            RichText::new(routing)
+                                                .font(FontId::proportional(12.0 * font_scale))
+                                                .color(theme.inside),
+                                        )
+                                        .sense(egui::Sense::click()),
+                                    )
+                                    .on_hover_text("Channel strip routing, click to edit");
+                                if routing.clicked() {
+                                    ui_state.show_settings = true;
+                                }
+                            }
+                            // Hidden self test panel
+                            if title.clicked() && ui.input(|i| i.modifiers.command) {
+                                ui_state.show_self_test = !ui_state.show_self_test;
+                            }
+                            if ui.small_button("Settings").clicked() {
+                                ui_state.show_settings = !ui_state.show_settings;
+                            }
                         });
 
-                        //sliders
-                        ui.vertical(|ui| {
+                        // Clip storm warning until the user dismisses it
+                        if safety_engaged.load(std::sync::atomic::Ordering::Relaxed) {
                             ui.horizontal(|ui| {
                                 ui.label(
-                                    RichText::new("Coefficient Value")
-                                        .font(FontId::proportional(14.0))
-                                        .color(A_KNOB_OUTSIDE_COLOR),
-                                );
-                                ui.add_space(160.0);
-                                ui.label(
-                                    RichText::new("Skew Value")
-                                        .font(FontId::proportional(14.0))
-                                        .color(A_KNOB_OUTSIDE_COLOR),
+                                    RichText::new("Safety limiter engaged - the output was clipping hard")
+                                        .font(FontId::proportional(12.0 * font_scale))
+                                        .color(Color32::WHITE)
+                                        .background_color(Color32::from_rgb(170, 20, 20)),
                                 );
+                                if ui
+                                    .small_button("Dismiss")
+                                    .on_hover_text("Turn the limiter back off, it engages again if the clipping continues")
+                                    .clicked()
+                                {
+                                    safety_dismiss.store(true, std::sync::atomic::Ordering::Relaxed);
+                                }
                             });
+                        }
+
+                        // Safe mode banner after a crash
+                        if safe_mode.load(std::sync::atomic::Ordering::Relaxed) {
                             ui.horizontal(|ui| {
-                                ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_1_coeff, setter)
-                                        .with_width(180.0),
-                                );
-                                ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_1_skew, setter)
-                                        .with_width(180.0),
+                                ui.label(
+                                    RichText::new("Safe mode - the last session crashed with Glade Desk active, Push, Harmonics, Tape, Transformer and Feedback are bypassed")
+                                        .font(FontId::proportional(12.0 * font_scale))
+                                        .color(Color32::BLACK)
+                                        .background_color(Color32::from_rgb(230, 180, 40)),
                                 );
+                                if ui
+                                    .small_button("Restore")
+                                    .on_hover_text("Run the saved settings again")
+                                    .clicked()
+                                {
+                                    safe_mode.store(false, std::sync::atomic::Ordering::Relaxed);
+                                }
                             });
+                        }
+
+                        // Peak Meters
+                        // One bar per channel of whatever layout the host negotiated, played out
+                        // at the sample the audio thread is estimated to be on
+                        let (newest_meter, meter_block) = out_meter.newest();
+                        let meter_position = ui_state.meter_clock.position(
+                            newest_meter,
+                            meter_block,
+                            ui.input(|i| i.time),
+                            session_sample_rate.load(std::sync::atomic::Ordering::Relaxed),
+                        );
+                        let vertical_meters =
+                            settings.meter_layout == gui_settings::MeterLayout::Vertical;
+                        let styled_meter = |level: f32, text: String| {
+                            let mut meter_obj =
+                                db_meter::DBMeter::new((level + 60.0) / 60.0).text(text);
+                            meter_obj.set_background_color(theme.accent);
+                            meter_obj.set_bar_color(theme.inside);
+                            meter_obj.set_border_color(Color32::BLACK);
+                            meter_obj.set_stroke_width(theme.stroke_width);
+                            meter_obj.set_text_size(11.0 * font_scale);
+                            meter_obj
+                        };
+                        let meter_level = |meters: &ChannelMeters, channel: usize| {
+                            let level =
+                                util::gain_to_db(meters.level_at(channel, meter_position));
+                            if settings.reduced_motion {
+                                level.round()
+                            } else {
+                                level
+                            }
+                        };
+                        if vertical_meters {
+                            // Input and output groups side by side, the value on top of each bar
+                            // and the channel underneath
+                            ui.allocate_space(egui::Vec2::splat(2.0));
                             ui.horizontal(|ui| {
-                                ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_2_coeff, setter)
-                                        .with_width(180.0),
-                                );
-                                ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_2_skew, setter)
-                                        .with_width(180.0),
-                                );
+                                for (meters, name) in [(&in_meter, "In"), (&out_meter, "Out")] {
+                                    let channels = meters.channels();
+                                    for channel in 0..channels {
+                                        let level = meter_level(meters, channel);
+                                        let channel_name =
+                                            channel_meters::channel_name(channels, channel);
+                                        let meter_text = if level > util::MINUS_INFINITY_DB {
+                                            format!("{level:.0}")
+                                        } else {
+                                            "-inf".to_string()
+                                        };
+                                        ui.vertical(|ui| {
+                                            ui.add(
+                                                styled_meter(level, meter_text)
+                                                    .orientation(
+                                                        db_meter::MeterOrientation::Vertical,
+                                                    )
+                                                    .desired_width(36.0 * font_scale)
+                                                    .desired_height(96.0 * font_scale),
+                                            );
+                                            ui.label(
+                                                RichText::new(format!("{name} {channel_name}"))
+                                                    .size(10.0 * font_scale),
+                                            );
+                                        });
+                                    }
+                                    ui.add_space(12.0);
+                                }
                             });
-                            ui.horizontal(|ui| {
-                                ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_3_coeff, setter)
-                                        .with_width(180.0),
+                        } else {
+                            for (meters, name) in [(&in_meter, "Input"), (&out_meter, "Output")] {
+                                let channels = meters.channels();
+                                for channel in 0..channels {
+                                    let level = meter_level(meters, channel);
+                                    let channel_name =
+                                        channel_meters::channel_name(channels, channel);
+                                    let meter_text = if level > util::MINUS_INFINITY_DB {
+                                        format!("{level:.1} dBFS {name} {channel_name}")
+                                    } else {
+                                        format!("-inf dBFS {name} {channel_name}")
+                                    };
+                                    ui.allocate_space(egui::Vec2::splat(2.0));
+                                    ui.add(styled_meter(level, meter_text));
+                                }
+                            }
+                        }
+
+                        // Signal present LED per input channel
+                        ui.horizontal(|ui| {
+                            let channels = in_meter.channels();
+                            for channel in 0..channels {
+                                let channel_name = channel_meters::channel_name(channels, channel);
+                                let present = signal_presence.present(channel);
+                                let (rect, response) = ui.allocate_exact_size(
+                                    egui::Vec2::splat(10.0 * font_scale),
+                                    egui::Sense::hover(),
                                 );
-                                ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_3_skew, setter)
-                                        .with_width(180.0),
+                                ui.painter().circle_filled(
+                                    rect.center(),
+                                    4.0 * font_scale,
+                                    if present {
+                                        Color32::from_rgb(60, 220, 90)
+                                    } else {
+                                        Color32::DARK_GRAY
+                                    },
                                 );
-                            });
-                            ui.horizontal(|ui| {
-                                ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_4_coeff, setter)
-                                        .with_width(180.0),
+                                response.on_hover_text(format!("Signal present on {channel_name}"));
+                                ui.label(
+                                    RichText::new(channel_name)
+                                        .font(FontId::proportional(11.0 * font_scale))
+                                        .color(theme.accent),
                                 );
-                                ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_4_skew, setter)
-                                        .with_width(180.0),
+                            }
+                            if signal_presence.standby() {
+                                ui.label(
+                                    RichText::new("Standby")
+                                        .font(FontId::proportional(11.0 * font_scale))
+                                        .color(theme.accent),
+                                )
+                                .on_hover_text("No input, the random modulation is parked until signal comes back");
+                            }
+                            if let Some(result) = null_test_shared.load() {
+                                ui.label(
+                                    RichText::new(format!("Polarity {:+.2}", result.correlation))
+                                        .font(FontId::proportional(11.0 * font_scale))
+                                        .color(theme.accent),
+                                )
+                                .on_hover_text("Correlation of the output with the input after lining up the latency, +1 is in phase and -1 is inverted");
+                            }
+                        });
+
+                        // The bank sums to a flipped copy of the input, offer the fix
+                        if null_test_shared
+                            .load()
+                            .is_some_and(|result| result.correlation < null_test::POLARITY_WARNING)
+                        {
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    RichText::new("The output is polarity inverted against the input")
+                                        .font(FontId::proportional(12.0 * font_scale))
+                                        .color(Color32::BLACK)
+                                        .background_color(Color32::from_rgb(230, 180, 40)),
                                 );
+                                if ui
+                                    .small_button("Flip output")
+                                    .on_hover_text("Toggles Invert Output so the output lines up with the input again")
+                                    .clicked()
+                                {
+                                    setter.begin_set_parameter(&params.output_invert);
+                                    setter.set_parameter(
+                                        &params.output_invert,
+                                        !params.output_invert.value(),
+                                    );
+                                    setter.end_set_parameter(&params.output_invert);
+                                    null_test_shared.store(None);
+                                }
                             });
-                            ui.horizontal(|ui| {
-                                ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_5_coeff, setter)
-                                        .with_width(180.0),
+                        }
+
+                        // Drive a capture morph replay before anything else reads the params
+                        let morph_bank = params.morph_bank();
+                        let now = ui.input(|i| i.time);
+                        let current_morph = morph_bank.map(|param| param.modulated_plain_value());
+                        let was_replaying = ui_state.morph_capture.is_replaying();
+                        if was_replaying {
+                            if let Some(values) = ui_state.morph_capture.replay_frame(now) {
+                                for (param, value) in morph_bank.iter().zip(values) {
+                                    if param.modulated_plain_value() != value {
+                                        setter.set_parameter(*param, value);
+                                    }
+                                }
+                            }
+                        }
+
+                        // Grab the newest tap energy column for the heat map
+                        let energy: [f32; engine::TAPS] = std::array::from_fn(|tap| {
+                            tap_energy[tap].load(std::sync::atomic::Ordering::Relaxed)
+                        });
+                        if settings.reduced_motion {
+                            viz.tap_energy_history.clear();
+                        } else if viz.tap_energy_history.len() >= tap_heatmap::HISTORY_LENGTH {
+                            viz.tap_energy_history.pop_front();
+                        }
+                        viz.tap_energy_history.push_back(energy);
+
+                        // Scope only scrolls while motion is allowed
+                        if settings.reduced_motion {
+                            viz.mod_history.clear();
+                        } else {
+                            if viz.mod_history.len() >= mod_scope::HISTORY_LENGTH {
+                                viz.mod_history.pop_front();
+                            }
+                            viz
+                                .mod_history
+                                .push_back(mod_scope_value.load(std::sync::atomic::Ordering::Relaxed));
+                        }
+
+                        ui.horizontal(|ui| {
+                            for (tab, name) in [
+                                (EditorTab::Console, "Console"),
+                                (EditorTab::Tools, "Tools"),
+                                (EditorTab::Visuals, "Visuals"),
+                                (EditorTab::Explain, "Explain"),
+                                (EditorTab::Modulation, "Mod"),
+                            ] {
+                                ui.selectable_value(&mut ui_state.tab, tab, name);
+                            }
+                        });
+
+                        match ui_state.tab {
+                            EditorTab::Console => {
+                                // Knobs and labels, any float param can be pinned here from Tools
+                                ui.horizontal(|ui| {
+                                    let knob_size = 37.0;
+                                    let top_knobs = params.top_knobs.read().unwrap().clone();
+                                    let defaults = gui_settings::TopKnobs::default();
+                                    for (id, default_id) in top_knobs.ids.iter().zip(defaults.ids.iter()) {
+                                        // Ids this version doesn't know fall back to the default for the slot
+                                        let Some((id, param)) = params
+                                            .pinned_param(id)
+                                            .map(|param| (id, param))
+                                            .or_else(|| {
+                                                params.pinned_param(default_id).map(|param| (default_id, param))
+                                            })
+                                        else {
+                                            continue;
+                                        };
+                                        let knob = ui_knob::ArcKnob::for_param(
+                                            param,
+                                            setter,
+                                            knob_size,
+                                            ui_knob::KnobLayout::Vertical,
+                                        )
+                                            .preset_style(ui_knob::KnobStyle::Preset1)
+                                            .set_text_size(11.0 * font_scale)
+                                            .set_fill_color(theme.inside)
+                                            .set_line_color(theme.accent)
+                                            .set_line_width(knob_size * 0.3 * theme.knob_line_scale);
+                                        status_bar::track(ui.add(knob), param).context_menu(|ui| {
+                                            ui_knob::param_menu(ui, param, setter);
+                                            ui.separator();
+                                            user_config::favorites_menu(ui, param, id, setter);
+                                            ui.separator();
+                                            if ui.button("Reset top row").clicked() {
+                                                *params.top_knobs.write().unwrap() = gui_settings::TopKnobs::default();
+                                                ui.close_menu();
+                                            }
+                                        });
+                                    }
+                                });
+
+                                //sliders
+                                ui.vertical(|ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.label(
+                                            RichText::new("Coefficient Value")
+                                                .font(FontId::proportional(14.0 * font_scale))
+                                                .color(theme.accent),
+                                        );
+                                        ui.selectable_value(&mut ui_state.edit_bank, EditBank::A, "A");
+                                        ui.selectable_value(&mut ui_state.edit_bank, EditBank::B, "B")
+                                            .on_hover_text("Bank B is only heard with Dual Engine on in Tools");
+                                        ui.selectable_value(&mut ui_state.edit_bank, EditBank::R, "R")
+                                            .on_hover_text("Right channel offsets, only heard with Link off");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.link, setter)
+                                                    .with_width(30.0),
+                                            ),
+                                            &params.link,
+                                        )
+                                        .on_hover_text("Link: off gives the right channel its own offsets for a dual-mono desk");
+                                        ui.add_space(10.0);
+                                        ui.label(
+                                            RichText::new("Skew Value")
+                                                .font(FontId::proportional(14.0 * font_scale))
+                                                .color(theme.accent),
+                                        );
+                                    });
+                                    // Alt+drag on any slider moves the rest of its bank by the same amount
+                                    let (coeff_bank, skew_bank, coeff_name, skew_name) =
+                                        match ui_state.edit_bank {
+                                            EditBank::A => {
+                                                (params.coeff_bank(), params.skew_bank(), "coeff", "skew")
+                                            }
+                                            EditBank::B => {
+                                                (params.coeff_bank_b(), params.skew_bank_b(), "coeff_b", "skew_b")
+                                            }
+                                            EditBank::R => (
+                                                params.r_coeff_offsets(),
+                                                params.r_skew_offsets(),
+                                                "coeff_r",
+                                                "skew_r",
+                                            ),
+                                        };
+                                    // The status bar spells out which slider it is, the param names are only tap numbers
+                                    let (coeff_label, skew_label) = match ui_state.edit_bank {
+                                        EditBank::A => ("Coeff", "Skew"),
+                                        EditBank::B => ("B Coeff", "B Skew"),
+                                        EditBank::R => ("R Coeff Offset", "R Skew Offset"),
+                                    };
+                                    // Param ids of the bank read "{prefix}{tap}_Coeff{suffix}"
+                                    let (id_prefix, id_suffix) = match ui_state.edit_bank {
+                                        EditBank::A => ("", ""),
+                                        EditBank::B => ("B", ""),
+                                        EditBank::R => ("R", "_Offset"),
+                                    };
+                                    // Right click a slider to make it snap instead of smoothing
+                                    let snap_menu = |response: &egui::Response, param: &FloatParam, id: &str, bit: u64| {
+                                        response.context_menu(|ui| {
+                                            ui_knob::param_menu(ui, param, setter);
+                                            ui.separator();
+                                            user_config::favorites_menu(ui, param, id, setter);
+                                            ui.separator();
+                                            let mut snap = params.is_snapped(bit);
+                                            if ui
+                                                .checkbox(&mut snap, "Snap (no smoothing)")
+                                                .on_hover_text("Jumps straight to new values, for stepped automation like sign flips")
+                                                .changed()
+                                            {
+                                                params.set_snapped(bit, snap);
+                                                ui.close_menu();
+                                            }
+                                        });
+                                    };
+                                    for (i, (coeff, skew)) in
+                                        coeff_bank.iter().zip(skew_bank.iter()).enumerate()
+                                    {
+                                        ui.horizontal(|ui| {
+                                            let coeff_bit =
+                                                GladeDeskParams::snap_bit(ui_state.edit_bank, false, i);
+                                            let skew_bit =
+                                                GladeDeskParams::snap_bit(ui_state.edit_bank, true, i);
+                                            let coeff_before = coeff.unmodulated_normalized_value();
+                                            let coeff_response = status_bar::track_labeled(
+                                                ui.add(
+                                                    widgets::ParamSlider::for_param(*coeff, setter)
+                                                        .with_width(170.0),
+                                                ),
+                                                *coeff,
+                                                &format!("{} {}", coeff_label, i + 1),
+                                            );
+                                            snap_menu(
+                                                &coeff_response,
+                                                *coeff,
+                                                &format!("{id_prefix}{}_Coeff{id_suffix}", i + 1),
+                                                coeff_bit,
+                                            );
+                                            relative_drag::bank_relative_drag(
+                                                ui,
+                                                &coeff_response,
+                                                &coeff_bank,
+                                                i,
+                                                coeff_before,
+                                                setter,
+                                                coeff_name,
+                                            );
+                                            ui.add(
+                                                numeric_entry::NumericEntry::for_param(*coeff, setter)
+                                                    .with_text_size(11.0 * font_scale),
+                                            );
+                                            let skew_before = skew.unmodulated_normalized_value();
+                                            let skew_response = status_bar::track_labeled(
+                                                ui.add(
+                                                    widgets::ParamSlider::for_param(*skew, setter)
+                                                        .with_width(170.0),
+                                                ),
+                                                *skew,
+                                                &format!("{} {}", skew_label, i + 1),
+                                            );
+                                            snap_menu(
+                                                &skew_response,
+                                                *skew,
+                                                &format!("{id_prefix}{}_Skew{id_suffix}", i + 1),
+                                                skew_bit,
+                                            );
+                                            relative_drag::bank_relative_drag(
+                                                ui,
+                                                &skew_response,
+                                                &skew_bank,
+                                                i,
+                                                skew_before,
+                                                setter,
+                                                skew_name,
+                                            );
+                                            ui.add(
+                                                numeric_entry::NumericEntry::for_param(*skew, setter)
+                                                    .with_text_size(11.0 * font_scale),
+                                            );
+                                        });
+                                    }
+
+                                    // Click to flip the sign a tap is summed with
+                                    let polarity_bank = params.polarity_bank();
+                                    ui.horizontal(|ui| {
+                                        for (tap, param) in polarity_bank.iter().enumerate() {
+                                            let polarity = param.value();
+                                            let sign = match polarity {
+                                                engine::TapPolarity::Positive => "+",
+                                                engine::TapPolarity::Negative => "-",
+                                            };
+                                            let response = status_bar::track(
+                                                ui.add(
+                                                    egui::Button::new(
+                                                        RichText::new(format!("{} {}", tap + 1, sign))
+                                                            .font(FontId::monospace(11.0 * font_scale)),
+                                                    )
+                                                    .min_size(egui::vec2(36.0, 0.0)),
+                                                ),
+                                                *param,
+                                            )
+                                            .on_hover_text("Tap polarity, click to flip");
+                                            if response.clicked() {
+                                                setter.begin_set_parameter(*param);
+                                                setter.set_parameter(*param, polarity.flipped());
+                                                setter.end_set_parameter(*param);
+                                            }
+                                        }
+                                    });
+
+                                    // Sign/weight grid over the same coefficient params
+                                    let polarity = polarity_bank.map(|param| param.value().sign());
+                                    ui.add(
+                                        tap_grid::TapGrid::for_bank(&coeff_bank, &polarity, setter)
+                                            .cell_size(40.0)
+                                            .text_scale(font_scale)
+                                            .colors(
+                                                theme.accent,
+                                                Color32::LIGHT_BLUE,
+                                                theme.inside,
+                                            ),
+                                    );
+                                });
+                            }
+                            EditorTab::Tools => {
+                                let label = |ui: &mut egui::Ui, text: &str| {
+                                    ui.label(
+                                        RichText::new(text)
+                                            .font(FontId::proportional(12.0 * font_scale))
+                                            .color(theme.accent),
+                                    );
+                                };
+                                // The Tools rows outgrew the window, let them scroll
+                                egui::ScrollArea::vertical().show(ui, |ui| {
+                                    ui.horizontal(|ui| {
+                                        label(ui, "DC Trim L");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.dc_trim_l, setter)
+                                                    .with_width(140.0),
+                                            ),
+                                            &params.dc_trim_l,
+                                        );
+                                        label(ui, "R");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.dc_trim_r, setter)
+                                                    .with_width(140.0),
+                                            ),
+                                            &params.dc_trim_r,
+                                        );
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Taps");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.tap_count, setter)
+                                                    .with_width(140.0),
+                                            ),
+                                            &params.tap_count,
+                                        )
+                                        .on_hover_text("More taps smear the console longer, the eight sliders are read as a curve across them");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Feedback");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.feedback, setter)
+                                                    .with_width(140.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "feedback",
+                                        )
+                                        .on_hover_text("Sends the tap output back into the console for resonant textures");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.feedback_damping, setter)
+                                                    .with_width(140.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "feedback_damping",
+                                        )
+                                        .on_hover_text("Lowpass on the feedback, higher is darker");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Damping");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.tap_damping, setter)
+                                                    .with_width(140.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "tap_damping",
+                                        )
+                                        .on_hover_text("Lowpass on each tap that gets stronger towards the last one, a darker and smoother comb without touching the coefficients");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Spacing");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.spacing_mode, setter)
+                                                    .with_width(60.0),
+                                            ),
+                                            &params.spacing_mode,
+                                        )
+                                        .on_hover_text("Samples is the original sound, ms keeps the same texture at any sample rate");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.tap_spacing, setter)
+                                                    .with_width(140.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "tap_spacing",
+                                        );
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Random Mod");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.mod_mode, setter)
+                                                    .with_width(70.0),
+                                            ),
+                                            &params.mod_mode,
+                                        );
+                                        label(ui, "to");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.mod_target, setter)
+                                                    .with_width(90.0),
+                                            ),
+                                            &params.mod_target,
+                                        );
+                                        let seed = *params.random_seed.read().unwrap();
+                                        label(ui, &format!("Seed {seed:08X}"));
+                                        if ui
+                                            .small_button("New seed")
+                                            .on_hover_text("The random mod and drift replay the same way from this seed every time playback starts, so bounces match. A new seed gives them a different run")
+                                            .clicked()
+                                        {
+                                            params.set_random_seed(new_seed());
+                                        }
+                                    });
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Rate");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.mod_rate, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "mod_rate",
+                                        );
+                                        label(ui, "Smooth");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.mod_smooth, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "mod_smooth",
+                                        );
+                                        label(ui, "Depth");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.mod_depth, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "mod_depth",
+                                        );
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Drift");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.drift, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "drift",
+                                        )
+                                        .on_hover_text("Each coefficient wanders slowly on its own like aging components, up to 10% at full. The sliders themselves don't move");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "LFO");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.lfo_shape, setter)
+                                                    .with_width(70.0),
+                                            ),
+                                            &params.lfo_shape,
+                                        );
+                                        label(ui, "to");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.lfo_target, setter)
+                                                    .with_width(90.0),
+                                            ),
+                                            &params.lfo_target,
+                                        );
+                                        label(ui, "Sync");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.lfo_sync, setter)
+                                                    .with_width(60.0),
+                                            ),
+                                            &params.lfo_sync,
+                                        )
+                                        .on_hover_text("Free runs at Rate, the rest follow the host tempo and line up with its beat while playing");
+                                    });
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Rate");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.lfo_rate, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "lfo_rate",
+                                        )
+                                        .on_hover_text("LFO Rate: only used while Sync is Free");
+                                        label(ui, "Depth");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.lfo_depth, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "lfo_depth",
+                                        );
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Envelope");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.env_attack, setter)
+                                                    .with_width(80.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "env_attack",
+                                        )
+                                        .on_hover_text("How fast the follower rises with the input");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.env_release, setter)
+                                                    .with_width(80.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "env_release",
+                                        )
+                                        .on_hover_text("How fast the follower falls back");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.env_sidechain, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.env_sidechain,
+                                        )
+                                        .on_hover_text("Follow the Sidechain input instead of the main input, the host has to route a signal to it");
+                                    });
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Env to");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.env_push, setter)
+                                                    .with_width(80.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "env_push",
+                                        )
+                                        .on_hover_text("Env > Push: louder input adds Push, negative backs it off instead");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.env_skew, setter)
+                                                    .with_width(80.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "env_skew",
+                                        )
+                                        .on_hover_text("Env > Skew: louder input scales the skew terms up, negative scales them down");
+                                    });
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Push Dynamics");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.push_dynamics, setter)
+                                                    .with_width(80.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "push_dynamics",
+                                        )
+                                        .on_hover_text("Positive eases Push off as the input gets louder so transients stay cleaner than the sustain, negative drives loud parts harder");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Mod Output");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.mod_output, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.mod_output,
+                                        )
+                                        .on_hover_text("Sends the random mod, the envelope and the LFO to the host as MIDI CCs once per block, route the plugin's MIDI out to follow them elsewhere");
+                                        label(ui, "CC");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.mod_output_cc, setter)
+                                                    .with_width(60.0),
+                                            ),
+                                            &params.mod_output_cc,
+                                        )
+                                        .on_hover_text("Random mod goes out on this CC centred at 64, then the envelope and the LFO on the next two up");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Alert Output");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.alert_output, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.alert_output,
+                                        )
+                                        .on_hover_text("Sends MIDI when the output clips or the overload warning comes up, so a controller pad or DAW macro can react");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.alert_message, setter)
+                                                    .with_width(50.0),
+                                            ),
+                                            &params.alert_message,
+                                        )
+                                        .on_hover_text("A note held while the alert is on, or a CC that goes to 127 and back to 0");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.alert_number, setter)
+                                                    .with_width(60.0),
+                                            ),
+                                            &params.alert_number,
+                                        )
+                                        .on_hover_text("Clipping goes out on this note or CC number, the overload warning on the next one up");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Oversampling");
+                                        status_bar::track(
+                                            ui.add_enabled(
+                                                !params.zero_latency.value(),
+                                                widgets::ParamSlider::for_param(&params.oversampling, setter)
+                                                    .with_width(60.0),
+                                            ),
+                                            &params.oversampling,
+                                        )
+                                        .on_hover_text("Runs the Push shaper and tap network at a higher rate to cut aliasing, adds latency");
+                                        label(ui, "HQ Shaper");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.hq_shaper, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.hq_shaper,
+                                        )
+                                        .on_hover_text("Anti-derivative anti-aliasing on the Push shaper, cheaper than oversampling");
+                                        label(ui, "Auto Mult");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.auto_mult, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.auto_mult,
+                                        )
+                                        .on_hover_text("Slowly lowers the multiplier on loud material and raises it on quiet material");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Auto Gain");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.auto_gain, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.auto_gain,
+                                        )
+                                        .on_hover_text("Trims the processed signal back to the input loudness so Push and Multiplier can be judged without the level changing");
+                                        if params.auto_gain.value() {
+                                            let trim = util::gain_to_db(
+                                                auto_gain_trim.load(std::sync::atomic::Ordering::Relaxed),
+                                            );
+                                            label(ui, &format!("Trim: {trim:+.1} dB"));
+                                        }
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Gain Match");
+                                        let measuring = matches!(
+                                            gain_match.state(),
+                                            gain_match::GainMatchState::Requested
+                                                | gain_match::GainMatchState::Measuring
+                                        );
+                                        if ui
+                                            .add_enabled(!measuring, egui::Button::new("Match"))
+                                            .on_hover_text("Listens to the input and output for two seconds of playback, then sets Output Gain so they're equally loud")
+                                            .clicked()
+                                        {
+                                            ui_state.measuring_loudness = false;
+                                            gain_match.request();
+                                        }
+                                        if measuring {
+                                            label(ui, "Measuring...");
+                                        } else if gain_match.state() == gain_match::GainMatchState::NoSignal {
+                                            label(ui, "No signal, start playback and try again");
+                                        } else {
+                                            label(ui, &ui_state.gain_match_status);
+                                        }
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Preset Loudness");
+                                        let measuring = matches!(
+                                            gain_match.state(),
+                                            gain_match::GainMatchState::Requested
+                                                | gain_match::GainMatchState::Measuring
+                                        );
+                                        if ui
+                                            .add_enabled(!measuring, egui::Button::new("Measure"))
+                                            .on_hover_text("Listens to the input and output for two seconds of playback and saves how much louder or quieter this state makes things")
+                                            .clicked()
+                                        {
+                                            ui_state.measuring_loudness = true;
+                                            gain_match.request();
+                                        }
+                                        let loudness_delta = *params.loudness_delta.read().unwrap();
+                                        match loudness_delta {
+                                            Some(delta_db) => {
+                                                label(ui, &format!("{delta_db:+.1} dB"));
+                                                if ui.small_button("Clear").clicked() {
+                                                    params.set_loudness_delta(None);
+                                                }
+                                            }
+                                            None => label(ui, "Not measured"),
+                                        }
+                                        let mut normalize = user_config::normalize_presets();
+                                        if ui
+                                            .checkbox(&mut normalize, "Normalize")
+                                            .on_hover_text("Every instance trims its output by its measured loudness, so auditioning presets isn't a loudness contest. Shared by all sessions")
+                                            .changed()
+                                        {
+                                            user_config::set_normalize_presets(normalize);
+                                        }
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Invert Output");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.output_invert, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.output_invert,
+                                        )
+                                        .on_hover_text("Flips the output polarity, for banks whose negative taps outweigh the rest");
+                                        label(ui, "Delta");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.delta, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.delta,
+                                        )
+                                        .on_hover_text("Outputs only the difference between wet and the latency aligned dry, so you hear exactly what the coefficients and Push add");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Push Curve");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.push_curve, setter)
+                                                    .with_width(80.0),
+                                            ),
+                                            &params.push_curve,
+                                        )
+                                        .on_hover_text("Shape the Push stage blends in: Sine is the original, Fold folds back instead of flattening");
+                                        label(ui, "Bias");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.bias, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "bias",
+                                        )
+                                        .on_hover_text("Pushes the shaper off center for even harmonics, DC Block cleans up what's left");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Even/Odd");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.even_odd, setter)
+                                                    .with_width(140.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "even_odd",
+                                        )
+                                        .on_hover_text("Blends the symmetric Push curve (push-pull, odd harmonics) into an asymmetric one (triode, even harmonics)");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Harmonics");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.harmonic_mode, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params.harmonic_mode,
+                                        )
+                                        .on_hover_text("Designs a Chebyshev shaper from the levels below, after the taps or in place of them");
+                                    });
+                                    ui.horizontal_wrapped(|ui| {
+                                        for (harmonic, param) in (2..).zip(params.harmonic_bank()) {
+                                            label(ui, &format!("H{harmonic}"));
+                                            status_bar::track(
+                                                ui.add(
+                                                    widgets::ParamSlider::for_param(param, setter)
+                                                        .with_width(50.0),
+                                                ),
+                                                param,
+                                            )
+                                            .on_hover_text("Level of this harmonic on a full scale sine");
+                                        }
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Zero Latency");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.zero_latency, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.zero_latency,
+                                        )
+                                        .on_hover_text("Turns off everything that adds latency for tracking and live monitoring");
+                                        // What the host was actually told, so the switch can be trusted
+                                        let reported = latency_samples.load(std::sync::atomic::Ordering::Relaxed);
+                                        label(ui, &format!("Reported: {reported} samples"));
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Quality");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.quality_mode, setter)
+                                                    .with_width(80.0),
+                                            ),
+                                            &params.quality_mode,
+                                        )
+                                        .on_hover_text("Manual uses the Oversampling and HQ Shaper params. Auto runs Render while the host bounces offline, Tracking with Zero Latency on and Mixing otherwise");
+                                    });
+                                    if params.quality_mode.value() != quality::QualityMode::Manual {
+                                        let profiles = params.quality_profiles.read().unwrap().clone();
+                                        for profile in quality::QualityProfile::ALL {
+                                            let current = profiles.get(profile);
+                                            let mut edited = current;
+                                            ui.horizontal(|ui| {
+                                                label(ui, &format!("  {}", profile.name()));
+                                                for factor in quality::ProfileSettings::OVERSAMPLING_FACTORS {
+                                                    ui.selectable_value(
+                                                        &mut edited.oversampling,
+                                                        factor,
+                                                        format!("{factor}x"),
+                                                    );
+                                                }
+                                                ui.checkbox(&mut edited.hq_shaper, "HQ Shaper");
+                                            });
+                                            if edited != current {
+                                                params.set_quality_profile(profile, edited);
+                                            }
+                                        }
+                                    }
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "True Peak");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.true_peak, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.true_peak,
+                                        )
+                                        .on_hover_text("4x oversampled true-peak limiter as the last stage, 1 ms of lookahead is added to the latency");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.true_peak_ceiling, setter)
+                                                    .with_width(90.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "true_peak_ceiling",
+                                        )
+                                        .on_hover_text("Ceiling: nothing gets out above this, inter-sample peaks included");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.true_peak_release, setter)
+                                                    .with_width(90.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "true_peak_release",
+                                        )
+                                        .on_hover_text("Limiter Release: how fast the gain comes back after a peak");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Auto Standby");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.auto_standby, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.auto_standby,
+                                        )
+                                        .on_hover_text("Ramps the random modulation down after half a second without input so silent parts of printed stems stay clean");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Scene Fade");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.scene_fade, setter)
+                                                    .with_width(120.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "scene_fade",
+                                        )
+                                        .on_hover_text("Glide time from the old console settings when the host switches presets or scenes");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Console Mode");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.console_mode, setter)
+                                                    .with_width(80.0),
+                                            ),
+                                            &params.console_mode,
+                                        )
+                                        .on_hover_text("Pair instances like a console: Channel on each track encodes its output, Bus on the bus they sum into decodes it again. A single channel comes back at unity, a full mix picks up the glue");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Mix Law");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.mix_law, setter)
+                                                    .with_width(90.0),
+                                            ),
+                                            &params.mix_law,
+                                        )
+                                        .on_hover_text("Additive is the classic dry + wet sum, Linear and Equal Power crossfade from the latency aligned dry to the taps alone, so 100% has no dry left in it");
+                                        label(ui, "Calibration");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.calibration, setter)
+                                                    .with_width(80.0),
+                                            ),
+                                            &params.calibration,
+                                        )
+                                        .on_hover_text("The level your tracks average at. Hotter references are turned down into the console, tape and transformer and back up after, so presets saturate the same however hot the gain staging is");
+                                        label(ui, "DC Block");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.dc_block, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.dc_block,
+                                        );
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.dc_block_cutoff, setter)
+                                                    .with_width(80.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "dc_block_cutoff",
+                                        );
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Input Filters");
+                                        for (slope, cutoff, id, hover) in [
+                                            (&params.hpf_slope, &params.hpf_cutoff, "hpf_cutoff", "High-pass before the console so rumble doesn't drive the shaper"),
+                                            (&params.lpf_slope, &params.lpf_cutoff, "lpf_cutoff", "Low-pass before the console to tame fizz before the skews amplify it"),
+                                        ] {
+                                            status_bar::track(
+                                                ui.add(
+                                                    widgets::ParamSlider::for_param(slope, setter)
+                                                        .with_width(70.0),
+                                                ),
+                                                slope,
+                                            )
+                                            .on_hover_text(hover);
+                                            pin_to_top(
+                                                ui.add(
+                                                    widgets::ParamSlider::for_param(cutoff, setter)
+                                                        .with_width(80.0),
+                                                ),
+                                                &params,
+                                                setter,
+                                                id,
+                                            );
+                                        }
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Tilt");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.tilt, setter)
+                                                    .with_width(90.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "tilt",
+                                        )
+                                        .on_hover_text("Tilt: positive brightens, negative darkens, around the pivot");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.tilt_pivot, setter)
+                                                    .with_width(80.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "tilt_pivot",
+                                        )
+                                        .on_hover_text("Tilt Pivot: the frequency the tilt swings around");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.tilt_placement, setter)
+                                                    .with_width(90.0),
+                                            ),
+                                            &params.tilt_placement,
+                                        )
+                                        .on_hover_text("Pre changes what the saturation reacts to, Post shapes the result");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Wow & Flutter");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.wow_depth, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "wow_depth",
+                                        )
+                                        .on_hover_text("Depth of the tape speed wobble, 0% is off. Adds a couple of ms of delay while on");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.wow_rate, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "wow_rate",
+                                        )
+                                        .on_hover_text("Wow Rate: speed of the slow wobble, the flutter follows about 7 times faster");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Tape");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.tape, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.tape,
+                                        )
+                                        .on_hover_text("Tape hysteresis after the console to finish the analog chain");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.tape_drive, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "tape_drive",
+                                        )
+                                        .on_hover_text("Tape Drive: harder saturation");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.tape_bias, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "tape_bias",
+                                        )
+                                        .on_hover_text("Tape Bias: low is wide, smeary hysteresis, high is cleaner and more linear");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Transformer");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.transformer, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.transformer,
+                                        )
+                                        .on_hover_text("Output transformer, saturates the lows more than the highs");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.transformer_drive, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "transformer_drive",
+                                        )
+                                        .on_hover_text("Transformer Drive: how hard the signal hits the core");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.transformer_core, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "transformer_core",
+                                        )
+                                        .on_hover_text("Core Size: small cores saturate early and up into the low mids, large ones only thump the deep lows");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Crosstalk");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.crosstalk, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "crosstalk",
+                                        )
+                                        .on_hover_text("How loud each side leaks into the other, mostly the highs like a real desk. All the way down is off");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.crosstalk_dynamics, setter)
+                                                    .with_width(80.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "crosstalk_dynamics",
+                                        )
+                                        .on_hover_text("Crosstalk Dynamics: louder input bleeds more, up to 12 dB above Crosstalk at full scale. Follows the Envelope settings");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Dual Engine");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.dual_engine, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.dual_engine,
+                                        )
+                                        .on_hover_text("Runs banks A and B side by side and blends them");
+                                        label(ui, "Balance");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.engine_balance, setter)
+                                                    .with_width(120.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "engine_balance",
+                                        );
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.bank_morph, setter)
+                                                    .with_width(90.0),
+                                            ),
+                                            &params.bank_morph,
+                                        )
+                                        .on_hover_text("Linear blends the outputs, Min Change blends each tap's size and flips opposite signed taps half way so the sound never thins out mid-morph");
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Multiband");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.multiband, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.multiband,
+                                        )
+                                        .on_hover_text("Splits into low/mid/high, each band gets its own tap network with its own drive and mix. Takes over from Push and Dual Engine");
+                                        label(ui, "X-over");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.crossover_low, setter)
+                                                    .with_width(90.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "crossover_low",
+                                        );
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.crossover_high, setter)
+                                                    .with_width(90.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "crossover_high",
+                                        );
+                                    });
+                                    for (name, drive, mix, drive_id, mix_id) in [
+                                        ("Low", &params.low_drive, &params.low_mix, "low_drive", "low_mix"),
+                                        ("Mid", &params.mid_drive, &params.mid_mix, "mid_drive", "mid_mix"),
+                                        ("High", &params.high_drive, &params.high_mix, "high_drive", "high_mix"),
+                                    ] {
+                                        ui.horizontal(|ui| {
+                                            label(ui, &format!("{name} Drive"));
+                                            pin_to_top(
+                                                ui.add(
+                                                    widgets::ParamSlider::for_param(drive, setter)
+                                                        .with_width(100.0),
+                                                ),
+                                                &params,
+                                                setter,
+                                                drive_id,
+                                            );
+                                            label(ui, "Mix");
+                                            pin_to_top(
+                                                ui.add(
+                                                    widgets::ParamSlider::for_param(mix, setter)
+                                                        .with_width(100.0),
+                                                ),
+                                                &params,
+                                                setter,
+                                                mix_id,
+                                            );
+                                        });
+                                    }
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Lo-fi Rate");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.lofi_ratio, setter)
+                                                    .with_width(70.0),
+                                            ),
+                                            &params.lofi_ratio,
+                                        )
+                                        .on_hover_text("Runs the tap network at a fraction of the host rate, aliasing included");
+                                        label(ui, "Interp");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.lofi_interp, setter)
+                                                    .with_width(70.0),
+                                            ),
+                                            &params.lofi_interp,
+                                        );
+                                    });
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Crush");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.crush_bits, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "crush_bits",
+                                        );
+                                        label(ui, "Mix");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.crush_mix, setter)
+                                                    .with_width(100.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "crush_mix",
+                                        );
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Note Gate");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.note_gate, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.note_gate,
+                                        );
+                                        label(ui, "Atk");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.gate_attack, setter)
+                                                    .with_width(90.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "gate_attack",
+                                        );
+                                        label(ui, "Rel");
+                                        pin_to_top(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.gate_release, setter)
+                                                    .with_width(90.0),
+                                            ),
+                                            &params,
+                                            setter,
+                                            "gate_release",
+                                        );
+                                        label(ui, "Diag Log");
+                                        status_bar::track(
+                                            ui.add(
+                                                widgets::ParamSlider::for_param(&params.diagnostic_log, setter)
+                                                    .with_width(40.0),
+                                            ),
+                                            &params.diagnostic_log,
+                                        )
+                                        .on_hover_text(
+                                            "Logs output clips and extreme internal values with the current settings",
+                                        );
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Capture Morph");
+                                        let capture = &mut ui_state.morph_capture;
+                                        if capture.is_recording() || capture.is_replaying() {
+                                            if ui.button("Stop").clicked() {
+                                                capture.stop();
+                                            }
+                                        } else {
+                                            if ui.button("Rec").clicked() {
+                                                capture.start_recording(now, current_morph);
+                                            }
+                                            let has_capture = capture.len() > 1;
+                                            if ui
+                                                .add_enabled(has_capture, egui::Button::new("Play"))
+                                                .clicked()
+                                                && capture.start_replay(now)
+                                            {
+                                                for param in morph_bank.iter() {
+                                                    setter.begin_set_parameter(*param);
+                                                }
+                                            }
+                                            if ui
+                                                .add_enabled(
+                                                    has_capture,
+                                                    egui::Button::new("Copy A/B"),
+                                                )
+                                                .on_hover_text(
+                                                    "Copy the start and end of the capture as a morph pair",
+                                                )
+                                                .clicked()
+                                            {
+                                                if let Some((a, b)) = capture.morph_pair() {
+                                                    ui.output_mut(|o| {
+                                                        o.copied_text =
+                                                            morph_capture::morph_pair_to_string(
+                                                                &a, &b,
+                                                            )
+                                                    });
+                                                }
+                                            }
+                                        }
+                                        ui.label(format!(
+                                            "{} pts, {:.1} s",
+                                            capture.len(),
+                                            capture.duration()
+                                        ));
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Null Test");
+                                        let mut enabled = null_test_shared
+                                            .enabled
+                                            .load(std::sync::atomic::Ordering::Relaxed);
+                                        if ui
+                                            .checkbox(&mut enabled, "")
+                                            .on_hover_text("Compares the output to the bypassed input, lining up the reported latency and gain first")
+                                            .changed()
+                                        {
+                                            null_test_shared
+                                                .enabled
+                                                .store(enabled, std::sync::atomic::Ordering::Relaxed);
+                                            null_test_shared.store(None);
+                                        }
+                                        if enabled {
+                                            let readout = match null_test_shared.load() {
+                                                Some(result) => format!(
+                                                    "residual {:.1} dB | lag {} smp | gain {:+.2} dB",
+                                                    result.residual_db, result.lag_samples, result.gain_db
+                                                ),
+                                                None => "waiting for signal".to_string(),
+                                            };
+                                            ui.label(
+                                                RichText::new(readout)
+                                                    .font(FontId::monospace(11.0 * font_scale)),
+                                            );
+                                        }
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Render");
+                                        ui.add(
+                                            egui::TextEdit::singleline(&mut ui_state.render_input)
+                                                .hint_text("WAV path, empty for the demo clip")
+                                                .desired_width(200.0),
+                                        );
+                                        if ui
+                                            .button("Render demo clip")
+                                            .on_hover_text("Processes the clip offline with the current settings and writes a WAV")
+                                            .clicked()
+                                        {
+                                            let input = ui_state.render_input.trim();
+                                            let (input, output) = if input.is_empty() {
+                                                (None, std::env::temp_dir().join("gladedesk_demo.wav"))
+                                            } else {
+                                                let input = std::path::PathBuf::from(input);
+                                                let output = input.with_file_name(format!(
+                                                    "{}_gladedesk.wav",
+                                                    input
+                                                        .file_stem()
+                                                        .map(|stem| stem.to_string_lossy())
+                                                        .unwrap_or_default()
+                                                ));
+                                                (Some(input), output)
+                                            };
+                                            *render_status.write().unwrap() = "Rendering...".to_string();
+                                            async_executor.execute_background(Task::RenderClip(
+                                                offline::RenderJob {
+                                                    input,
+                                                    output,
+                                                    settings: params.offline_settings(),
+                                                },
+                                            ));
+                                        }
+                                    });
+                                    let status = render_status.read().unwrap().clone();
+                                    if !status.is_empty() {
+                                        ui.label(RichText::new(status).font(FontId::monospace(10.0 * font_scale)));
+                                    }
+
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Import");
+                                        ui.add(
+                                            egui::TextEdit::singleline(&mut ui_state.import_path)
+                                                .hint_text("Subhoofer etc. state file")
+                                                .desired_width(200.0),
+                                        );
+                                        if ui
+                                            .button("Import")
+                                            .on_hover_text("Maps gain, drive and hardness from a related Ardura plugin's saved state onto Glade Desk")
+                                            .clicked()
+                                        {
+                                            ui_state.import_status = match std::fs::read_to_string(
+                                                ui_state.import_path.trim(),
+                                            )
+                                            .map_err(|e| e.to_string())
+                                            .and_then(|text| preset_import::import_state(&text))
+                                            {
+                                                Ok(imported) => {
+                                                    for (param, value) in [
+                                                        (&params.free_gain, imported.input_gain),
+                                                        (&params.push_amount, imported.push),
+                                                        (&params.multiplier, imported.multiplier),
+                                                        (&params.output_gain, imported.output_gain),
+                                                        (&params.dry_wet, imported.dry_wet),
+                                                    ] {
+                                                        if let Some(value) = value {
+                                                            setter.begin_set_parameter(param);
+                                                            setter.set_parameter(param, value);
+                                                            setter.end_set_parameter(param);
+                                                        }
+                                                    }
+                                                    format!("Imported {}", imported.used.join(", "))
+                                                }
+                                                Err(message) => format!("Import failed: {message}"),
+                                            };
+                                        }
+                                    });
+                                    if !ui_state.import_status.is_empty() {
+                                        ui.label(
+                                            RichText::new(&ui_state.import_status)
+                                                .font(FontId::monospace(10.0 * font_scale)),
+                                        );
+                                    }
+
+                                    // Lets users check their quality settings actually took
+                                    ui.horizontal(|ui| {
+                                        label(ui, "Session");
+                                        ui.label(
+                                            RichText::new(format!(
+                                                "{:.0} Hz | buffer {} | {} | latency {} smp",
+                                                session_sample_rate
+                                                    .load(std::sync::atomic::Ordering::Relaxed),
+                                                max_buffer_size
+                                                    .load(std::sync::atomic::Ordering::Relaxed),
+                                                params.oversampling,
+                                                latency_samples
+                                                    .load(std::sync::atomic::Ordering::Relaxed),
+                                            ))
+                                            .font(FontId::monospace(11.0 * font_scale)),
+                                        );
+                                    });
+                                });
+                            }
+                            EditorTab::Visuals => {
+                                if ui_state.analyzer_detached {
+                                    ui.label("The analyzer is popped out into its own window");
+                                    if ui.button("Dock analyzer").clicked() {
+                                        ui_state.analyzer_detached = false;
+                                    }
+                                } else {
+                                    if ui
+                                        .small_button("Pop out")
+                                        .on_hover_text("Keep the analyzer visible in a resizable window while you work on other tabs")
+                                        .clicked()
+                                    {
+                                        ui_state.analyzer_detached = true;
+                                    }
+                                    viz.analyzer_ui(
+                                        ui,
+                                        &theme,
+                                        font_scale,
+                                        settings.reduced_motion,
+                                        &params.mod_target.to_string(),
+                                        mod_scope_value.load(std::sync::atomic::Ordering::Relaxed),
+                                        egui::vec2(500.0, 200.0),
+                                    );
+                                }
+                            }
+                            EditorTab::Explain => {
+                                ui.label(
+                                    RichText::new("What the sliders do")
+                                        .font(FontId::proportional(12.0 * font_scale))
+                                        .color(theme.accent),
                                 );
-                                ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_5_skew, setter)
-                                        .with_width(180.0),
+                                ui.label(
+                                    RichText::new(
+                                        "A single impulse goes into the history and moves one tap per step. \
+                                         Each tap multiplies what it reads by its coefficient plus skew, \
+                                         times the Multiplier, and the bars add up into the output. \
+                                         Push and the skew's level dependence are left out here.",
+                                    )
+                                    .size(11.0 * font_scale),
                                 );
-                            });
-                            ui.horizontal(|ui| {
+                                let console = params.console_params();
+                                // Reduced motion shows every tap at once instead of the walk
+                                let position = if settings.reduced_motion {
+                                    console.tap_count as f32
+                                } else {
+                                    explainer::impulse_position(
+                                        ui.input(|i| i.time),
+                                        console.tap_count,
+                                    )
+                                };
                                 ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_6_coeff, setter)
-                                        .with_width(180.0),
+                                    explainer::TapExplainer::new(&console, position)
+                                        .desired_size(egui::vec2(500.0, 180.0))
+                                        .color(theme.accent)
+                                        .background_color(theme.inside.linear_multiply(0.3))
+                                        .font_scale(font_scale),
+                                )
+                                .on_hover_text("Move the coefficient and skew sliders on the Console tab and watch the bars follow");
+                            }
+                            EditorTab::Modulation => {
+                                egui::ScrollArea::vertical().show(ui, |ui| {
+                                    ui.label(
+                                        RichText::new("Macros")
+                                            .font(FontId::proportional(12.0 * font_scale))
+                                            .color(theme.accent),
+                                    );
+                                    ui.label(
+                                        RichText::new(
+                                            "Each macro sets its targets between Min and Max along its curve, \
+                                             as a share of the target's range. Modulation rides on top, and \
+                                             when two macros share a target the later one wins.",
+                                        )
+                                        .size(11.0 * font_scale),
+                                    );
+                                    let current_macros = params.macro_map.read().unwrap().clone();
+                                    let mut edited_macros = current_macros.clone();
+                                    let macro_ids = ["macro_1", "macro_2", "macro_3", "macro_4"];
+                                    for (knob, (param, targets)) in params
+                                        .macro_bank()
+                                        .into_iter()
+                                        .zip(edited_macros.targets.iter_mut())
+                                        .enumerate()
+                                    {
+                                        ui.horizontal(|ui| {
+                                            ui.label(
+                                                RichText::new(param.name())
+                                                    .font(FontId::proportional(12.0 * font_scale))
+                                                    .color(theme.accent),
+                                            );
+                                            pin_to_top(
+                                                ui.add(
+                                                    widgets::ParamSlider::for_param(param, setter)
+                                                        .with_width(100.0),
+                                                ),
+                                                &params,
+                                                setter,
+                                                macro_ids[knob],
+                                            );
+                                        });
+                                        egui::CollapsingHeader::new(format!(
+                                            "{} targets ({})",
+                                            param.name(),
+                                            targets.len()
+                                        ))
+                                        .id_source(("macro_targets", knob))
+                                        .show(ui, |ui| {
+                                            let mut removed = None;
+                                            for (slot, target) in targets.iter_mut().enumerate() {
+                                                ui.horizontal(|ui| {
+                                                    egui::ComboBox::from_id_source(("macro_destination", knob, slot))
+                                                        .selected_text(target.destination.name())
+                                                        .width(130.0)
+                                                        .show_ui(ui, |ui| {
+                                                            for destination in (0..mod_matrix::ModDestination::COUNT)
+                                                                .filter_map(mod_matrix::ModDestination::from_index)
+                                                            {
+                                                                ui.selectable_value(
+                                                                    &mut target.destination,
+                                                                    destination,
+                                                                    destination.name(),
+                                                                );
+                                                            }
+                                                        });
+                                                    ui.add(egui::Slider::new(&mut target.min, 0.0..=1.0).text("Min"));
+                                                    ui.add(egui::Slider::new(&mut target.max, 0.0..=1.0).text("Max"));
+                                                    egui::ComboBox::from_id_source(("macro_curve", knob, slot))
+                                                        .selected_text(target.curve.name())
+                                                        .width(90.0)
+                                                        .show_ui(ui, |ui| {
+                                                            for curve in macros::MacroCurve::ALL {
+                                                                ui.selectable_value(&mut target.curve, curve, curve.name());
+                                                            }
+                                                        });
+                                                    if ui.small_button("Remove").clicked() {
+                                                        removed = Some(slot);
+                                                    }
+                                                });
+                                            }
+                                            if let Some(slot) = removed {
+                                                targets.remove(slot);
+                                            }
+                                            if targets.len() < macros::MAX_TARGETS
+                                                && ui.button("Add target").clicked()
+                                            {
+                                                targets.push(macros::MacroTarget {
+                                                    destination: mod_matrix::ModDestination::Push,
+                                                    min: 0.0,
+                                                    max: 1.0,
+                                                    curve: macros::MacroCurve::Linear,
+                                                });
+                                            }
+                                        });
+                                    }
+                                    if edited_macros != current_macros {
+                                        params.set_macro_map(edited_macros);
+                                    }
+
+                                    ui.separator();
+                                    ui.label(
+                                        RichText::new("Mod Matrix")
+                                            .font(FontId::proportional(12.0 * font_scale))
+                                            .color(theme.accent),
+                                    );
+                                    ui.label(
+                                        RichText::new(
+                                            "Each route moves a console control by its depth times the source, \
+                                             as a share of the control's range. Random and LFO swing both ways \
+                                             before their own Depth, the envelope and macros only go up.",
+                                        )
+                                        .size(11.0 * font_scale),
+                                    );
+                                    let current = params.mod_matrix.read().unwrap().clone();
+                                    let mut edited = current.clone();
+                                    let mut removed = None;
+                                    for (slot, route) in edited.routes.iter_mut().enumerate() {
+                                        ui.horizontal(|ui| {
+                                            egui::ComboBox::from_id_source(("mod_source", slot))
+                                                .selected_text(route.source.name())
+                                                .width(90.0)
+                                                .show_ui(ui, |ui| {
+                                                    for source in mod_matrix::ModSource::ALL {
+                                                        ui.selectable_value(&mut route.source, source, source.name());
+                                                    }
+                                                });
+                                            ui.label("to");
+                                            egui::ComboBox::from_id_source(("mod_destination", slot))
+                                                .selected_text(route.destination.name())
+                                                .width(130.0)
+                                                .show_ui(ui, |ui| {
+                                                    for destination in (0..mod_matrix::ModDestination::COUNT)
+                                                        .filter_map(mod_matrix::ModDestination::from_index)
+                                                    {
+                                                        ui.selectable_value(
+                                                            &mut route.destination,
+                                                            destination,
+                                                            destination.name(),
+                                                        );
+                                                    }
+                                                });
+                                            ui.add(egui::Slider::new(&mut route.depth, -1.0..=1.0).text("Depth"));
+                                            if ui.small_button("Remove").clicked() {
+                                                removed = Some(slot);
+                                            }
+                                        });
+                                    }
+                                    if let Some(slot) = removed {
+                                        edited.routes.remove(slot);
+                                    }
+                                    if edited.routes.len() < mod_matrix::MAX_ROUTES
+                                        && ui.button("Add route").clicked()
+                                    {
+                                        edited.routes.push(mod_matrix::ModRoute {
+                                            source: mod_matrix::ModSource::Lfo,
+                                            destination: mod_matrix::ModDestination::Push,
+                                            depth: 0.25,
+                                        });
+                                    }
+                                    if edited != current {
+                                        params.set_mod_matrix(edited);
+                                    }
+                                });
+                            }
+                        }
+
+                        // A finished gain match lands on Output Gain whichever tab is showing,
+                        // or is saved as the preset loudness when that was asked for
+                        if let Some(correction) = gain_match.take_correction() {
+                            if ui_state.measuring_loudness {
+                                ui_state.measuring_loudness = false;
+                                let delta_db = -correction;
+                                params.set_loudness_delta(Some(delta_db));
+                                ui_state.gain_match_status =
+                                    format!("Preset loudness {delta_db:+.1} dB");
+                            } else {
+                                let current = util::gain_to_db(params.output_gain.value());
+                                let matched = (current + correction).clamp(-12.0, 12.0);
+                                setter.begin_set_parameter(&params.output_gain);
+                                setter.set_parameter(&params.output_gain, util::db_to_gain(matched));
+                                setter.end_set_parameter(&params.output_gain);
+                                ui_state.gain_match_status =
+                                    format!("Output Gain {current:+.1} -> {matched:+.1} dB");
+                            }
+                        }
+
+                        // Capture morph keeps recording/replaying no matter which tab is shown.
+                        // Replay goes through the setter so the host can write all 16 lanes at once
+                        let capture = &mut ui_state.morph_capture;
+                        if capture.is_recording() {
+                            capture.record_frame(now, current_morph);
+                        }
+                        if was_replaying && !capture.is_replaying() {
+                            for param in morph_bank.iter() {
+                                setter.end_set_parameter(*param);
+                            }
+                        }
+                        if capture.is_recording() || capture.is_replaying() {
+                            ui.ctx().request_repaint();
+                        }
+                    });
+                });
+
+                egui::Window::new("Settings")
+                    .open(&mut ui_state.show_settings)
+                    .resizable(false)
+                    .show(egui_ctx, |ui| {
+                        let mut new_settings = settings.clone();
+                        ui.checkbox(&mut new_settings.high_contrast, "High contrast");
+                        ui.checkbox(&mut new_settings.reduced_motion, "Reduced motion")
+                            .on_hover_text("Meters stop animating and moving visualizations are hidden");
+                        ui.add(
+                            egui::Slider::new(
+                                &mut new_settings.font_scale,
+                                gui_settings::FONT_SCALE_RANGE,
+                            )
+                            .text("Font size"),
+                        );
+                        ui.add(
+                            egui::Slider::new(
+                                &mut new_settings.slider_decimals,
+                                gui_settings::SLIDER_DECIMALS_RANGE,
+                            )
+                            .text("Slider decimals"),
+                        );
+                        ui.horizontal(|ui| {
+                            ui.label("Coefficients as");
+                            for display in gui_settings::CoeffDisplay::ALL {
+                                ui.selectable_value(
+                                    &mut new_settings.coeff_display,
+                                    display,
+                                    display.name(),
                                 );
-                                ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_6_skew, setter)
-                                        .with_width(180.0),
+                            }
+                        })
+                        .response
+                        .on_hover_text("Percent or the dB gain of each tap's contribution instead of the raw multiplier");
+                        ui.horizontal(|ui| {
+                            ui.label("Meters");
+                            for layout in gui_settings::MeterLayout::ALL {
+                                ui.selectable_value(
+                                    &mut new_settings.meter_layout,
+                                    layout,
+                                    layout.name(),
                                 );
-                            });
-                            ui.horizontal(|ui| {
-                                ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_7_coeff, setter)
-                                        .with_width(180.0),
+                            }
+                        })
+                        .response
+                        .on_hover_text("Full width stacked bars or input and output standing side by side");
+                        ui.horizontal(|ui| {
+                            ui.label("Refresh");
+                            for frame_rate in gui_settings::FrameRate::ALL {
+                                ui.selectable_value(
+                                    &mut new_settings.frame_rate,
+                                    frame_rate,
+                                    frame_rate.name(),
                                 );
+                            }
+                        })
+                        .response
+                        .on_hover_text("How often the meters and scopes redraw, lower rates save battery on laptops");
+                        if new_settings != settings {
+                            *params.gui_settings.write().unwrap() = new_settings;
+                        }
+
+                        ui.separator();
+                        let mut new_tag = instance_tag.clone();
+                        ui.horizontal(|ui| {
+                            ui.label("Tag name");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut new_tag.name)
+                                    .hint_text("e.g. Drum Bus")
+                                    .desired_width(120.0),
+                            );
+                        });
+                        ui.horizontal(|ui| {
+                            let mut use_color = new_tag.color.is_some();
+                            ui.checkbox(&mut use_color, "Tag color");
+                            let mut color = new_tag
+                                .color
+                                .unwrap_or(gui_settings::InstanceTag::DEFAULT_COLOR);
+                            if use_color {
+                                egui::color_picker::color_edit_button_srgb(ui, &mut color);
+                            }
+                            new_tag.color = use_color.then_some(color);
+                        });
+                        if new_tag != instance_tag {
+                            *params.instance_tag.write().unwrap() = new_tag;
+                        }
+
+                        ui.separator();
+                        let mut new_routing = routing_labels.clone();
+                        ui.checkbox(&mut new_routing.show, "Channel strip header");
+                        ui.add_enabled_ui(new_routing.show, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label("From");
                                 ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_7_skew, setter)
-                                        .with_width(180.0),
+                                    egui::TextEdit::singleline(&mut new_routing.from)
+                                        .hint_text("e.g. Drum Bus")
+                                        .desired_width(120.0),
                                 );
                             });
                             ui.horizontal(|ui| {
+                                ui.label("To");
                                 ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_8_coeff, setter)
-                                        .with_width(180.0),
-                                );
-                                ui.add(
-                                    widgets::ParamSlider::for_param(&params.slider_8_skew, setter)
-                                        .with_width(180.0),
+                                    egui::TextEdit::singleline(&mut new_routing.to)
+                                        .hint_text("e.g. Mix Bus")
+                                        .desired_width(120.0),
                                 );
                             });
                         });
+                        if new_routing != routing_labels {
+                            *params.routing_labels.write().unwrap() = new_routing;
+                        }
+                    });
+
+                let mut analyzer_open = ui_state.analyzer_detached;
+                egui::Window::new("Analyzer")
+                    .open(&mut analyzer_open)
+                    .resizable(true)
+                    .default_size(egui::vec2(500.0, 200.0))
+                    .min_width(200.0)
+                    .min_height(120.0)
+                    .show(egui_ctx, |ui| {
+                        let size = ui.available_size();
+                        viz.analyzer_ui(
+                            ui,
+                            &theme,
+                            font_scale,
+                            settings.reduced_motion,
+                            &params.mod_target.to_string(),
+                            mod_scope_value.load(std::sync::atomic::Ordering::Relaxed),
+                            size,
+                        );
+                    });
+                ui_state.analyzer_detached = analyzer_open;
+
+                egui::Window::new("Self Test")
+                    .open(&mut ui_state.show_self_test)
+                    .resizable(false)
+                    .show(egui_ctx, |ui| {
+                        if ui.button("Run impulse + sine").clicked() {
+                            ui_state.self_test_report = Some(engine::self_test(
+                                &params.console_params(),
+                                session_sample_rate.load(std::sync::atomic::Ordering::Relaxed),
+                                latency_samples.load(std::sync::atomic::Ordering::Relaxed),
+                            ));
+                        }
+                        if let Some(report) = &ui_state.self_test_report {
+                            let pass_fail = |ok: bool| if ok { "PASS" } else { "FAIL" };
+                            ui.label(format!(
+                                "Impulse output finite: {}",
+                                pass_fail(report.impulse_finite)
+                            ));
+                            ui.label(format!(
+                                "Sine output finite: {}",
+                                pass_fail(report.sine_finite)
+                            ));
+                            ui.label(format!(
+                                "Reported latency: {} samples",
+                                report.latency_samples
+                            ));
+                            ui.label(format!("1 kHz sine peak: {:.2} dBFS", report.sine_peak_db));
+                            ui.label("Effective FIR:");
+                            for (tap, weight) in report.fir.iter().enumerate() {
+                                ui.label(
+                                    RichText::new(format!("  {}: {:+.6}", tap + 1, weight))
+                                        .font(FontId::monospace(11.0 * font_scale)),
+                                );
+                            }
+                        }
                     });
-                });
             },
         )
     }
 
     fn initialize(
         &mut self,
-        _audio_io_layout: &AudioIOLayout,
+        audio_io_layout: &AudioIOLayout,
         buffer_config: &BufferConfig,
-        _context: &mut impl InitContext<Self>,
+        context: &mut impl InitContext<Self>,
     ) -> bool {
         // After `PEAK_METER_DECAY_MS` milliseconds of pure silence, the peak meter's value should have dropped by 12 dB
         self.out_meter_decay_weight = 0.25f64
             .powf((buffer_config.sample_rate as f64 * PEAK_METER_DECAY_MS / 1000.0).recip())
             as f32;
+        self.sample_rate = buffer_config.sample_rate;
+        self.session_sample_rate
+            .store(buffer_config.sample_rate, std::sync::atomic::Ordering::Relaxed);
+        self.max_buffer_size.store(
+            buffer_config.max_buffer_size as usize,
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        self.diagnostics.set_sample_rate(buffer_config.sample_rate);
+        // Meter every channel the host actually gave us
+        let channels = audio_io_layout
+            .main_output_channels
+            .map_or(2, |channels| channels.get() as usize);
+        self.in_meter.set_channels(channels);
+        self.out_meter.set_channels(channels);
+        self.safety_limiter
+            .set_sample_rate(buffer_config.sample_rate);
+        self.auto_mult.set_sample_rate(buffer_config.sample_rate);
+        self.envelope.set_sample_rate(buffer_config.sample_rate);
+        self.alert_output.set_sample_rate(buffer_config.sample_rate);
+        self.auto_gain.set_sample_rate(buffer_config.sample_rate);
+        self.null_test.set_sample_rate(buffer_config.sample_rate);
+        self.presence.set_sample_rate(buffer_config.sample_rate);
+        self.meter_sub_block =
+            ((buffer_config.sample_rate * channel_meters::SUB_BLOCK_SECONDS) as usize).max(1);
+        self.left_wow_flutter.set_sample_rate(buffer_config.sample_rate);
+        self.right_wow_flutter.set_sample_rate(buffer_config.sample_rate);
+        self.left_wow_dry.set_sample_rate(buffer_config.sample_rate);
+        self.right_wow_dry.set_sample_rate(buffer_config.sample_rate);
+        self.left_tape.set_sample_rate(buffer_config.sample_rate);
+        self.right_tape.set_sample_rate(buffer_config.sample_rate);
+        self.crosstalk.set_sample_rate(buffer_config.sample_rate);
+        self.left_transformer
+            .set_sample_rate(buffer_config.sample_rate);
+        self.right_transformer
+            .set_sample_rate(buffer_config.sample_rate);
+        // Initialized again while already running means the host loaded new state, fade into
+        // it from what was playing
+        self.scene_fade_len =
+            (self.params.scene_fade.value() * 0.001 * buffer_config.sample_rate) as u32;
+        self.scene_fade_from = self
+            .last_console_params
+            .take()
+            .filter(|_| self.scene_fade_len > 0);
+        self.scene_fade_pos = 0;
+        if !self.crash_guard_active {
+            self.crash_guard_active = true;
+            if crash_guard::activate() {
+                self.safe_mode
+                    .store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+        }
+        // Persisted state is restored by now, bring the audio side copy of the snap flags in line
+        self.params
+            .slider_readout
+            .store(&self.params.gui_settings.read().unwrap());
+        self.params.snap_mask.store(
+            *self.params.snap_flags.read().unwrap(),
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        self.params.seed.store(
+            *self.params.random_seed.read().unwrap(),
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        self.true_peak.set_sample_rate(buffer_config.sample_rate);
+        self.true_peak_lookahead =
+            self.params.true_peak.value() && !self.params.zero_latency.value();
+        self.true_peak.set_lookahead(self.true_peak_lookahead);
+        self.wow_flutter_on = self.wow_flutter_active();
+        // Hosts initialize again when they switch to an offline bounce
+        self.offline_render = buffer_config.process_mode == ProcessMode::Offline;
+        self.params.quality_mask.store(
+            self.params.quality_profiles.read().unwrap().pack(),
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        self.params
+            .mod_routes
+            .store(&self.params.mod_matrix.read().unwrap());
+        self.params
+            .macro_targets
+            .store(&self.params.macro_map.read().unwrap());
+        self.params.loudness_delta_db.store(
+            self.params.loudness_delta.read().unwrap().unwrap_or(0.0),
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        let oversampling = self.params.active_oversampling(self.offline_render);
+        self.set_oversampling(oversampling);
+        context.set_latency_samples(self.total_latency());
 
         true
     }
@@ -679,193 +5409,1074 @@ impl Plugin for GladeDesk {
     fn process(
         &mut self,
         buffer: &mut Buffer,
-        _aux: &mut AuxiliaryBuffers,
-        _context: &mut impl ProcessContext<Self>,
+        aux: &mut AuxiliaryBuffers,
+        context: &mut impl ProcessContext<Self>,
     ) -> ProcessStatus {
-        for mut channel_samples in buffer.iter_samples() {
-            let mut out_amplitude: f32 = 0.0;
-            let mut in_amplitude: f32 = 0.0;
+        let note_gate_on: bool = self.params.note_gate.value();
+        let diagnostic_log: bool = self.params.diagnostic_log.value();
+        let block_start: i64 = context.transport().pos_samples().unwrap_or(0);
+        let mod_mode = self.params.mod_mode.value();
+        let mod_rate: f32 = self.params.mod_rate.value();
+        let mod_smooth: f32 = self.params.mod_smooth.value();
+        let mod_target = self.params.mod_target.value();
+        let lfo_shape = self.params.lfo_shape.value();
+        let lfo_target = self.params.lfo_target.value();
+        let mod_routes = self.params.mod_routes.load();
+        let macro_targets = self.params.macro_targets.load();
+        let macros_on = macro_targets.iter().flatten().any(Option::is_some);
+        // Synced, the LFO follows the host tempo and snaps to its beat position while playing
+        let transport = context.transport();
+        // The random sources start over from the saved seed each time playback starts, so
+        // every pass of a bounce comes out the same
+        let seed = self.params.seed.load(std::sync::atomic::Ordering::Relaxed);
+        if seed != self.active_seed || (transport.playing && !self.was_playing) {
+            self.reseed(seed);
+        }
+        self.was_playing = transport.playing;
+        let lfo_increment: f64 = match self.params.lfo_sync.value().beats() {
+            Some(beats) => {
+                if transport.playing {
+                    if let Some(pos_beats) = transport.pos_beats() {
+                        self.lfo.sync_to(pos_beats, beats);
+                    }
+                }
+                transport.tempo.unwrap_or(120.0) / 60.0 / beats / self.sample_rate as f64
+            }
+            None => self.params.lfo_rate.value() as f64 / self.sample_rate as f64,
+        };
+        let lofi_ratio = self.params.lofi_ratio.value();
+        let lofi_interp = self.params.lofi_interp.value();
+        let dual_engine: bool = self.params.dual_engine.value();
+        let auto_mult: bool = self.params.auto_mult.value();
+        self.envelope
+            .set_times(self.params.env_attack.value(), self.params.env_release.value());
+        let auto_gain: bool = self.params.auto_gain.value();
+        let output_invert: bool = self.params.output_invert.value();
+        let delta: bool = self.params.delta.value();
+        let loudness_trim: f32 =
+            if self.normalize_loudness.load(std::sync::atomic::Ordering::Relaxed) {
+                util::db_to_gain(
+                    -self
+                        .params
+                        .loudness_delta_db
+                        .load(std::sync::atomic::Ordering::Relaxed),
+                )
+            } else {
+                1.0
+            };
+        let hq_shaper: bool = self.params.active_hq_shaper(self.offline_render);
+        let push_curve = self.params.push_curve.value();
+        let harmonic_mode = self.params.harmonic_mode.value();
+        let tap_count = self.params.tap_count.value() as usize;
+        let polarity = self
+            .params
+            .polarity_bank()
+            .map(|param| param.value().sign());
+        let mix_law = self.params.mix_law.value();
+        let calibration_in: f32 = self.params.calibration.value().input_gain();
+        let calibration_out: f32 = 1.0 / calibration_in;
+        let console_mode = self.params.console_mode.value();
+        let bank_morph = self.params.bank_morph.value();
+        let link: bool = self.params.link.value();
+        // The same measurement drives the polarity readout, so it also runs while the editor
+        // is open
+        let null_test_on = self
+            .null_test_shared
+            .enabled
+            .load(std::sync::atomic::Ordering::Relaxed)
+            || self.params.editor_state.is_open();
+        if !null_test_on {
+            self.null_test.reset();
+        }
+        if self.gain_match.take_request() {
+            self.gain_match_meter.start(self.sample_rate);
+        }
+        let dc_block: bool = self.params.dc_block.value();
+        let dc_block_cutoff: f32 = self.params.dc_block_cutoff.value();
+        self.left_dc_blocker
+            .set_cutoff(dc_block_cutoff, self.sample_rate);
+        self.right_dc_blocker
+            .set_cutoff(dc_block_cutoff, self.sample_rate);
+        // Host samples between taps, then scaled to the rate the consoles run at
+        let tap_spacing = engine::tap_spacing(self.params.tap_spacing_ms(), self.sample_rate);
+        // Start bank B from silence instead of whatever it held when it was switched off
+        if dual_engine && !self.dual_was_on {
+            self.left_console_b.reset();
+            self.right_console_b.reset();
+        }
+        self.dual_was_on = dual_engine;
+        let multiband: bool = self.params.multiband.value();
+        if multiband && !self.multiband_was_on {
+            self.left_multiband.reset();
+            self.right_multiband.reset();
+        }
+        self.multiband_was_on = multiband;
+        let safe_mode: bool = self.safe_mode.load(std::sync::atomic::Ordering::Relaxed);
+        let mod_matrix_on = mod_routes.iter().any(Option::is_some) && !safe_mode;
+        let tape: bool = self.params.tape.value() && !safe_mode;
+        if tape && !self.tape_was_on {
+            self.left_tape.reset();
+            self.right_tape.reset();
+        }
+        self.tape_was_on = tape;
+        let transformer: bool = self.params.transformer.value() && !safe_mode;
+        if transformer && !self.transformer_was_on {
+            self.left_transformer.reset();
+            self.right_transformer.reset();
+        }
+        self.transformer_was_on = transformer;
+        for (filter, slope, cutoff) in [
+            (&mut self.left_hpf, &self.params.hpf_slope, &self.params.hpf_cutoff),
+            (&mut self.right_hpf, &self.params.hpf_slope, &self.params.hpf_cutoff),
+            (&mut self.left_lpf, &self.params.lpf_slope, &self.params.lpf_cutoff),
+            (&mut self.right_lpf, &self.params.lpf_slope, &self.params.lpf_cutoff),
+        ] {
+            filter.set(slope.value(), cutoff.value(), self.sample_rate);
+        }
+        let tilt_placement = self.params.tilt_placement.value();
+        let tilt_pivot: f32 = self.params.tilt_pivot.value();
+        self.left_tilt.set_pivot(tilt_pivot, self.sample_rate);
+        self.right_tilt.set_pivot(tilt_pivot, self.sample_rate);
+        let transformer_core: f32 = self.params.transformer_core.value();
+        self.left_transformer.set_core(transformer_core);
+        self.right_transformer.set_core(transformer_core);
+        let crossover_low: f32 = self.params.crossover_low.value();
+        let crossover_high: f32 = self.params.crossover_high.value();
+        self.left_multiband
+            .set_crossovers(crossover_low, crossover_high);
+        self.right_multiband
+            .set_crossovers(crossover_low, crossover_high);
+
+        // Oversampling changes the latency so let the host know
+        let oversampling = self.params.active_oversampling(self.offline_render);
+        let true_peak: bool = self.params.true_peak.value();
+        // Zero Latency keeps the limiter but drops its lookahead
+        let true_peak_lookahead = true_peak && !self.params.zero_latency.value();
+        // Its centre delay is latency too, so it's switched per block like the rest
+        let wow_flutter = self.wow_flutter_active();
+        if oversampling != self.oversampling
+            || true_peak_lookahead != self.true_peak_lookahead
+            || wow_flutter != self.wow_flutter_on
+        {
+            self.true_peak_lookahead = true_peak_lookahead;
+            self.true_peak.set_lookahead(true_peak_lookahead);
+            if wow_flutter && !self.wow_flutter_on {
+                self.left_wow_flutter.reset();
+                self.right_wow_flutter.reset();
+                self.left_wow_dry.reset();
+                self.right_wow_dry.reset();
+            }
+            self.wow_flutter_on = wow_flutter;
+            self.set_oversampling(oversampling);
+            context.set_latency_samples(self.total_latency());
+        }
+        // The null test lines the bypassed input up by what the host was told
+        self.null_test.set_latency(self.total_latency() as usize);
+        self.true_peak
+            .set_release(self.params.true_peak_release.value());
+        let running_spacing = tap_spacing * oversampling.factor() as f32;
+        let adaa_delay = engine::adaa_delay(hq_shaper, oversampling.factor());
+        for console in [
+            &mut self.left_console,
+            &mut self.right_console,
+            &mut self.left_console_b,
+            &mut self.right_console_b,
+        ] {
+            console.set_spacing(running_spacing);
+        }
+        for multiband in [&mut self.left_multiband, &mut self.right_multiband] {
+            multiband.set_spacing(running_spacing);
+        }
+        self.note_gate.set_times(
+            self.params.gate_attack.value(),
+            self.params.gate_release.value(),
+            self.sample_rate,
+        );
+
+        if self
+            .safety_dismiss
+            .swap(false, std::sync::atomic::Ordering::Relaxed)
+        {
+            self.safety_limiter.disengage();
+            self.safety_engaged
+                .store(false, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        let mut last_mod_value: f32 = 0.0;
+        let mut last_lfo_value: f32 = 0.0;
+        let mut last_envelope: f32 = 0.0;
+        let mut block_clipped = false;
+        let mut in_peaks = channel_meters::BlockPeaks::default();
+        let mut in_sub_peaks = channel_meters::BlockPeaks::default();
+        let mut out_sub_peaks = channel_meters::BlockPeaks::default();
+        let mut sub_block_len: usize = 0;
+        // Meters are only worked out while the GUI is open
+        let meters_open = self.params.editor_state.is_open();
+        let meter_decay = if self.reduced_motion.load(std::sync::atomic::Ordering::Relaxed) {
+            0.0
+        } else {
+            self.out_meter_decay_weight
+        };
+        let block_len = buffer.samples();
+        // Sidechain key for the envelope follower, only while it's on and the host sends audio
+        let sidechain = aux
+            .inputs
+            .first()
+            .map(|key| key.as_slice_immutable())
+            .filter(|key| {
+                self.params.env_sidechain.value()
+                    && key.first().is_some_and(|channel| channel.len() >= block_len)
+            });
+        // The Delta output always carries what the console adds, whatever the main output plays
+        let mut delta_out = aux
+            .outputs
+            .first_mut()
+            .map(|delta_out| delta_out.as_slice())
+            .filter(|delta_out| {
+                delta_out
+                    .iter()
+                    .all(|channel| channel.len() >= block_len)
+            });
+        let mut next_event = context.next_event();
+        for (sample_id, mut channel_samples) in buffer.iter_samples().enumerate() {
+            // Handle MIDI at the sample it lands on so the gate stays tight
+            while let Some(event) = next_event {
+                if event.timing() > sample_id as u32 {
+                    break;
+                }
+
+                match event {
+                    NoteEvent::NoteOn { note, .. } => self.note_gate.note_on(note),
+                    NoteEvent::NoteOff { note, .. } => self.note_gate.note_off(note),
+                    NoteEvent::Choke { note, .. } => self.note_gate.note_off(note),
+                    _ => (),
+                }
+
+                next_event = context.next_event();
+            }
+            let gate_level: f32 = self.note_gate.next();
+
             let mut processed_sample_l: f32;
             let mut processed_sample_r: f32;
-            let num_samples = channel_samples.len();
+            let num_channels = channel_samples.len();
 
             let gain: f32 = util::gain_to_db(self.params.free_gain.smoothed.next());
-            let num_gain: f32;
             let output_gain: f32 = self.params.output_gain.smoothed.next();
-            let slider_1_coeff: f32 = self.params.slider_1_coeff.smoothed.next();
-            let slider_2_coeff: f32 = self.params.slider_2_coeff.smoothed.next();
-            let slider_3_coeff: f32 = self.params.slider_3_coeff.smoothed.next();
-            let slider_4_coeff: f32 = self.params.slider_4_coeff.smoothed.next();
-            let slider_5_coeff: f32 = self.params.slider_5_coeff.smoothed.next();
-            let slider_6_coeff: f32 = self.params.slider_6_coeff.smoothed.next();
-            let slider_7_coeff: f32 = self.params.slider_7_coeff.smoothed.next();
-            let slider_8_coeff: f32 = self.params.slider_8_coeff.smoothed.next();
-            let slider_1_skew: f32 = self.params.slider_1_skew.smoothed.next();
-            let slider_2_skew: f32 = self.params.slider_2_skew.smoothed.next();
-            let slider_3_skew: f32 = self.params.slider_3_skew.smoothed.next();
-            let slider_4_skew: f32 = self.params.slider_4_skew.smoothed.next();
-            let slider_5_skew: f32 = self.params.slider_5_skew.smoothed.next();
-            let slider_6_skew: f32 = self.params.slider_6_skew.smoothed.next();
-            let slider_7_skew: f32 = self.params.slider_7_skew.smoothed.next();
-            let slider_8_skew: f32 = self.params.slider_8_skew.smoothed.next();
-            let push_amount: f32 = self.params.push_amount.smoothed.next();
-            let multiplier: f32 = self.params.multiplier.smoothed.next();
             let dry_wet: f32 = self.params.dry_wet.value();
+            let mut console_params = engine::ConsoleParams {
+                input_gain: util::db_to_gain(gain),
+                push: self.params.push_amount.smoothed.next(),
+                multiplier: self.params.multiplier.smoothed.next(),
+                coeffs: next_bank(
+                    self.params.coeff_bank(),
+                    self.params.snap_slots(EditBank::A, false),
+                ),
+                skews: next_bank(
+                    self.params.skew_bank(),
+                    self.params.snap_slots(EditBank::A, true),
+                ),
+                wet: dry_wet,
+                output_gain,
+                hq_shaper,
+                push_curve,
+                bias: self.params.bias.smoothed.next(),
+                even_odd: self.params.even_odd.smoothed.next(),
+                harmonic_mode,
+                harmonics: self
+                    .params
+                    .harmonic_bank()
+                    .map(|param| param.smoothed.next()),
+                tap_count,
+                polarity,
+                feedback: self.params.feedback.smoothed.next(),
+                feedback_damping: self.params.feedback_damping.smoothed.next(),
+                tap_damping: self.params.tap_damping.smoothed.next(),
+            };
+
+            // Macros set their destinations first so the modulation below rides on top
+            let macro_values = self.params.macro_bank().map(|param| param.smoothed.next());
+            let macro_positions = if macros_on {
+                macros::positions(&macro_targets, &macro_values)
+            } else {
+                [None; mod_matrix::ModDestination::COUNT]
+            };
+            if macros_on {
+                apply_macros(&self.params, &mut console_params, &macro_positions, false);
+            }
+
+            // Random modulation onto Push or Multiplier
+            let standby_gain: f32 = self.presence.next_gain();
+            let mod_depth: f32 = self.params.mod_depth.smoothed.next();
+            let random_raw: f32 =
+                self.random_mod
+                    .next(mod_mode, mod_rate, mod_smooth, self.sample_rate)
+                    * standby_gain;
+            let mod_value: f32 = random_raw * mod_depth;
+            last_mod_value = mod_value;
+            match mod_target {
+                modulation::ModTarget::Push => {
+                    console_params.push = (console_params.push + mod_value * 0.5).clamp(0.0, 1.0);
+                }
+                modulation::ModTarget::Multiplier => {
+                    console_params.multiplier =
+                        (console_params.multiplier * (1.0 + mod_value * 0.5)).clamp(1.0, 10.0);
+                }
+            }
+
+            // LFO onto Push, Multiplier or the skews of both banks
+            let lfo_depth: f32 = self.params.lfo_depth.smoothed.next();
+            let lfo_raw: f32 = self.lfo.next(lfo_shape, lfo_increment) * standby_gain;
+            let lfo_value: f32 = lfo_raw * lfo_depth;
+            last_lfo_value = lfo_value;
+            let mut lfo_skew_scale: f32 = 1.0;
+            match lfo_target {
+                modulation::LfoTarget::Push => {
+                    console_params.push = (console_params.push + lfo_value * 0.5).clamp(0.0, 1.0);
+                }
+                modulation::LfoTarget::Multiplier => {
+                    console_params.multiplier =
+                        (console_params.multiplier * (1.0 + lfo_value * 0.5)).clamp(1.0, 10.0);
+                }
+                modulation::LfoTarget::Skew if !safe_mode => lfo_skew_scale += lfo_value,
+                modulation::LfoTarget::Skew => (),
+            }
+            for skew in console_params.skews.iter_mut() {
+                *skew *= lfo_skew_scale;
+            }
+
+            // Analog drift scales each coefficient of both banks, safe mode holds them still
+            let drift_amount: f32 = self.params.drift.smoothed.next() * standby_gain;
+            let drift: [f32; engine::TAPS] = if drift_amount > 0.0 && !safe_mode {
+                let sample_rate = self.sample_rate;
+                let mut factors = [1.0; engine::TAPS];
+                for ((factor, source), rate) in factors
+                    .iter_mut()
+                    .zip(self.drift.iter_mut())
+                    .zip(DRIFT_RATES)
+                {
+                    let wander =
+                        source.next(modulation::RandomMode::SmoothRandom, rate, 1.0, sample_rate);
+                    *factor += wander * drift_amount * MAX_DRIFT;
+                }
+                factors
+            } else {
+                [1.0; engine::TAPS]
+            };
+            for (coeff, factor) in console_params.coeffs.iter_mut().zip(drift) {
+                *coeff *= factor;
+            }
+
+            // Glide out of the previous scene after a state load
+            if let Some(from) = self.scene_fade_from {
+                self.scene_fade_pos += 1;
+                if self.scene_fade_pos >= self.scene_fade_len {
+                    self.scene_fade_from = None;
+                } else {
+                    let t = self.scene_fade_pos as f32 / self.scene_fade_len as f32;
+                    console_params = from.blended(&console_params, t);
+                }
+            }
+            self.last_console_params = Some(console_params);
+
+            // Safe mode keeps only the linear tap network running
+            if safe_mode {
+                console_params.push = 0.0;
+                console_params.bias = 0.0;
+                console_params.harmonic_mode = engine::HarmonicMode::Off;
+                console_params.feedback = 0.0;
+            }
 
-            // Split left and right same way original subhoofer did
+            // Split left and right same way original subhoofer did. A mono layout runs its one
+            // channel through both sides and only the left gets written back.
             let mut in_l = *channel_samples.get_mut(0).unwrap();
-            let mut in_r = *channel_samples.get_mut(1).unwrap();
+            let mut in_r = if num_channels > 1 {
+                *channel_samples.get_mut(1).unwrap()
+            } else {
+                in_l
+            };
+            let bypassed = in_l + in_r;
+            let raw_input = [in_l, in_r];
+            in_l = console_mode.decode(in_l);
+            in_r = console_mode.decode(in_r);
 
-            num_gain = gain;
-            in_l *= util::db_to_gain(num_gain);
-            in_r *= util::db_to_gain(num_gain);
-            in_amplitude += in_l + in_r;
+            in_l *= console_params.input_gain;
+            in_r *= console_params.input_gain;
+            let input_levels = [in_l, in_r];
+
+            // Auto Mult leans the multiplier against the long term input loudness
+            if auto_mult {
+                console_params.multiplier = (console_params.multiplier
+                    * self.auto_mult.next(in_l, in_r))
+                .clamp(1.0, 10.0);
+            }
+
+            // Envelope follower pushes harder or backs off with the level, safe mode holds still
+            let envelope: f32 = match sidechain {
+                Some(key) => {
+                    let key_l = key[0][sample_id];
+                    self.envelope
+                        .next(key_l, key.get(1).map_or(key_l, |right| right[sample_id]))
+                }
+                None => self.envelope.next(in_l, in_r),
+            };
+            last_envelope = envelope;
+            let env_push: f32 = self.params.env_push.smoothed.next();
+            let env_skew: f32 = self.params.env_skew.smoothed.next();
+            let push_dynamics: f32 = self.params.push_dynamics.smoothed.next();
+            let env_amount = if safe_mode { 0.0 } else { envelope * standby_gain };
+            console_params.push = (console_params.push * (1.0 - env_amount * push_dynamics)
+                + env_amount * env_push)
+                .clamp(0.0, 1.0);
+            let env_skew_scale = 1.0 + env_amount * env_skew;
+            for skew in console_params.skews.iter_mut() {
+                *skew *= env_skew_scale;
+            }
+
+            // Mod matrix routes on top of everything above, in each destination's own range
+            let matrix_offsets = if mod_matrix_on {
+                let [macro_1, macro_2, macro_3, macro_4] = macro_values;
+                mod_matrix::offsets(
+                    &mod_routes,
+                    &[
+                        random_raw, lfo_raw, env_amount, macro_1, macro_2, macro_3, macro_4,
+                    ],
+                )
+            } else {
+                [0.0; mod_matrix::ModDestination::COUNT]
+            };
+            if mod_matrix_on {
+                let offset = |destination: mod_matrix::ModDestination| {
+                    matrix_offsets[destination.index()]
+                };
+                let params = &self.params;
+                let (coeff_bank, skew_bank) = (params.coeff_bank(), params.skew_bank());
+                console_params.push = offset_plain(
+                    &params.push_amount,
+                    console_params.push,
+                    offset(mod_matrix::ModDestination::Push),
+                );
+                console_params.multiplier = offset_plain(
+                    &params.multiplier,
+                    console_params.multiplier,
+                    offset(mod_matrix::ModDestination::Multiplier),
+                );
+                console_params.wet = offset_plain(
+                    &params.dry_wet,
+                    console_params.wet,
+                    offset(mod_matrix::ModDestination::DryWet),
+                );
+                console_params.bias = offset_plain(
+                    &params.bias,
+                    console_params.bias,
+                    offset(mod_matrix::ModDestination::Bias),
+                );
+                console_params.even_odd = offset_plain(
+                    &params.even_odd,
+                    console_params.even_odd,
+                    offset(mod_matrix::ModDestination::EvenOdd),
+                );
+                console_params.feedback = offset_plain(
+                    &params.feedback,
+                    console_params.feedback,
+                    offset(mod_matrix::ModDestination::Feedback),
+                );
+                console_params.feedback_damping = offset_plain(
+                    &params.feedback_damping,
+                    console_params.feedback_damping,
+                    offset(mod_matrix::ModDestination::FeedbackDamping),
+                );
+                console_params.tap_damping = offset_plain(
+                    &params.tap_damping,
+                    console_params.tap_damping,
+                    offset(mod_matrix::ModDestination::TapDamping),
+                );
+                for (tap, (coeff, skew)) in console_params
+                    .coeffs
+                    .iter_mut()
+                    .zip(console_params.skews.iter_mut())
+                    .enumerate()
+                {
+                    *coeff = offset_plain(
+                        coeff_bank[tap],
+                        *coeff,
+                        offset(mod_matrix::ModDestination::Coeff(tap)),
+                    );
+                    *skew = offset_plain(
+                        skew_bank[tap],
+                        *skew,
+                        offset(mod_matrix::ModDestination::Skew(tap)),
+                    );
+                }
+            }
+
+            // Into the nonlinear stages at the calibrated level, undone after the transformer
+            in_l *= calibration_in;
+            in_r *= calibration_in;
+
+            // DC trim right at the console input, before the shaper
+            in_l += self.params.dc_trim_l.smoothed.next();
+            in_r += self.params.dc_trim_r.smoothed.next();
+
+            // Rumble and fizz out before they reach the shaper and skew terms
+            in_l = self.left_lpf.process(self.left_hpf.process(in_l));
+            in_r = self.right_lpf.process(self.right_hpf.process(in_r));
+
+            let tilt: f32 = self.params.tilt.smoothed.next();
+            self.left_tilt.set_tilt(tilt);
+            self.right_tilt.set_tilt(tilt);
+            if tilt_placement == filters::TiltPlacement::Pre {
+                in_l = self.left_tilt.process(in_l);
+                in_r = self.right_tilt.process(in_r);
+            }
 
             ///////////////////////////////////////////////////////////////////////
             // Perform processing on the sample
+            // Bank B shares everything with A except the coefficients and skews
+            let mut console_params_b = engine::ConsoleParams {
+                coeffs: next_bank(
+                    self.params.coeff_bank_b(),
+                    self.params.snap_slots(EditBank::B, false),
+                ),
+                skews: next_bank(
+                    self.params.skew_bank_b(),
+                    self.params.snap_slots(EditBank::B, true),
+                ),
+                ..console_params
+            };
+            if macros_on {
+                apply_macros(&self.params, &mut console_params_b, &macro_positions, true);
+            }
+            for (coeff, factor) in console_params_b.coeffs.iter_mut().zip(drift) {
+                *coeff *= factor;
+            }
+            for skew in console_params_b.skews.iter_mut() {
+                *skew *= env_skew_scale * lfo_skew_scale;
+            }
+            if mod_matrix_on {
+                let (coeff_bank, skew_bank) =
+                    (self.params.coeff_bank_b(), self.params.skew_bank_b());
+                for (tap, (coeff, skew)) in console_params_b
+                    .coeffs
+                    .iter_mut()
+                    .zip(console_params_b.skews.iter_mut())
+                    .enumerate()
+                {
+                    *coeff = offset_plain(
+                        coeff_bank[tap],
+                        *coeff,
+                        matrix_offsets[mod_matrix::ModDestination::Coeff(tap).index()],
+                    );
+                    *skew = offset_plain(
+                        skew_bank[tap],
+                        *skew,
+                        matrix_offsets[mod_matrix::ModDestination::Skew(tap).index()],
+                    );
+                }
+            }
+            let engine_balance: f32 = self.params.engine_balance.smoothed.next();
 
-            // Normalize really small values
-            if in_l.abs() < 1.18e-23 {
-                in_l = 0.1 * 1.18e-17;
-            }
-            if in_r.abs() < 1.18e-23 {
-                in_r = 0.1 * 1.18e-17;
-            }
-
-            // Calculate our sin 'warmed' sample
-            processed_sample_l = (1.0 - push_amount) * in_l + push_amount * ((in_l * 1.2).sin());
-            processed_sample_r = (1.0 - push_amount) * in_r + push_amount * ((in_r * 1.2).sin());
-
-            // Shift the buffer arrays
-            self.left_vec.push_front(processed_sample_l);
-            self.left_vec.pop_back();
-            self.right_vec.push_front(processed_sample_r);
-            self.right_vec.pop_back();
-
-            let mut temp_l: f32 = 0.0;
-            let mut temp_r: f32 = 0.0;
-
-            // Sequential process like the Airwindows Console emulations
-            if true {
-                temp_l += self.left_vec[0]
-                    * (slider_1_coeff * multiplier
-                        + slider_1_skew * multiplier * self.left_vec[0].abs());
-                temp_l += self.left_vec[1]
-                    * (slider_2_coeff * multiplier
-                        + slider_2_skew * multiplier * self.left_vec[1].abs());
-                temp_l -= self.left_vec[2]
-                    * (slider_3_coeff * multiplier
-                        + slider_3_skew * multiplier * self.left_vec[2].abs());
-                temp_l += self.left_vec[3]
-                    * (slider_4_coeff * multiplier
-                        + slider_4_skew * multiplier * self.left_vec[3].abs());
-                temp_l -= self.left_vec[4]
-                    * (slider_5_coeff * multiplier
-                        + slider_5_skew * multiplier * self.left_vec[4].abs());
-                temp_l += self.left_vec[5]
-                    * (slider_6_coeff * multiplier
-                        + slider_6_skew * multiplier * self.left_vec[5].abs());
-                temp_l -= self.left_vec[6]
-                    * (slider_7_coeff * multiplier
-                        + slider_7_skew * multiplier * self.left_vec[6].abs());
-                temp_l += self.left_vec[7]
-                    * (slider_8_coeff * multiplier
-                        + slider_8_skew * multiplier * self.left_vec[7].abs());
-
-                temp_r += self.right_vec[0]
-                    * (slider_1_coeff * multiplier
-                        + slider_1_skew * multiplier * self.right_vec[0].abs());
-                temp_r += self.right_vec[1]
-                    * (slider_2_coeff * multiplier
-                        + slider_2_skew * multiplier * self.right_vec[1].abs());
-                temp_r -= self.right_vec[2]
-                    * (slider_3_coeff * multiplier
-                        + slider_3_skew * multiplier * self.right_vec[2].abs());
-                temp_r += self.right_vec[3]
-                    * (slider_4_coeff * multiplier
-                        + slider_4_skew * multiplier * self.right_vec[3].abs());
-                temp_r -= self.right_vec[4]
-                    * (slider_5_coeff * multiplier
-                        + slider_5_skew * multiplier * self.right_vec[4].abs());
-                temp_r += self.right_vec[5]
-                    * (slider_6_coeff * multiplier
-                        + slider_6_skew * multiplier * self.right_vec[5].abs());
-                temp_r -= self.right_vec[6]
-                    * (slider_7_coeff * multiplier
-                        + slider_7_skew * multiplier * self.right_vec[6].abs());
-                temp_r += self.right_vec[7]
-                    * (slider_8_coeff * multiplier
-                        + slider_8_skew * multiplier * self.right_vec[7].abs());
+            // Unlinked, the right channel gets its own offsets on top of both banks
+            let r_coeff_offsets = next_bank(
+                self.params.r_coeff_offsets(),
+                self.params.snap_slots(EditBank::R, false),
+            );
+            let r_skew_offsets = next_bank(
+                self.params.r_skew_offsets(),
+                self.params.snap_slots(EditBank::R, true),
+            );
+            let (console_params_r, console_params_b_r) = if link {
+                (console_params, console_params_b)
+            } else {
+                (
+                    console_params.with_offsets(&r_coeff_offsets, &r_skew_offsets),
+                    console_params_b.with_offsets(&r_coeff_offsets, &r_skew_offsets),
+                )
+            };
+
+            // Band drives stand in for Push in multiband mode
+            let mut band_drive = [
+                self.params.low_drive.smoothed.next(),
+                self.params.mid_drive.smoothed.next(),
+                self.params.high_drive.smoothed.next(),
+            ];
+            if safe_mode {
+                band_drive = [0.0; multiband::BANDS];
             }
+            let band_mix = [
+                self.params.low_mix.smoothed.next(),
+                self.params.mid_mix.smoothed.next(),
+                self.params.high_mix.smoothed.next(),
+            ];
+
+            let left_console = &mut self.left_console;
+            let right_console = &mut self.right_console;
+            let left_console_b = &mut self.left_console_b;
+            let right_console_b = &mut self.right_console_b;
+            let left_multiband = &mut self.left_multiband;
+            let right_multiband = &mut self.right_multiband;
+            let left_oversampler = &mut self.left_oversampler;
+            let right_oversampler = &mut self.right_oversampler;
+            let temp_l = self.left_lofi.process(lofi_ratio, lofi_interp, in_l, |x| {
+                left_oversampler.process(x, |y| {
+                    if multiband {
+                        left_multiband.process_taps(y, &console_params, &band_drive, &band_mix)
+                    } else if dual_engine {
+                        engine::process_dual_taps(
+                            left_console,
+                            left_console_b,
+                            y,
+                            &console_params,
+                            &console_params_b,
+                            engine_balance,
+                            bank_morph,
+                        )
+                        .1
+                    } else {
+                        left_console.process_taps(y, &console_params).1
+                    }
+                })
+            });
+            let temp_r = self.right_lofi.process(lofi_ratio, lofi_interp, in_r, |x| {
+                right_oversampler.process(x, |y| {
+                    if multiband {
+                        right_multiband.process_taps(y, &console_params_r, &band_drive, &band_mix)
+                    } else if dual_engine {
+                        engine::process_dual_taps(
+                            right_console,
+                            right_console_b,
+                            y,
+                            &console_params_r,
+                            &console_params_b_r,
+                            engine_balance,
+                            bank_morph,
+                        )
+                        .1
+                    } else {
+                        right_console.process_taps(y, &console_params_r).1
+                    }
+                })
+            });
+
+            // Dry path gets the same delay as the oversampled wet path
+            let in_l = self
+                .left_adaa_aligner
+                .process(self.left_oversampler.delay_dry(in_l), adaa_delay);
+            let in_r = self
+                .right_adaa_aligner
+                .process(self.right_oversampler.delay_dry(in_r), adaa_delay);
+
+            // Bit crusher on the tap network output, rest of the lo-fi section
+            let crush_bits: f32 = self.params.crush_bits.smoothed.next();
+            let crush_mix: f32 = self.params.crush_mix.smoothed.next();
+            let (temp_l, temp_r) = if crush_mix > 0.0 {
+                (
+                    temp_l + (lofi::crush(temp_l, crush_bits) - temp_l) * crush_mix,
+                    temp_r + (lofi::crush(temp_r, crush_bits) - temp_r) * crush_mix,
+                )
+            } else {
+                (temp_l, temp_r)
+            };
 
             processed_sample_l = temp_l;
             processed_sample_r = temp_r;
+            let tap_output = [temp_l, temp_r];
 
             ///////////////////////////////////////////////////////////////////////
 
             // Calculate dry/wet mix
-            let wet_gain: f32 = dry_wet;
-            //processed_sample_l = (1.0 - wet_gain)*in_l + processed_sample_l * wet_gain;
-            //processed_sample_r = (1.0 - wet_gain)*in_r + processed_sample_r * wet_gain;
-            processed_sample_l = in_l + processed_sample_l * wet_gain;
-            processed_sample_r = in_r + processed_sample_r * wet_gain;
+            let wet_gain: f32 = if note_gate_on {
+                dry_wet * gate_level
+            } else {
+                dry_wet
+            };
+            // The dry is already lined up with the oversampling and the ADAA shaper
+            let (dry_l, dry_r) = (in_l, in_r);
+            processed_sample_l = mix_law.mix(dry_l, processed_sample_l, wet_gain);
+            processed_sample_r = mix_law.mix(dry_r, processed_sample_r, wet_gain);
+
+            // Channels bleed into each other in the desk, a mono layout has nothing to bleed
+            let crosstalk_db = self.params.crosstalk.smoothed.next();
+            let crosstalk_dynamics = self.params.crosstalk_dynamics.smoothed.next();
+            let crosstalk = crosstalk_db > crosstalk::CROSSTALK_OFF_DB && num_channels > 1;
+            if crosstalk && !self.crosstalk_was_on {
+                self.crosstalk.reset();
+            }
+            self.crosstalk_was_on = crosstalk;
+            if crosstalk {
+                // Like real desks the bleed climbs with the level
+                let bleed_db =
+                    crosstalk_db + env_amount * crosstalk_dynamics * crosstalk::DYNAMIC_RANGE_DB;
+                (processed_sample_l, processed_sample_r) = self.crosstalk.process(
+                    processed_sample_l,
+                    processed_sample_r,
+                    util::db_to_gain(bleed_db),
+                );
+            }
+
+            if tilt_placement == filters::TiltPlacement::Post {
+                processed_sample_l = self.left_tilt.process(processed_sample_l);
+                processed_sample_r = self.right_tilt.process(processed_sample_r);
+            }
+
+            // Same transport for both sides, so the two wobble together
+            let wow_depth = self.params.wow_depth.smoothed.next();
+            let wow_rate = self.params.wow_rate.smoothed.next();
+            let (dry_l, dry_r) = if self.wow_flutter_on {
+                processed_sample_l =
+                    self.left_wow_flutter.process(processed_sample_l, wow_rate, wow_depth);
+                processed_sample_r =
+                    self.right_wow_flutter.process(processed_sample_r, wow_rate, wow_depth);
+                (
+                    self.left_wow_dry.process(dry_l, wow_rate, 0.0),
+                    self.right_wow_dry.process(dry_r, wow_rate, 0.0),
+                )
+            } else {
+                (dry_l, dry_r)
+            };
+
+            let tape_drive = self.params.tape_drive.smoothed.next();
+            let tape_bias = self.params.tape_bias.smoothed.next();
+            if tape {
+                self.left_tape.set_params(tape_drive, tape_bias);
+                self.right_tape.set_params(tape_drive, tape_bias);
+                processed_sample_l = self.left_tape.process(processed_sample_l);
+                processed_sample_r = self.right_tape.process(processed_sample_r);
+            }
+            let transformer_drive = self.params.transformer_drive.smoothed.next();
+            if transformer {
+                self.left_transformer.set_drive(transformer_drive);
+                self.right_transformer.set_drive(transformer_drive);
+                processed_sample_l = self.left_transformer.process(processed_sample_l);
+                processed_sample_r = self.right_transformer.process(processed_sample_r);
+            }
+            processed_sample_l *= calibration_out;
+            processed_sample_r *= calibration_out;
+
+            // Strip any DC the asymmetric skew terms left behind
+            if dc_block {
+                processed_sample_l = self.left_dc_blocker.process(processed_sample_l);
+                processed_sample_r = self.right_dc_blocker.process(processed_sample_r);
+            }
+
+            // Level match against the input before the output gain so that still works
+            if auto_gain {
+                let trim = self
+                    .auto_gain
+                    .next(input_levels, [processed_sample_l, processed_sample_r]);
+                processed_sample_l *= trim;
+                processed_sample_r *= trim;
+            }
+
+            // Delta takes away the same dry the mix law used, leaving what was added
+            let (delta_l, delta_r) = (
+                processed_sample_l - dry_l * calibration_out,
+                processed_sample_r - dry_r * calibration_out,
+            );
+            if let Some(delta_out) = delta_out.as_mut() {
+                for (channel, value) in delta_out.iter_mut().zip([delta_l, delta_r]) {
+                    channel[sample_id] = value;
+                }
+            }
+            if delta {
+                processed_sample_l = delta_l;
+                processed_sample_r = delta_r;
+            }
 
-            // get the output amplitude here
             processed_sample_l = processed_sample_l * output_gain;
             processed_sample_r = processed_sample_r * output_gain;
-            out_amplitude += processed_sample_l + processed_sample_r;
+            if output_invert {
+                processed_sample_l = -processed_sample_l;
+                processed_sample_r = -processed_sample_r;
+            }
+            processed_sample_l = console_mode.encode(processed_sample_l);
+            processed_sample_r = console_mode.encode(processed_sample_r);
+
+            self.gain_match_meter.next(
+                raw_input,
+                [processed_sample_l, processed_sample_r],
+                &self.gain_match,
+            );
+            // Loudness normalization after the measurement so it never measures itself
+            processed_sample_l *= loudness_trim;
+            processed_sample_r *= loudness_trim;
+
+            if diagnostic_log {
+                if let Some(event) = self.diagnostics.check(
+                    block_start + sample_id as i64,
+                    [processed_sample_l, processed_sample_r],
+                    tap_output,
+                ) {
+                    context.execute_background(Task::Diagnostic(
+                        event,
+                        self.params.diagnostic_context(),
+                    ));
+                }
+            }
+
+            // Master bus limiting, ahead of the safety limiter so that never has to step in
+            let mut frame = [processed_sample_l, processed_sample_r];
+            let true_peak_ceiling =
+                util::db_to_gain(self.params.true_peak_ceiling.smoothed.next());
+            if true_peak {
+                self.true_peak.process(&mut frame, true_peak_ceiling);
+            }
+
+            // Protect the monitors if the output runs away
+            if self.safety_limiter.process(&mut frame) {
+                self.safety_engaged
+                    .store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+            let [processed_sample_l, processed_sample_r] = frame;
+
+            if null_test_on {
+                if let Some(result) = self
+                    .null_test
+                    .process(bypassed, processed_sample_l + processed_sample_r)
+                {
+                    self.null_test_shared.store(result);
+                }
+            }
 
             // Assign back so we can output our processed sounds
             *channel_samples.get_mut(0).unwrap() = processed_sample_l;
-            *channel_samples.get_mut(1).unwrap() = processed_sample_r;
-
-            // calculations that are only displayed on the GUI while the GUI is open
-            if self.params.editor_state.is_open() {
-                // Input gain meter
-                in_amplitude = (in_amplitude / num_samples as f32).abs();
-                let current_in_meter = self.in_meter.load(std::sync::atomic::Ordering::Relaxed);
-                let new_in_meter = if in_amplitude > current_in_meter {
-                    in_amplitude
-                } else {
-                    current_in_meter * self.out_meter_decay_weight
-                        + in_amplitude * (1.0 - self.out_meter_decay_weight)
-                };
-                self.in_meter
-                    .store(new_in_meter, std::sync::atomic::Ordering::Relaxed);
-
-                // Output gain meter
-                out_amplitude = (out_amplitude / num_samples as f32).abs();
-                let current_out_meter = self.out_meter.load(std::sync::atomic::Ordering::Relaxed);
-                let new_out_meter = if out_amplitude > current_out_meter {
-                    out_amplitude
-                } else {
-                    current_out_meter * self.out_meter_decay_weight
-                        + out_amplitude * (1.0 - self.out_meter_decay_weight)
-                };
-                self.out_meter
-                    .store(new_out_meter, std::sync::atomic::Ordering::Relaxed);
+            if num_channels > 1 {
+                *channel_samples.get_mut(1).unwrap() = processed_sample_r;
+            }
+            block_clipped |= processed_sample_l.abs() > 1.0 || processed_sample_r.abs() > 1.0;
+
+            // Each channel meters its own peak instead of a summed L+R that cancelled or doubled
+            for (channel, (input, output)) in input_levels
+                .into_iter()
+                .zip([processed_sample_l, processed_sample_r])
+                .enumerate()
+                .take(num_channels)
+            {
+                in_peaks.add(channel, input);
+                in_sub_peaks.add(channel, input);
+                out_sub_peaks.add(channel, output);
+            }
+
+            // Meters move every few ms inside the block so huge host buffers still animate
+            self.meter_clock += 1;
+            sub_block_len += 1;
+            if sub_block_len == self.meter_sub_block || sample_id + 1 == block_len {
+                if meters_open {
+                    self.in_meter
+                        .push(&in_sub_peaks, meter_decay, sub_block_len, self.meter_clock);
+                    self.out_meter
+                        .push(&out_sub_peaks, meter_decay, sub_block_len, self.meter_clock);
+                }
+                in_sub_peaks = channel_meters::BlockPeaks::default();
+                out_sub_peaks = channel_meters::BlockPeaks::default();
+                sub_block_len = 0;
+            }
+        }
+
+        // Standby needs this even with the editor closed
+        self.presence.update(
+            &in_peaks,
+            self.in_meter.channels(),
+            block_len,
+            self.params.auto_standby.value(),
+            &self.signal_presence,
+        );
+
+        if meters_open {
+            self.auto_gain_trim
+                .store(self.auto_gain.trim(), std::sync::atomic::Ordering::Relaxed);
+            self.in_meter.set_block_len(block_len);
+            self.out_meter.set_block_len(block_len);
+        }
+
+        // Hand the energy per tap of this block to the heat map
+        let left_energy = self.left_console.take_tap_energy();
+        let right_energy = self.right_console.take_tap_energy();
+        let left_energy_b = self.left_console_b.take_tap_energy();
+        let right_energy_b = self.right_console_b.take_tap_energy();
+        let left_energy_bands = self.left_multiband.take_tap_energy();
+        let right_energy_bands = self.right_multiband.take_tap_energy();
+        let editor_open = self.params.editor_state.is_open();
+        if self.editor_was_open && !editor_open {
+            context.execute_background(Task::ReleaseVisuals);
+        }
+        self.editor_was_open = editor_open;
+        if editor_open {
+            for (tap, energy) in self.tap_energy.iter().enumerate() {
+                energy.store(
+                    left_energy[tap]
+                        + right_energy[tap]
+                        + left_energy_b[tap]
+                        + right_energy_b[tap]
+                        + left_energy_bands[tap]
+                        + right_energy_bands[tap],
+                    std::sync::atomic::Ordering::Relaxed,
+                );
+            }
+            self.mod_scope_value
+                .store(last_mod_value, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        // Once per block is plenty for followers, sent on the last sample so it's the newest
+        if self.params.mod_output.value() {
+            let base_cc = self.params.mod_output_cc.value() as u8;
+            if let Some(value) = self
+                .mod_output
+                .changed(mod_output::RANDOM_MOD, 0.5 + 0.5 * last_mod_value)
+            {
+                context.send_event(NoteEvent::MidiCC {
+                    timing: block_len.saturating_sub(1) as u32,
+                    channel: 0,
+                    cc: base_cc + mod_output::RANDOM_MOD as u8,
+                    value,
+                });
+            }
+            if let Some(value) = self.mod_output.changed(mod_output::ENVELOPE, last_envelope) {
+                context.send_event(NoteEvent::MidiCC {
+                    timing: block_len.saturating_sub(1) as u32,
+                    channel: 0,
+                    cc: base_cc + mod_output::ENVELOPE as u8,
+                    value,
+                });
+            }
+            if let Some(value) = self
+                .mod_output
+                .changed(mod_output::LFO, 0.5 + 0.5 * last_lfo_value)
+            {
+                context.send_event(NoteEvent::MidiCC {
+                    timing: block_len.saturating_sub(1) as u32,
+                    channel: 0,
+                    cc: base_cc + mod_output::LFO as u8,
+                    value,
+                });
             }
         }
 
+        // Alerts go out at the end of the block too, switching off releases anything still held
+        if self.params.alert_output.value() {
+            let overload = self
+                .safety_engaged
+                .load(std::sync::atomic::Ordering::Relaxed);
+            self.alert_output
+                .update(alert_output::CLIP, block_clipped, block_len);
+            self.alert_output
+                .update(alert_output::OVERLOAD, overload, block_len);
+        } else {
+            self.alert_output.reset();
+        }
+        let alert_changes = self.alert_output.changes(
+            self.params.alert_message.value(),
+            self.params.alert_number.value() as u8,
+        );
+        for (message, number, on) in alert_changes.into_iter().flatten() {
+            let timing = block_len.saturating_sub(1) as u32;
+            context.send_event(match (message, on) {
+                (alert_output::AlertMessage::Note, true) => NoteEvent::NoteOn {
+                    timing,
+                    voice_id: None,
+                    channel: 0,
+                    note: number,
+                    velocity: 1.0,
+                },
+                (alert_output::AlertMessage::Note, false) => NoteEvent::NoteOff {
+                    timing,
+                    voice_id: None,
+                    channel: 0,
+                    note: number,
+                    velocity: 0.0,
+                },
+                (alert_output::AlertMessage::Cc, on) => NoteEvent::MidiCC {
+                    timing,
+                    channel: 0,
+                    cc: number,
+                    value: if on { 1.0 } else { 0.0 },
+                },
+            });
+        }
+
         ProcessStatus::Normal
     }
 
-    const MIDI_INPUT: MidiConfig = MidiConfig::None;
+    const MIDI_INPUT: MidiConfig = MidiConfig::Basic;
 
-    const MIDI_OUTPUT: MidiConfig = MidiConfig::None;
+    const MIDI_OUTPUT: MidiConfig = MidiConfig::MidiCCs;
 
     const HARD_REALTIME_ONLY: bool = false;
 
     fn task_executor(&mut self) -> TaskExecutor<Self> {
-        // In the default implementation we can simply ignore the value
-        Box::new(|_| ())
+        // Diagnostic events get logged here so the audio thread never formats strings
+        let render_status = self.render_status.clone();
+        let viz_buffers = self.viz_buffers.clone();
+        Box::new(move |task| match task {
+            Task::Diagnostic(event, param_context) => {
+                diagnostics::log_event(&event, &param_context);
+            }
+            Task::RenderClip(job) => {
+                let status = match offline::run_render_job(&job) {
+                    Ok(message) => message,
+                    Err(message) => format!("Render failed: {message}"),
+                };
+                *render_status.write().unwrap() = status;
+            }
+            Task::ReleaseVisuals => viz_buffers.release(),
+        })
     }
 
     fn filter_state(_state: &mut PluginState) {}
 
-    fn reset(&mut self) {}
+    fn reset(&mut self) {
+        self.alert_output.reset();
+        self.left_console.reset();
+        self.right_console.reset();
+        self.note_gate.reset();
+        self.diagnostics.reset();
+        self.reseed(self.params.seed.load(std::sync::atomic::Ordering::Relaxed));
+        self.lfo.reset();
+        self.mod_output.reset();
+        self.left_lofi.reset();
+        self.right_lofi.reset();
+        self.left_console_b.reset();
+        self.right_console_b.reset();
+        self.left_multiband.reset();
+        self.right_multiband.reset();
+        self.left_oversampler.reset();
+        self.right_oversampler.reset();
+        self.auto_mult.reset();
+        self.envelope.reset();
+        self.auto_gain.reset();
+        self.gain_match_meter.cancel(&self.gain_match);
+        self.left_adaa_aligner.reset();
+        self.right_adaa_aligner.reset();
+        self.left_hpf.reset();
+        self.right_hpf.reset();
+        self.left_lpf.reset();
+        self.right_lpf.reset();
+        self.left_tilt.reset();
+        self.right_tilt.reset();
+        self.left_wow_flutter.reset();
+        self.right_wow_flutter.reset();
+        self.left_wow_dry.reset();
+        self.right_wow_dry.reset();
+        self.left_tape.reset();
+        self.right_tape.reset();
+        self.crosstalk.reset();
+        self.left_transformer.reset();
+        self.right_transformer.reset();
+        self.left_dc_blocker.reset();
+        self.right_dc_blocker.reset();
+        self.null_test.reset();
+        self.presence.reset();
+        self.true_peak.reset();
+    }
 
-    fn deactivate(&mut self) {}
+    fn deactivate(&mut self) {
+        self.alert_output.reset();
+        self.last_console_params = None;
+        self.scene_fade_from = None;
+        if self.crash_guard_active {
+            self.crash_guard_active = false;
+            crash_guard::deactivate();
+        }
+    }
 }
 
 impl ClapPlugin for GladeDesk {
@@ -889,3 +6500,56 @@ impl Vst3Plugin for GladeDesk {
 
 nih_export_clap!(GladeDesk);
 nih_export_vst3!(GladeDesk);
+
+/// Offline batch processing for the command line build, renders with the default settings
+/// plus an optional preset file
+pub fn run_cli(args: &[String]) -> Result<String, String> {
+    offline::run_cli(GladeDeskParams::default().offline_settings(), args)
+}
+
+pub use channel_meters::{meter_level, BlockPeaks, ChannelMeters, MeterClock};
+pub use engine::{ConsoleParams, MixLaw, PushCurve};
+pub use offline::TestSignal;
+
+/// Deterministic render of a known signal through the default settings with a `.gladepreset`
+/// applied on top, one Vec per channel. The golden tests sit on this.
+pub fn render_test_signal(
+    preset: &str,
+    signal: TestSignal,
+    sample_rate: u32,
+    channels: usize,
+    length: usize,
+) -> Result<Vec<Vec<f32>>, String> {
+    let settings = offline::apply_preset(GladeDeskParams::default().offline_settings(), preset)?;
+    Ok(offline::render_signal(
+        &settings,
+        signal,
+        sample_rate,
+        channels,
+        length,
+    ))
+}
+
+/// Console settings of a fresh instance, a starting point for tests to change
+pub fn default_console_params() -> ConsoleParams {
+    GladeDeskParams::default().console_params()
+}
+
+/// Run `input` through a fresh console channel with `params_at(i)` giving the settings and the
+/// tap spacing in samples for sample i, the way automation would move them. Returns the full
+/// chain output (dry + taps * wet after the gains).
+pub fn render_trajectory(
+    input: &[f32],
+    mut params_at: impl FnMut(usize) -> (ConsoleParams, f32),
+) -> Vec<f32> {
+    let mut console = engine::ConsoleChannel::new();
+    input
+        .iter()
+        .enumerate()
+        .map(|(i, sample)| {
+            let (params, spacing) = params_at(i);
+            console.set_spacing(spacing);
+            console.process_sample(*sample, &params)
+        })
+        .collect()
+}
@@ -0,0 +1,107 @@
+// lofi.rs - Ardura 2024
+// Creative lo-fi section: runs the tap network at a reduced internal rate on purpose and
+// bit crushes its output. No anti-aliasing anywhere, the grit is the point.
+
+use nih_plug::prelude::Enum;
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LofiRatio {
+    #[name = "Off"]
+    Off,
+    #[name = "1/2"]
+    Half,
+    #[name = "1/4"]
+    Quarter,
+    #[name = "1/8"]
+    Eighth,
+    #[name = "1/16"]
+    Sixteenth,
+}
+
+impl LofiRatio {
+    /// How many host samples each internal sample lasts
+    pub fn factor(self) -> u32 {
+        match self {
+            LofiRatio::Off => 1,
+            LofiRatio::Half => 2,
+            LofiRatio::Quarter => 4,
+            LofiRatio::Eighth => 8,
+            LofiRatio::Sixteenth => 16,
+        }
+    }
+}
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LofiInterp {
+    /// Zero order hold, steppy and bright
+    #[name = "Hold"]
+    Hold,
+    /// Straight line between internal samples, one internal sample late
+    #[name = "Linear"]
+    Linear,
+}
+
+/// Runs a stage every `factor` samples and fills the gaps in between
+pub struct RateReducer {
+    counter: u32,
+    previous: f32,
+    current: f32,
+}
+
+impl RateReducer {
+    pub fn new() -> Self {
+        Self {
+            counter: 0,
+            previous: 0.0,
+            current: 0.0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Runs `stage` (the wet path) every `ratio` samples and holds/interpolates in between.
+    /// The dry signal stays at the host rate.
+    pub fn process(
+        &mut self,
+        ratio: LofiRatio,
+        interp: LofiInterp,
+        input: f32,
+        stage: impl FnOnce(f32) -> f32,
+    ) -> f32 {
+        let factor = ratio.factor();
+        if factor <= 1 {
+            self.counter = 0;
+            let wet = stage(input);
+            self.previous = wet;
+            self.current = wet;
+            return wet;
+        }
+
+        if self.counter >= factor {
+            self.counter = 0;
+        }
+        if self.counter == 0 {
+            self.previous = self.current;
+            self.current = stage(input);
+        }
+
+        let wet = match interp {
+            LofiInterp::Hold => self.current,
+            LofiInterp::Linear => {
+                let t = self.counter as f32 / factor as f32;
+                self.previous + (self.current - self.previous) * t
+            }
+        };
+        self.counter += 1;
+        wet
+    }
+}
+
+/// Quantize a sample to `bits` of resolution over -1 to 1. Fractional bits give the in between
+/// step sizes so the bits control can sweep smoothly.
+pub fn crush(sample: f32, bits: f32) -> f32 {
+    let steps = 2.0f32.powf(bits - 1.0);
+    (sample * steps).round() / steps
+}
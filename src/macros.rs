@@ -0,0 +1,158 @@
+// macros.rs - Ardura 2024
+// Four macro knobs, each mapped onto several console controls with its own min/max range and
+// curve so one automatable knob can sweep a whole coefficient shape. Saved with the plugin state
+// and mirrored into atomics for the audio thread like the mod matrix.
+
+use atomic_float::AtomicF32;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::mod_matrix::ModDestination;
+
+pub const MACROS: usize = 4;
+
+/// Targets one macro can hold
+pub const MAX_TARGETS: usize = 8;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MacroCurve {
+    Linear,
+    /// Slow start, most of the sweep happens near the top of the knob
+    Exponential,
+    /// Eases in and out at both ends
+    SCurve,
+}
+
+impl MacroCurve {
+    pub const ALL: [MacroCurve; 3] = [
+        MacroCurve::Linear,
+        MacroCurve::Exponential,
+        MacroCurve::SCurve,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MacroCurve::Linear => "Linear",
+            MacroCurve::Exponential => "Exponential",
+            MacroCurve::SCurve => "S-Curve",
+        }
+    }
+
+    fn index(self) -> u32 {
+        match self {
+            MacroCurve::Linear => 0,
+            MacroCurve::Exponential => 1,
+            MacroCurve::SCurve => 2,
+        }
+    }
+
+    /// Shape a 0-1 knob position
+    pub fn apply(self, x: f32) -> f32 {
+        let x = x.clamp(0.0, 1.0);
+        match self {
+            MacroCurve::Linear => x,
+            MacroCurve::Exponential => x * x,
+            MacroCurve::SCurve => x * x * (3.0 - 2.0 * x),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MacroTarget {
+    pub destination: ModDestination,
+    /// Normalized position of the destination with the macro at 0 and at 1, min above max inverts
+    pub min: f32,
+    pub max: f32,
+    pub curve: MacroCurve,
+}
+
+impl MacroTarget {
+    /// Normalized destination position for a 0-1 macro value
+    pub fn position(&self, value: f32) -> f32 {
+        let shaped = self.curve.apply(value);
+        (self.min + (self.max - self.min) * shaped).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MacroMap {
+    pub targets: [Vec<MacroTarget>; MACROS],
+}
+
+impl MacroMap {
+    pub fn is_empty(&self) -> bool {
+        self.targets.iter().all(Vec::is_empty)
+    }
+}
+
+pub type MacroTargets = [[Option<MacroTarget>; MAX_TARGETS]; MACROS];
+
+/// Audio thread copy of the mappings, a zero slot is empty
+pub struct SharedMacros {
+    slots: [AtomicU32; MACROS * MAX_TARGETS],
+    mins: [AtomicF32; MACROS * MAX_TARGETS],
+    maxs: [AtomicF32; MACROS * MAX_TARGETS],
+}
+
+impl SharedMacros {
+    pub fn new() -> Self {
+        Self {
+            slots: std::array::from_fn(|_| AtomicU32::new(0)),
+            mins: std::array::from_fn(|_| AtomicF32::new(0.0)),
+            maxs: std::array::from_fn(|_| AtomicF32::new(0.0)),
+        }
+    }
+
+    pub fn store(&self, map: &MacroMap) {
+        for (knob, targets) in map.targets.iter().enumerate() {
+            for target_slot in 0..MAX_TARGETS {
+                let slot = knob * MAX_TARGETS + target_slot;
+                match targets.get(target_slot) {
+                    Some(target) => {
+                        self.mins[slot].store(target.min, Ordering::Relaxed);
+                        self.maxs[slot].store(target.max, Ordering::Relaxed);
+                        // Destination in the low 16 bits counting from 1, curve above it
+                        self.slots[slot].store(
+                            (target.destination.index() as u32 + 1) | (target.curve.index() << 16),
+                            Ordering::Relaxed,
+                        );
+                    }
+                    None => self.slots[slot].store(0, Ordering::Relaxed),
+                }
+            }
+        }
+    }
+
+    /// Mappings for this block
+    pub fn load(&self) -> MacroTargets {
+        std::array::from_fn(|knob| {
+            std::array::from_fn(|target_slot| {
+                let slot = knob * MAX_TARGETS + target_slot;
+                let packed = self.slots[slot].load(Ordering::Relaxed);
+                let destination = ((packed & 0xFFFF) as usize).checked_sub(1)?;
+                Some(MacroTarget {
+                    destination: ModDestination::from_index(destination)?,
+                    min: self.mins[slot].load(Ordering::Relaxed),
+                    max: self.maxs[slot].load(Ordering::Relaxed),
+                    curve: *MacroCurve::ALL.get((packed >> 16) as usize)?,
+                })
+            })
+        })
+    }
+}
+
+/// Normalized position per destination, indexed like `ModDestination::index`, None where no
+/// macro is mapped. When two macros share a destination the later knob wins
+pub fn positions(
+    targets: &MacroTargets,
+    values: &[f32; MACROS],
+) -> [Option<f32>; ModDestination::COUNT] {
+    let mut positions = [None; ModDestination::COUNT];
+    for (knob_targets, value) in targets.iter().zip(values) {
+        for target in knob_targets.iter().flatten() {
+            positions[target.destination.index()] = Some(target.position(*value));
+        }
+    }
+    positions
+}
@@ -0,0 +1,227 @@
+// mod_matrix.rs - Ardura 2024
+// Modulation matrix: up to eight routes from the modulation sources onto the console controls,
+// each with its own depth. Saved with the plugin state and mirrored into atomics for the audio
+// thread.
+
+use atomic_float::AtomicF32;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::engine;
+
+pub const MAX_ROUTES: usize = 8;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ModSource {
+    /// The random mod before its own depth, -1 to 1
+    Random,
+    /// The LFO before its own depth, -1 to 1
+    Lfo,
+    /// The input envelope, 0 to 1
+    Envelope,
+    /// The macro knobs, 0 to 1
+    Macro1,
+    Macro2,
+    Macro3,
+    Macro4,
+}
+
+impl ModSource {
+    pub const COUNT: usize = 7;
+    pub const ALL: [ModSource; Self::COUNT] = [
+        ModSource::Random,
+        ModSource::Lfo,
+        ModSource::Envelope,
+        ModSource::Macro1,
+        ModSource::Macro2,
+        ModSource::Macro3,
+        ModSource::Macro4,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ModSource::Random => "Random",
+            ModSource::Lfo => "LFO",
+            ModSource::Envelope => "Envelope",
+            ModSource::Macro1 => "Macro 1",
+            ModSource::Macro2 => "Macro 2",
+            ModSource::Macro3 => "Macro 3",
+            ModSource::Macro4 => "Macro 4",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        match self {
+            ModSource::Random => 0,
+            ModSource::Lfo => 1,
+            ModSource::Envelope => 2,
+            ModSource::Macro1 => 3,
+            ModSource::Macro2 => 4,
+            ModSource::Macro3 => 5,
+            ModSource::Macro4 => 6,
+        }
+    }
+}
+
+/// Console controls a route can move, coefficients and skews by slider
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ModDestination {
+    Push,
+    Multiplier,
+    DryWet,
+    Bias,
+    EvenOdd,
+    Feedback,
+    FeedbackDamping,
+    TapDamping,
+    Coeff(usize),
+    Skew(usize),
+}
+
+// Everything before the per slider destinations
+const FIXED_DESTINATIONS: usize = 8;
+
+impl ModDestination {
+    pub const COUNT: usize = FIXED_DESTINATIONS + 2 * engine::TAPS;
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        let destination = match index {
+            0 => ModDestination::Push,
+            1 => ModDestination::Multiplier,
+            2 => ModDestination::DryWet,
+            3 => ModDestination::Bias,
+            4 => ModDestination::EvenOdd,
+            5 => ModDestination::Feedback,
+            6 => ModDestination::FeedbackDamping,
+            7 => ModDestination::TapDamping,
+            _ if index < FIXED_DESTINATIONS + engine::TAPS => {
+                ModDestination::Coeff(index - FIXED_DESTINATIONS)
+            }
+            _ if index < Self::COUNT => {
+                ModDestination::Skew(index - FIXED_DESTINATIONS - engine::TAPS)
+            }
+            _ => return None,
+        };
+        Some(destination)
+    }
+
+    pub fn index(self) -> usize {
+        match self {
+            ModDestination::Push => 0,
+            ModDestination::Multiplier => 1,
+            ModDestination::DryWet => 2,
+            ModDestination::Bias => 3,
+            ModDestination::EvenOdd => 4,
+            ModDestination::Feedback => 5,
+            ModDestination::FeedbackDamping => 6,
+            ModDestination::TapDamping => 7,
+            ModDestination::Coeff(tap) => FIXED_DESTINATIONS + tap.min(engine::TAPS - 1),
+            ModDestination::Skew(tap) => {
+                FIXED_DESTINATIONS + engine::TAPS + tap.min(engine::TAPS - 1)
+            }
+        }
+    }
+
+    pub fn name(self) -> String {
+        match self {
+            ModDestination::Push => String::from("Push"),
+            ModDestination::Multiplier => String::from("Multiplier"),
+            ModDestination::DryWet => String::from("Dry/Wet"),
+            ModDestination::Bias => String::from("Bias"),
+            ModDestination::EvenOdd => String::from("Even/Odd"),
+            ModDestination::Feedback => String::from("Feedback"),
+            ModDestination::FeedbackDamping => String::from("Feedback Damping"),
+            ModDestination::TapDamping => String::from("Tap Damping"),
+            ModDestination::Coeff(tap) => format!("Coeff {}", tap + 1),
+            ModDestination::Skew(tap) => format!("Skew {}", tap + 1),
+        }
+    }
+
+    /// The destination's value inside a console snapshot
+    pub fn value_mut(self, console: &mut engine::ConsoleParams) -> &mut f32 {
+        match self {
+            ModDestination::Push => &mut console.push,
+            ModDestination::Multiplier => &mut console.multiplier,
+            ModDestination::DryWet => &mut console.wet,
+            ModDestination::Bias => &mut console.bias,
+            ModDestination::EvenOdd => &mut console.even_odd,
+            ModDestination::Feedback => &mut console.feedback,
+            ModDestination::FeedbackDamping => &mut console.feedback_damping,
+            ModDestination::TapDamping => &mut console.tap_damping,
+            ModDestination::Coeff(tap) => &mut console.coeffs[tap.min(engine::TAPS - 1)],
+            ModDestination::Skew(tap) => &mut console.skews[tap.min(engine::TAPS - 1)],
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModRoute {
+    pub source: ModSource,
+    pub destination: ModDestination,
+    /// Share of the destination's range a full scale source moves it, negative inverts
+    pub depth: f32,
+}
+
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModMatrix {
+    pub routes: Vec<ModRoute>,
+}
+
+/// Audio thread copy of the routes, a zero slot is empty
+pub struct SharedMatrix {
+    routes: [AtomicU32; MAX_ROUTES],
+    depths: [AtomicF32; MAX_ROUTES],
+}
+
+impl SharedMatrix {
+    pub fn new() -> Self {
+        Self {
+            routes: std::array::from_fn(|_| AtomicU32::new(0)),
+            depths: std::array::from_fn(|_| AtomicF32::new(0.0)),
+        }
+    }
+
+    pub fn store(&self, matrix: &ModMatrix) {
+        for (slot, (route, depth)) in self.routes.iter().zip(self.depths.iter()).enumerate() {
+            match matrix.routes.get(slot) {
+                Some(entry) => {
+                    depth.store(entry.depth, Ordering::Relaxed);
+                    // Source in the low byte counting from 1, destination above it
+                    route.store(
+                        (entry.source.index() as u32 + 1)
+                            | ((entry.destination.index() as u32) << 8),
+                        Ordering::Relaxed,
+                    );
+                }
+                None => route.store(0, Ordering::Relaxed),
+            }
+        }
+    }
+
+    /// Routes for this block
+    pub fn load(&self) -> [Option<ModRoute>; MAX_ROUTES] {
+        std::array::from_fn(|slot| {
+            let packed = self.routes[slot].load(Ordering::Relaxed);
+            let source = ModSource::ALL.get(((packed & 0xFF) as usize).checked_sub(1)?)?;
+            Some(ModRoute {
+                source: *source,
+                destination: ModDestination::from_index((packed >> 8) as usize)?,
+                depth: self.depths[slot].load(Ordering::Relaxed),
+            })
+        })
+    }
+}
+
+/// Normalized offset per destination, indexed like `ModDestination::index`, from this
+/// sample's source values indexed like `ModSource::index`
+pub fn offsets(
+    routes: &[Option<ModRoute>; MAX_ROUTES],
+    sources: &[f32; ModSource::COUNT],
+) -> [f32; ModDestination::COUNT] {
+    let mut offsets = [0.0; ModDestination::COUNT];
+    for route in routes.iter().flatten() {
+        offsets[route.destination.index()] += sources[route.source.index()] * route.depth;
+    }
+    offsets
+}
@@ -0,0 +1,44 @@
+// mod_output.rs - Ardura 2024
+// Control rate modulation out to the host: nih_plug has no output parameters, so the sources go
+// out as MIDI CCs once per block for other plugins and devices to follow
+
+/// Modulation sources that can be sent, each on its own CC counting up from the base one
+pub const SOURCES: usize = 3;
+
+/// Slot of the random mod in `ModOutput`
+pub const RANDOM_MOD: usize = 0;
+
+/// Slot of the input envelope follower
+pub const ENVELOPE: usize = 1;
+
+/// Slot of the tempo syncable LFO
+pub const LFO: usize = 2;
+
+pub struct ModOutput {
+    // Last 7 bit value sent per source, None sends on the next block whatever it is
+    last_sent: [Option<u8>; SOURCES],
+}
+
+impl ModOutput {
+    pub fn new() -> Self {
+        Self {
+            last_sent: [None; SOURCES],
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.last_sent = [None; SOURCES];
+    }
+
+    /// The 0-1 value to send for a source, None when it didn't move a whole CC step since the
+    /// last one so quiet blocks don't flood the host
+    pub fn changed(&mut self, source: usize, value: f32) -> Option<f32> {
+        let value = value.clamp(0.0, 1.0);
+        let step = (value * 127.0).round() as u8;
+        if self.last_sent[source] == Some(step) {
+            return None;
+        }
+        self.last_sent[source] = Some(step);
+        Some(value)
+    }
+}
@@ -0,0 +1,93 @@
+// mod_scope.rs - Ardura 2024
+// Scrolling plot of a modulation source in the -1 to 1 range, newest value on the right
+
+use std::collections::VecDeque;
+
+use nih_plug_egui::egui::{
+    vec2, Color32, Pos2, Response, Rounding, Sense, Shape, Stroke, Ui, Vec2, Widget,
+};
+
+/// How many points of history the scope keeps
+pub const HISTORY_LENGTH: usize = 240;
+
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct ModScope<'a> {
+    history: &'a VecDeque<f32>,
+    desired_size: Vec2,
+    line_color: Color32,
+    background_color: Color32,
+    line_width: f32,
+}
+
+impl<'a> ModScope<'a> {
+    pub fn new(history: &'a VecDeque<f32>) -> Self {
+        Self {
+            history,
+            desired_size: vec2(256.0, 64.0),
+            line_color: Color32::YELLOW,
+            background_color: Color32::BLACK,
+            line_width: 1.5,
+        }
+    }
+
+    pub fn desired_size(mut self, desired_size: Vec2) -> Self {
+        self.desired_size = desired_size;
+        self
+    }
+
+    pub fn line_color(mut self, color: Color32) -> Self {
+        self.line_color = color;
+        self
+    }
+
+    pub fn background_color(mut self, color: Color32) -> Self {
+        self.background_color = color;
+        self
+    }
+
+    pub fn line_width(mut self, width: f32) -> Self {
+        self.line_width = width;
+        self
+    }
+}
+
+impl<'a> Widget for ModScope<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let (rect, response) = ui.allocate_exact_size(self.desired_size, Sense::hover());
+
+        if ui.is_rect_visible(rect) {
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, Rounding::from(2.0), self.background_color);
+
+            // Zero line
+            painter.line_segment(
+                [rect.left_center(), rect.right_center()],
+                Stroke::new(1.0, self.line_color.linear_multiply(0.3)),
+            );
+
+            let step = rect.width() / (HISTORY_LENGTH - 1) as f32;
+            let points: Vec<Pos2> = self
+                .history
+                .iter()
+                .rev()
+                .enumerate()
+                .map(|(age, value)| {
+                    Pos2::new(
+                        rect.right() - age as f32 * step,
+                        rect.center().y - value.clamp(-1.0, 1.0) * (rect.height() / 2.0 - 2.0),
+                    )
+                })
+                .collect();
+            if points.len() > 1 {
+                painter.add(Shape::line(
+                    points,
+                    Stroke::new(self.line_width, self.line_color),
+                ));
+            }
+
+            painter.rect_stroke(rect, Rounding::from(2.0), Stroke::new(1.0, Color32::BLACK));
+        }
+
+        response
+    }
+}
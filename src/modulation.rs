@@ -0,0 +1,184 @@
+// modulation.rs - Ardura 2024
+// Modulation sources that can be pointed at the console controls
+
+use nih_plug::prelude::Enum;
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RandomMode {
+    #[name = "S&H"]
+    SampleHold,
+    #[name = "Smooth"]
+    SmoothRandom,
+}
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModTarget {
+    #[name = "Push"]
+    Push,
+    #[name = "Multiplier"]
+    Multiplier,
+}
+
+/// Sample & hold / smoothed random source putting out -1 to 1
+pub struct RandomSource {
+    rng_state: u32,
+    phase: f32,
+    previous: f32,
+    target: f32,
+    output: f32,
+}
+
+impl RandomSource {
+    pub fn new(seed: u32) -> Self {
+        let mut source = Self {
+            rng_state: seed.max(1),
+            phase: 0.0,
+            previous: 0.0,
+            target: 0.0,
+            output: 0.0,
+        };
+        source.target = source.next_random();
+        source
+    }
+
+    // xorshift32, plenty for modulation and allocation free
+    fn next_random(&mut self) -> f32 {
+        let mut x = self.rng_state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.rng_state = x;
+        (x as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+
+    /// Advance one sample. `smoothness` 0-1 glides the output over that fraction of a step.
+    pub fn next(&mut self, mode: RandomMode, rate_hz: f32, smoothness: f32, sample_rate: f32) -> f32 {
+        self.phase += rate_hz / sample_rate;
+        if self.phase >= 1.0 {
+            self.phase -= self.phase.floor();
+            self.previous = self.target;
+            self.target = self.next_random();
+        }
+
+        let raw = match mode {
+            RandomMode::SampleHold => self.target,
+            // Cosine interpolation between the random points
+            RandomMode::SmoothRandom => {
+                let t = 0.5 - 0.5 * (self.phase * std::f32::consts::PI).cos();
+                self.previous + (self.target - self.previous) * t
+            }
+        };
+
+        // One pole glide with a time constant of `smoothness` steps
+        let glide_samples = smoothness * sample_rate / rate_hz.max(0.001);
+        if glide_samples <= 1.0 {
+            self.output = raw;
+        } else {
+            self.output += (raw - self.output) / glide_samples;
+        }
+        self.output
+    }
+
+    pub fn reset(&mut self, seed: u32) {
+        *self = Self::new(seed);
+    }
+}
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LfoShape {
+    #[name = "Sine"]
+    Sine,
+    #[name = "Triangle"]
+    Triangle,
+    #[name = "Saw"]
+    Saw,
+    #[name = "Square"]
+    Square,
+}
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LfoSync {
+    /// Runs at the Rate param in Hz
+    #[name = "Free"]
+    Free,
+    #[name = "4 Bars"]
+    FourBars,
+    #[name = "2 Bars"]
+    TwoBars,
+    #[name = "1 Bar"]
+    OneBar,
+    #[name = "1/2"]
+    Half,
+    #[name = "1/4"]
+    Quarter,
+    #[name = "1/8"]
+    Eighth,
+    #[name = "1/16"]
+    Sixteenth,
+}
+
+impl LfoSync {
+    /// Length of one cycle in quarter note beats, None when free running. Bars are 4/4.
+    pub fn beats(self) -> Option<f64> {
+        match self {
+            LfoSync::Free => None,
+            LfoSync::FourBars => Some(16.0),
+            LfoSync::TwoBars => Some(8.0),
+            LfoSync::OneBar => Some(4.0),
+            LfoSync::Half => Some(2.0),
+            LfoSync::Quarter => Some(1.0),
+            LfoSync::Eighth => Some(0.5),
+            LfoSync::Sixteenth => Some(0.25),
+        }
+    }
+}
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LfoTarget {
+    #[name = "Push"]
+    Push,
+    #[name = "Multiplier"]
+    Multiplier,
+    /// Scales every skew term of both banks
+    #[name = "Skew"]
+    Skew,
+}
+
+/// Periodic source putting out -1 to 1, free running or locked to the host's beat position
+pub struct Lfo {
+    phase: f64,
+}
+
+impl Lfo {
+    pub fn new() -> Self {
+        Self { phase: 0.0 }
+    }
+
+    pub fn reset(&mut self) {
+        self.phase = 0.0;
+    }
+
+    /// Line the phase up with the host at the start of a block, so synced cycles start on the
+    /// beat wherever playback starts
+    pub fn sync_to(&mut self, pos_beats: f64, beats_per_cycle: f64) {
+        self.phase = (pos_beats / beats_per_cycle).rem_euclid(1.0);
+    }
+
+    /// Advance one sample by `increment` cycles
+    pub fn next(&mut self, shape: LfoShape, increment: f64) -> f32 {
+        let phase = self.phase as f32;
+        self.phase = (self.phase + increment).fract();
+        match shape {
+            LfoShape::Sine => (phase * std::f32::consts::TAU).sin(),
+            LfoShape::Triangle => 1.0 - 4.0 * (phase - 0.5).abs(),
+            LfoShape::Saw => 2.0 * phase - 1.0,
+            LfoShape::Square => {
+                if phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+        }
+    }
+}
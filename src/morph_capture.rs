@@ -0,0 +1,145 @@
+// morph_capture.rs - Ardura 2024
+// Records a manual performance of the coefficient/skew sliders into breakpoints on the GUI
+// thread so it can be replayed through the ParamSetter (letting the host write all 16 lanes at
+// once) or exported as a start/end pair
+
+/// Coefficients followed by skews
+pub const MORPH_VALUES: usize = 16;
+
+#[derive(Clone, Copy)]
+struct Breakpoint {
+    time: f64,
+    values: [f32; MORPH_VALUES],
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CaptureState {
+    Idle,
+    Recording { start: f64 },
+    Replaying { start: f64 },
+}
+
+pub struct MorphCapture {
+    breakpoints: Vec<Breakpoint>,
+    state: CaptureState,
+}
+
+impl Default for MorphCapture {
+    fn default() -> Self {
+        Self {
+            breakpoints: Vec::new(),
+            state: CaptureState::Idle,
+        }
+    }
+}
+
+impl MorphCapture {
+    pub fn is_recording(&self) -> bool {
+        matches!(self.state, CaptureState::Recording { .. })
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        matches!(self.state, CaptureState::Replaying { .. })
+    }
+
+    pub fn len(&self) -> usize {
+        self.breakpoints.len()
+    }
+
+    /// Length of the capture in seconds
+    pub fn duration(&self) -> f64 {
+        self.breakpoints.last().map(|point| point.time).unwrap_or(0.0)
+    }
+
+    pub fn start_recording(&mut self, now: f64, values: [f32; MORPH_VALUES]) {
+        self.breakpoints.clear();
+        self.breakpoints.push(Breakpoint { time: 0.0, values });
+        self.state = CaptureState::Recording { start: now };
+    }
+
+    /// Adds a breakpoint whenever any slider moved since the last one
+    pub fn record_frame(&mut self, now: f64, values: [f32; MORPH_VALUES]) {
+        if let CaptureState::Recording { start } = self.state {
+            let changed = self
+                .breakpoints
+                .last()
+                .map(|point| point.values != values)
+                .unwrap_or(true);
+            if changed {
+                self.breakpoints.push(Breakpoint {
+                    time: now - start,
+                    values,
+                });
+            }
+        }
+    }
+
+    pub fn start_replay(&mut self, now: f64) -> bool {
+        if self.breakpoints.len() < 2 || self.is_recording() {
+            return false;
+        }
+        self.state = CaptureState::Replaying { start: now };
+        true
+    }
+
+    pub fn stop(&mut self) {
+        self.state = CaptureState::Idle;
+    }
+
+    /// Values for this frame of the replay, linearly interpolated between breakpoints.
+    /// Returns `None` once the replay has finished.
+    pub fn replay_frame(&mut self, now: f64) -> Option<[f32; MORPH_VALUES]> {
+        let CaptureState::Replaying { start } = self.state else {
+            return None;
+        };
+
+        let elapsed = now - start;
+        if elapsed >= self.duration() {
+            self.state = CaptureState::Idle;
+            return self.breakpoints.last().map(|point| point.values);
+        }
+
+        let next_index = self
+            .breakpoints
+            .iter()
+            .position(|point| point.time > elapsed)
+            .unwrap_or(self.breakpoints.len() - 1)
+            .max(1);
+        let previous = self.breakpoints[next_index - 1];
+        let next = self.breakpoints[next_index];
+        let span = (next.time - previous.time).max(f64::EPSILON);
+        let t = ((elapsed - previous.time) / span).clamp(0.0, 1.0) as f32;
+
+        let mut values = previous.values;
+        for (value, target) in values.iter_mut().zip(next.values.iter()) {
+            *value += (target - *value) * t;
+        }
+        Some(values)
+    }
+
+    /// The first and last states of the capture
+    pub fn morph_pair(&self) -> Option<([f32; MORPH_VALUES], [f32; MORPH_VALUES])> {
+        match (self.breakpoints.first(), self.breakpoints.last()) {
+            (Some(first), Some(last)) if self.breakpoints.len() > 1 => {
+                Some((first.values, last.values))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Plain text version of a morph pair for pasting into notes or bug reports
+pub fn morph_pair_to_string(a: &[f32; MORPH_VALUES], b: &[f32; MORPH_VALUES]) -> String {
+    let mut text = String::new();
+    for (label, values) in [("A", a), ("B", b)] {
+        text.push_str(&format!("[Morph {label}]\n"));
+        for (index, value) in values.iter().enumerate() {
+            let kind = if index < MORPH_VALUES / 2 { "Coeff" } else { "Skew" };
+            text.push_str(&format!(
+                "{}_{kind} = {value:.6}\n",
+                index % (MORPH_VALUES / 2) + 1
+            ));
+        }
+    }
+    text
+}
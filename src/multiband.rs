@@ -0,0 +1,161 @@
+// multiband.rs - Ardura 2024
+// Three band console: the input is split low/mid/high with Linkwitz-Riley crossovers and each
+// band runs its own tap network with its own drive and mix
+
+use crate::{
+    engine::{ConsoleChannel, ConsoleParams, TAPS},
+    filters::{Biquad, BUTTERWORTH_Q},
+};
+
+pub const BANDS: usize = 3;
+
+/// Snapshot of the multiband controls for the offline renderer
+#[derive(Clone, Copy)]
+pub struct MultibandSettings {
+    pub crossovers: (f32, f32),
+    pub drive: [f32; BANDS],
+    pub mix: [f32; BANDS],
+}
+
+pub struct MultibandConsole {
+    // Fourth order Linkwitz-Riley sections, two Butterworth biquads each
+    low_lowpass: [Biquad; 2],
+    low_highpass: [Biquad; 2],
+    high_lowpass: [Biquad; 2],
+    high_highpass: [Biquad; 2],
+    // Gives the low band the same phase shift the upper crossover puts on mid + high
+    low_allpass: Biquad,
+    bands: [ConsoleChannel; BANDS],
+    sample_rate: f32,
+    crossovers: (f32, f32),
+}
+
+impl MultibandConsole {
+    pub fn new() -> Self {
+        let mut console = Self {
+            low_lowpass: [Biquad::new(); 2],
+            low_highpass: [Biquad::new(); 2],
+            high_lowpass: [Biquad::new(); 2],
+            high_highpass: [Biquad::new(); 2],
+            low_allpass: Biquad::new(),
+            bands: [
+                ConsoleChannel::new(),
+                ConsoleChannel::new(),
+                ConsoleChannel::new(),
+            ],
+            sample_rate: 44100.0,
+            crossovers: (200.0, 3000.0),
+        };
+        console.design();
+        console
+    }
+
+    /// Rate the bands run at, so the host rate times the oversampling factor
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        if sample_rate != self.sample_rate {
+            self.sample_rate = sample_rate;
+            self.design();
+        }
+    }
+
+    /// Only redesigns the filters when a crossover actually moved
+    pub fn set_crossovers(&mut self, low_hz: f32, high_hz: f32) {
+        if (low_hz, high_hz) != self.crossovers {
+            self.crossovers = (low_hz, high_hz);
+            self.design();
+        }
+    }
+
+    pub fn set_spacing(&mut self, spacing: f32) {
+        for band in self.bands.iter_mut() {
+            band.set_spacing(spacing);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        for filter in self
+            .low_lowpass
+            .iter_mut()
+            .chain(self.low_highpass.iter_mut())
+            .chain(self.high_lowpass.iter_mut())
+            .chain(self.high_highpass.iter_mut())
+        {
+            filter.reset();
+        }
+        self.low_allpass.reset();
+        for band in self.bands.iter_mut() {
+            band.reset();
+        }
+    }
+
+    /// Tap energy of all bands added together
+    pub fn take_tap_energy(&mut self) -> [f32; TAPS] {
+        let mut energy = [0.0; TAPS];
+        for band in self.bands.iter_mut() {
+            for (total, band_energy) in energy.iter_mut().zip(band.take_tap_energy()) {
+                *total += band_energy;
+            }
+        }
+        energy
+    }
+
+    fn design(&mut self) {
+        let (low, high) = self.crossovers;
+        let sample_rate = self.sample_rate;
+        for filter in self.low_lowpass.iter_mut() {
+            filter.set_lowpass(low, BUTTERWORTH_Q, sample_rate);
+        }
+        for filter in self.low_highpass.iter_mut() {
+            filter.set_highpass(low, BUTTERWORTH_Q, sample_rate);
+        }
+        for filter in self.high_lowpass.iter_mut() {
+            filter.set_lowpass(high, BUTTERWORTH_Q, sample_rate);
+        }
+        for filter in self.high_highpass.iter_mut() {
+            filter.set_highpass(high, BUTTERWORTH_Q, sample_rate);
+        }
+        self.low_allpass
+            .set_allpass(high, BUTTERWORTH_Q, sample_rate);
+    }
+
+    /// Split one sample and run each band through its own tap network. `drive` replaces Push
+    /// per band and `mix` scales each band's tap output. Returns the summed tap output.
+    pub fn process_taps(
+        &mut self,
+        input: f32,
+        params: &ConsoleParams,
+        drive: &[f32; BANDS],
+        mix: &[f32; BANDS],
+    ) -> f32 {
+        let low = self
+            .low_lowpass
+            .iter_mut()
+            .fold(input, |sample, filter| filter.process(sample));
+        let low = self.low_allpass.process(low);
+        let rest = self
+            .low_highpass
+            .iter_mut()
+            .fold(input, |sample, filter| filter.process(sample));
+        let mid = self
+            .high_lowpass
+            .iter_mut()
+            .fold(rest, |sample, filter| filter.process(sample));
+        let high = self
+            .high_highpass
+            .iter_mut()
+            .fold(rest, |sample, filter| filter.process(sample));
+
+        self.bands
+            .iter_mut()
+            .zip([low, mid, high])
+            .zip(drive.iter().zip(mix.iter()))
+            .map(|((band, signal), (drive, mix))| {
+                let band_params = ConsoleParams {
+                    push: *drive,
+                    ..*params
+                };
+                band.process_taps(signal, &band_params).1 * mix
+            })
+            .sum()
+    }
+}
@@ -0,0 +1,75 @@
+// note_gate.rs - Ardura 2024
+// Tracks held MIDI notes and produces a smoothed 0-1 gate level for blending the wet signal
+
+pub struct NoteGate {
+    held: [bool; 128],
+    held_count: u32,
+    level: f32,
+    attack_coeff: f32,
+    release_coeff: f32,
+}
+
+impl NoteGate {
+    pub fn new() -> Self {
+        Self {
+            held: [false; 128],
+            held_count: 0,
+            level: 0.0,
+            attack_coeff: 0.0,
+            release_coeff: 0.0,
+        }
+    }
+
+    /// Recalculate the one-pole coefficients for the given attack and release times
+    pub fn set_times(&mut self, attack_ms: f32, release_ms: f32, sample_rate: f32) {
+        self.attack_coeff = time_to_coeff(attack_ms, sample_rate);
+        self.release_coeff = time_to_coeff(release_ms, sample_rate);
+    }
+
+    pub fn note_on(&mut self, note: u8) {
+        let index = (note as usize).min(127);
+        if !self.held[index] {
+            self.held[index] = true;
+            self.held_count += 1;
+        }
+    }
+
+    pub fn note_off(&mut self, note: u8) {
+        let index = (note as usize).min(127);
+        if self.held[index] {
+            self.held[index] = false;
+            self.held_count -= 1;
+        }
+    }
+
+    /// Release every held note, used when the host sends a choke or we get reset
+    pub fn release_all(&mut self) {
+        self.held = [false; 128];
+        self.held_count = 0;
+    }
+
+    /// Advance the envelope by one sample and return the current gate level
+    pub fn next(&mut self) -> f32 {
+        let (target, coeff) = if self.held_count > 0 {
+            (1.0, self.attack_coeff)
+        } else {
+            (0.0, self.release_coeff)
+        };
+        self.level = target + coeff * (self.level - target);
+        self.level
+    }
+
+    pub fn reset(&mut self) {
+        self.release_all();
+        self.level = 0.0;
+    }
+}
+
+// Time in ms to reach ~63% of the way to the target
+fn time_to_coeff(time_ms: f32, sample_rate: f32) -> f32 {
+    if time_ms <= 0.0 {
+        0.0
+    } else {
+        (-1.0 / (time_ms * 0.001 * sample_rate)).exp()
+    }
+}
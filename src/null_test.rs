@@ -0,0 +1,179 @@
+// null_test.rs - Ardura 2024
+// Null test helper: compares the output against the untouched input, finds the best latency
+// and gain to line them up and reports what's left over
+
+use atomic_float::AtomicF32;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Longest reported latency the input gets lined up by, covers 8x oversampling, the limiter
+/// lookahead and the wow and flutter centre at 192 kHz
+pub const MAX_LATENCY: usize = 1024;
+
+/// How far past the reported latency the search goes, covers the tap network at the longest
+/// spacing up to 48 kHz
+pub const MAX_LAG: usize = 192;
+
+const HISTORY: usize = MAX_LATENCY + MAX_LAG + 1;
+
+/// Length of each measurement
+const WINDOW_SECONDS: f32 = 0.3;
+
+/// Correlation below this gets the inverted polarity warning
+pub const POLARITY_WARNING: f32 = -0.5;
+
+/// Result of one measurement window
+#[derive(Clone, Copy)]
+pub struct NullResult {
+    /// Residual energy relative to the output, dB. Very negative means a clean null.
+    pub residual_db: f32,
+    pub lag_samples: usize,
+    pub gain_db: f32,
+    /// Normalized correlation at that lag, -1 means the output is a flipped copy of the input
+    pub correlation: f32,
+}
+
+/// Switch and latest result shared between the GUI and the audio thread
+pub struct NullTestShared {
+    pub enabled: AtomicBool,
+    has_result: AtomicBool,
+    residual_db: AtomicF32,
+    lag_samples: AtomicUsize,
+    gain_db: AtomicF32,
+    correlation: AtomicF32,
+}
+
+impl NullTestShared {
+    pub fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            has_result: AtomicBool::new(false),
+            residual_db: AtomicF32::new(0.0),
+            lag_samples: AtomicUsize::new(0),
+            gain_db: AtomicF32::new(0.0),
+            correlation: AtomicF32::new(0.0),
+        }
+    }
+
+    pub fn store(&self, result: Option<NullResult>) {
+        match result {
+            Some(result) => {
+                self.residual_db.store(result.residual_db, Ordering::Relaxed);
+                self.lag_samples.store(result.lag_samples, Ordering::Relaxed);
+                self.gain_db.store(result.gain_db, Ordering::Relaxed);
+                self.correlation.store(result.correlation, Ordering::Relaxed);
+                self.has_result.store(true, Ordering::Relaxed);
+            }
+            None => self.has_result.store(false, Ordering::Relaxed),
+        }
+    }
+
+    pub fn load(&self) -> Option<NullResult> {
+        self.has_result.load(Ordering::Relaxed).then(|| NullResult {
+            residual_db: self.residual_db.load(Ordering::Relaxed),
+            lag_samples: self.lag_samples.load(Ordering::Relaxed),
+            gain_db: self.gain_db.load(Ordering::Relaxed),
+            correlation: self.correlation.load(Ordering::Relaxed),
+        })
+    }
+}
+
+pub struct NullTest {
+    history: [f32; HISTORY],
+    pos: usize,
+    cross: [f64; MAX_LAG + 1],
+    input_energy: f64,
+    output_energy: f64,
+    count: usize,
+    window: usize,
+    // Reported latency the input is lined up by before the search
+    latency: usize,
+}
+
+impl NullTest {
+    pub fn new() -> Self {
+        Self {
+            history: [0.0; HISTORY],
+            pos: 0,
+            cross: [0.0; MAX_LAG + 1],
+            input_energy: 0.0,
+            output_energy: 0.0,
+            count: 0,
+            window: 13230,
+            latency: 0,
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.window = (sample_rate * WINDOW_SECONDS) as usize;
+    }
+
+    /// The latency the plugin reports, the search starts there
+    pub fn set_latency(&mut self, latency: usize) {
+        let latency = latency.min(MAX_LATENCY);
+        if latency != self.latency {
+            self.latency = latency;
+            self.reset();
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.history = [0.0; HISTORY];
+        self.pos = 0;
+        self.cross = [0.0; MAX_LAG + 1];
+        self.input_energy = 0.0;
+        self.output_energy = 0.0;
+        self.count = 0;
+    }
+
+    /// Feed the bypassed input and processed output of one (mono summed) sample.
+    /// Returns Some at the end of every measurement window, holding None if it was silent.
+    pub fn process(&mut self, input: f32, output: f32) -> Option<Option<NullResult>> {
+        self.history[self.pos] = input;
+        let delayed =
+            |lag: usize| self.history[(self.pos + HISTORY - self.latency - lag) % HISTORY];
+        for (lag, cross) in self.cross.iter_mut().enumerate() {
+            *cross += output as f64 * delayed(lag) as f64;
+        }
+        let aligned = delayed(0);
+        self.pos = (self.pos + 1) % HISTORY;
+        self.input_energy += aligned as f64 * aligned as f64;
+        self.output_energy += output as f64 * output as f64;
+        self.count += 1;
+        if self.count < self.window {
+            return None;
+        }
+
+        let (extra_lag, cross) = self
+            .cross
+            .iter()
+            .copied()
+            .enumerate()
+            .fold((0, 0.0f64), |best, (lag, cross)| {
+                if cross.abs() > best.1.abs() {
+                    (lag, cross)
+                } else {
+                    best
+                }
+            });
+
+        let result = if self.input_energy <= 1.0e-12 || self.output_energy <= 1.0e-12 {
+            None
+        } else {
+            // Least squares gain, what it can't explain is the residual
+            let gain = cross / self.input_energy;
+            let residual = (self.output_energy - cross * gain).max(1.0e-20);
+            Some(NullResult {
+                residual_db: (10.0 * (residual / self.output_energy).log10()) as f32,
+                lag_samples: self.latency + extra_lag,
+                gain_db: (20.0 * gain.abs().max(1.0e-10).log10()) as f32,
+                correlation: (cross / (self.input_energy * self.output_energy).sqrt()) as f32,
+            })
+        };
+
+        self.cross = [0.0; MAX_LAG + 1];
+        self.input_energy = 0.0;
+        self.output_energy = 0.0;
+        self.count = 0;
+        Some(result)
+    }
+}
@@ -0,0 +1,77 @@
+// numeric_entry.rs - Ardura 2024
+// Small editable text field bound to a FloatParam for typing exact values
+
+use nih_plug::prelude::{FloatParam, Param, ParamSetter};
+use nih_plug_egui::egui::{self, FontId, Key, Response, TextEdit, Ui, Widget};
+
+/// Decimal places shown while not editing
+const DECIMALS: usize = 6;
+/// Width of the text field in pixels
+const WIDTH: f32 = 64.0;
+
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct NumericEntry<'a> {
+    param: &'a FloatParam,
+    param_setter: &'a ParamSetter<'a>,
+    text_size: f32,
+}
+
+impl<'a> NumericEntry<'a> {
+    pub fn for_param(param: &'a FloatParam, param_setter: &'a ParamSetter) -> Self {
+        Self {
+            param,
+            param_setter,
+            text_size: 11.0,
+        }
+    }
+
+    /// Font size of the field
+    pub fn with_text_size(mut self, text_size: f32) -> Self {
+        self.text_size = text_size;
+        self
+    }
+
+    fn set_value(&self, value: f32) {
+        // Round trip through the normalized value so typed numbers get clamped to the range
+        let value = self.param.preview_plain(self.param.preview_normalized(value));
+        self.param_setter.begin_set_parameter(self.param);
+        self.param_setter.set_parameter(self.param, value);
+        self.param_setter.end_set_parameter(self.param);
+    }
+}
+
+impl<'a> Widget for NumericEntry<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        // Params live behind an Arc so their address works as a stable unique id
+        let id = egui::Id::new(("numeric_entry", self.param as *const FloatParam));
+
+        // While editing keep the user's text around, otherwise show the live value
+        let editing = ui.memory(|mem| mem.has_focus(id));
+        let mut text = if editing {
+            ui.memory(|mem| mem.data.get_temp::<String>(id))
+                .unwrap_or_default()
+        } else {
+            format!("{:.*}", DECIMALS, self.param.modulated_plain_value())
+        };
+
+        let response = ui.add(
+            TextEdit::singleline(&mut text)
+                .id(id)
+                .desired_width(WIDTH)
+                .font(FontId::monospace(self.text_size)),
+        );
+
+        if response.lost_focus() {
+            if !ui.input(|i| i.key_pressed(Key::Escape)) {
+                if let Ok(value) = text.trim().parse::<f32>() {
+                    self.set_value(value);
+                }
+            }
+            ui.memory_mut(|mem| mem.data.remove::<String>(id));
+        } else if response.has_focus() {
+            ui.memory_mut(|mem| mem.data.insert_temp(id, text));
+        }
+
+        response
+    }
+}
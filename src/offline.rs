@@ -0,0 +1,726 @@
+// offline.rs - Ardura 2024
+// Offline rendering through a snapshot of the current settings, for bouncing clips outside
+// the DAW. Runs on the background executor, never on the audio thread.
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    auto_gain, engine, filters, lofi, multiband, oversampling, quality, tape, transformer,
+    true_peak, wav, wow_flutter,
+};
+
+/// Everything the offline chain needs, copied out of the params so the render doesn't race
+/// the host changing them
+#[derive(Clone, Copy)]
+pub struct OfflineSettings {
+    pub console: engine::ConsoleParams,
+    /// Bank B params and the A/B balance when the dual engine is on
+    pub console_b: Option<(engine::ConsoleParams, f32)>,
+    pub oversampling: oversampling::Oversampling,
+    pub lofi_ratio: lofi::LofiRatio,
+    pub lofi_interp: lofi::LofiInterp,
+    pub crush_bits: f32,
+    pub crush_mix: f32,
+    pub dc_trim: [f32; 2],
+    pub mix_law: engine::MixLaw,
+    /// Reference level for the nonlinear stages
+    pub calibration: engine::Calibration,
+    /// Decode on the way in as a bus, encode on the way out as a channel
+    pub console_mode: engine::ConsoleMode,
+    /// How the dual engine blends bank A into B
+    pub bank_morph: engine::BankMorph,
+    /// Right channel coefficient and skew offsets when unlinked
+    pub right_offsets: Option<([f32; engine::TAPS], [f32; engine::TAPS])>,
+    /// DC blocker cutoff in Hz when it's on
+    pub dc_block_cutoff: Option<f32>,
+    /// Trim the processed signal back to the input loudness
+    pub auto_gain: bool,
+    /// Flip the output polarity
+    pub output_invert: bool,
+    /// Output only the difference from the aligned dry
+    pub delta: bool,
+    /// True-peak ceiling in dB and release in ms when the limiter is on
+    pub true_peak: Option<(f32, f32)>,
+    /// Band split settings in multiband mode, takes over from the dual engine
+    pub multiband: Option<multiband::MultibandSettings>,
+    /// Tap spacing in ms, None for one sample apart
+    pub tap_spacing_ms: Option<f32>,
+    /// Input high-pass and low-pass slope and cutoff
+    pub hpf: (filters::FilterSlope, f32),
+    pub lpf: (filters::FilterSlope, f32),
+    /// Tilt in dB, pivot in Hz and where it sits
+    pub tilt: (f32, f32, filters::TiltPlacement),
+    /// Wow rate in Hz and depth when it's on
+    pub wow_flutter: Option<(f32, f32)>,
+    /// Tape drive and bias when the tape stage is on
+    pub tape: Option<(f32, f32)>,
+    /// Transformer drive and core size when the transformer is on
+    pub transformer: Option<(f32, f32)>,
+}
+
+impl OfflineSettings {
+    /// Oversampling and shaper from a quality profile, on both banks
+    pub fn with_quality(mut self, profile: quality::ProfileSettings) -> Self {
+        self.oversampling = profile.oversampling();
+        self.console.hq_shaper = profile.hq_shaper;
+        if let Some((console_b, _)) = self.console_b.as_mut() {
+            console_b.hq_shaper = profile.hq_shaper;
+        }
+        self
+    }
+}
+
+/// A render request from the GUI. `input` of None renders the built in demo clip.
+pub struct RenderJob {
+    pub input: Option<PathBuf>,
+    pub output: PathBuf,
+    pub settings: OfflineSettings,
+}
+
+/// Run one channel through the console chain in place
+pub fn render_channel(
+    samples: &mut [f32],
+    settings: &OfflineSettings,
+    channel: usize,
+    sample_rate: f32,
+) {
+    let mut console = engine::ConsoleChannel::new();
+    let mut console_b = engine::ConsoleChannel::new();
+    let mut bands = multiband::MultibandConsole::new();
+    let mut oversampler = oversampling::Oversampler::new();
+    let mut lofi = lofi::RateReducer::new();
+    let mut adaa_aligner = engine::AdaaAligner::new();
+    let mut dc_blocker = filters::DcBlocker::new();
+    let mut hpf = filters::SlopeFilter::new(true);
+    let mut lpf = filters::SlopeFilter::new(false);
+    hpf.set(settings.hpf.0, settings.hpf.1, sample_rate);
+    lpf.set(settings.lpf.0, settings.lpf.1, sample_rate);
+    let (tilt_db, tilt_pivot, tilt_placement) = settings.tilt;
+    let mut tilt = filters::TiltEq::new();
+    tilt.set_pivot(tilt_pivot, sample_rate);
+    tilt.set_tilt(tilt_db);
+    let mut wow_flutter = wow_flutter::WowFlutter::new();
+    wow_flutter.set_sample_rate(sample_rate);
+    // Zero depth on the dry Delta takes away, so it keeps up with the wow's centre delay
+    let mut wow_dry = wow_flutter::WowFlutter::new();
+    wow_dry.set_sample_rate(sample_rate);
+    let mut tape = tape::TapeHysteresis::new();
+    tape.set_sample_rate(sample_rate);
+    if let Some((drive, bias)) = settings.tape {
+        tape.set_params(drive, bias);
+    }
+    let mut transformer = transformer::Transformer::new();
+    transformer.set_sample_rate(sample_rate);
+    if let Some((drive, core)) = settings.transformer {
+        transformer.set_drive(drive);
+        transformer.set_core(core);
+    }
+    if let Some(cutoff) = settings.dc_block_cutoff {
+        dc_blocker.set_cutoff(cutoff, sample_rate);
+    }
+    // Offline renders run each channel on its own, so the limiter isn't linked here
+    let mut auto_gain = auto_gain::AutoGain::new();
+    auto_gain.set_sample_rate(sample_rate);
+    let mut limiter = true_peak::TruePeakLimiter::new();
+    limiter.set_sample_rate(sample_rate);
+    let limiter_ceiling = settings
+        .true_peak
+        .map(|(ceiling_db, _)| 10f32.powf(ceiling_db / 20.0));
+    if let Some((_, release_ms)) = settings.true_peak {
+        limiter.set_release(release_ms);
+    }
+    let tap_spacing = engine::tap_spacing(settings.tap_spacing_ms, sample_rate);
+    oversampler.set_oversampling(settings.oversampling);
+    let adaa_delay = engine::adaa_delay(settings.console.hq_shaper, settings.oversampling.factor());
+    let running_spacing = tap_spacing * settings.oversampling.factor() as f32;
+    console.set_spacing(running_spacing);
+    console_b.set_spacing(running_spacing);
+    bands.set_spacing(running_spacing);
+    bands.set_sample_rate(sample_rate * settings.oversampling.factor() as f32);
+    if let Some(multiband) = &settings.multiband {
+        bands.set_crossovers(multiband.crossovers.0, multiband.crossovers.1);
+    }
+
+    // The right channel picks up its offsets when unlinked
+    let (params, params_b) = match (channel, settings.right_offsets) {
+        (1, Some((coeff_offsets, skew_offsets))) => (
+            settings.console.with_offsets(&coeff_offsets, &skew_offsets),
+            settings.console_b.map(|(params_b, balance)| {
+                (params_b.with_offsets(&coeff_offsets, &skew_offsets), balance)
+            }),
+        ),
+        _ => (settings.console, settings.console_b),
+    };
+    let params = &params;
+    let calibration_in = settings.calibration.input_gain();
+    let calibration_out = 1.0 / calibration_in;
+    for sample in samples.iter_mut() {
+        let decoded = settings.console_mode.decode(*sample);
+        let input = decoded * params.input_gain * calibration_in + settings.dc_trim[channel.min(1)];
+        let mut input = lpf.process(hpf.process(input));
+        if tilt_placement == filters::TiltPlacement::Pre {
+            input = tilt.process(input);
+        }
+
+        let wet = lofi.process(settings.lofi_ratio, settings.lofi_interp, input, |x| {
+            oversampler.process(x, |y| match (&settings.multiband, &params_b) {
+                (Some(multiband), _) => {
+                    bands.process_taps(y, params, &multiband.drive, &multiband.mix)
+                }
+                (None, Some((params_b, balance))) => {
+                    engine::process_dual_taps(
+                        &mut console,
+                        &mut console_b,
+                        y,
+                        params,
+                        params_b,
+                        *balance,
+                        settings.bank_morph,
+                    )
+                    .1
+                }
+                (None, None) => console.process_taps(y, params).1,
+            })
+        });
+        let wet = if settings.crush_mix > 0.0 {
+            wet + (lofi::crush(wet, settings.crush_bits) - wet) * settings.crush_mix
+        } else {
+            wet
+        };
+        let dry = adaa_aligner.process(oversampler.delay_dry(input), adaa_delay);
+        let mut mixed = settings.mix_law.mix(dry, wet, params.wet);
+        if tilt_placement == filters::TiltPlacement::Post {
+            mixed = tilt.process(mixed);
+        }
+        let mut dry = dry;
+        if let Some((rate, depth)) = settings.wow_flutter {
+            mixed = wow_flutter.process(mixed, rate, depth);
+            dry = wow_dry.process(dry, rate, 0.0);
+        }
+        if settings.tape.is_some() {
+            mixed = tape.process(mixed);
+        }
+        if settings.transformer.is_some() {
+            mixed = transformer.process(mixed);
+        }
+        mixed *= calibration_out;
+        if settings.dc_block_cutoff.is_some() {
+            mixed = dc_blocker.process(mixed);
+        }
+
+        if settings.auto_gain {
+            mixed *= auto_gain.next([decoded * params.input_gain; 2], [mixed; 2]);
+        }
+        if settings.delta {
+            mixed -= dry * calibration_out;
+        }
+
+        *sample = mixed * params.output_gain;
+        if settings.output_invert {
+            *sample = -*sample;
+        }
+        *sample = settings.console_mode.encode(*sample);
+        if let Some(ceiling) = limiter_ceiling {
+            let mut frame = [*sample; 2];
+            limiter.process(&mut frame, ceiling);
+            *sample = frame[0];
+        }
+    }
+}
+
+/// Known inputs for deterministic renders
+#[derive(Clone, Copy, Debug)]
+pub enum TestSignal {
+    /// One sample at half scale, then silence
+    Impulse,
+    /// Sine at the given Hz, -6 dBFS
+    Sine(f32),
+    /// White noise from a fixed seed, -6 dBFS peak
+    Noise(u32),
+}
+
+impl TestSignal {
+    pub fn generate(self, sample_rate: u32, length: usize) -> Vec<f32> {
+        match self {
+            TestSignal::Impulse => (0..length).map(|i| if i == 0 { 0.5 } else { 0.0 }).collect(),
+            TestSignal::Sine(hz) => (0..length)
+                .map(|i| {
+                    (std::f32::consts::TAU * hz * i as f32 / sample_rate as f32).sin() * 0.5
+                })
+                .collect(),
+            TestSignal::Noise(seed) => {
+                // Same xorshift as the demo clip, zero would get stuck
+                let mut state = seed.max(1);
+                (0..length)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 17;
+                        state ^= state << 5;
+                        (state as f32 / u32::MAX as f32 * 2.0 - 1.0) * 0.5
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Render a test signal into each channel from fresh state, same arguments give the same
+/// output every time
+pub fn render_signal(
+    settings: &OfflineSettings,
+    signal: TestSignal,
+    sample_rate: u32,
+    channels: usize,
+    length: usize,
+) -> Vec<Vec<f32>> {
+    let input = signal.generate(sample_rate, length);
+    (0..channels)
+        .map(|channel| {
+            let mut samples = input.clone();
+            render_channel(&mut samples, settings, channel, sample_rate as f32);
+            samples
+        })
+        .collect()
+}
+
+/// Short stereo clip with lows, mids and transients so the console has something to chew on
+pub fn demo_clip(sample_rate: u32) -> wav::WavData {
+    let length = sample_rate as usize * 4;
+    let beat = sample_rate as usize / 2;
+    let mut noise_state: u32 = 0x2545_F491;
+    let mut left = Vec::with_capacity(length);
+    let mut right = Vec::with_capacity(length);
+    for i in 0..length {
+        let t = i as f32 / sample_rate as f32;
+        let since_beat = (i % beat) as f32 / sample_rate as f32;
+
+        // Kick with a falling pitch
+        let kick_phase = std::f32::consts::TAU
+            * (50.0 * since_beat + 2.0 * (1.0 - (-since_beat * 30.0).exp()));
+        let kick = kick_phase.sin() * (-since_beat * 8.0).exp() * 0.6;
+
+        // Noise hat on the offbeat
+        noise_state ^= noise_state << 13;
+        noise_state ^= noise_state >> 17;
+        noise_state ^= noise_state << 5;
+        let noise = noise_state as f32 / u32::MAX as f32 * 2.0 - 1.0;
+        let hat_time = ((i + beat / 2) % beat) as f32 / sample_rate as f32;
+        let hat = noise * (-hat_time * 60.0).exp() * 0.15;
+
+        // Slow chord, slightly different per side
+        let chord = [110.0, 164.81, 220.0, 277.18]
+            .iter()
+            .map(|freq| (std::f32::consts::TAU * freq * t).sin())
+            .sum::<f32>()
+            * 0.06;
+
+        left.push(kick + hat * 0.8 + chord);
+        right.push(kick + hat * 1.2 + chord * 0.9);
+    }
+
+    wav::WavData {
+        sample_rate,
+        channels: vec![left, right],
+    }
+}
+
+/// Load (or make) the clip, render it and write the result. Returns a status message.
+pub fn run_render_job(job: &RenderJob) -> Result<String, String> {
+    let mut clip = match &job.input {
+        Some(path) => wav::read_wav(path)?,
+        None => demo_clip(48000),
+    };
+    for (channel, samples) in clip.channels.iter_mut().enumerate() {
+        render_channel(samples, &job.settings, channel, clip.sample_rate as f32);
+    }
+    wav::write_wav(&job.output, &clip)?;
+    Ok(format!("Rendered {}", job.output.display()))
+}
+
+/// Apply a `.gladepreset` file on top of `base`. The format is one `param_id = value` per line
+/// using the plugin's param ids and plain values (gains are linear), `#` starts a comment.
+pub fn apply_preset(base: OfflineSettings, text: &str) -> Result<OfflineSettings, String> {
+    let mut settings = base;
+    let mut console_b = base.console_b.map_or(base.console, |(params, _)| params);
+    let mut balance = base.console_b.map_or(0.5, |(_, balance)| balance);
+    let mut dual = base.console_b.is_some();
+    let mut link = base.right_offsets.is_none();
+    let (mut r_coeff_offsets, mut r_skew_offsets) =
+        base.right_offsets.unwrap_or(([0.0; engine::TAPS], [0.0; engine::TAPS]));
+    let mut dc_block = base.dc_block_cutoff.is_some();
+    let mut dc_block_cutoff = base.dc_block_cutoff.unwrap_or(10.0);
+    let mut true_peak_on = base.true_peak.is_some();
+    let (mut true_peak_ceiling, mut true_peak_release) = base.true_peak.unwrap_or((-1.0, 100.0));
+    let mut multiband_on = base.multiband.is_some();
+    let mut bands = base.multiband.unwrap_or(multiband::MultibandSettings {
+        crossovers: (200.0, 3000.0),
+        drive: [0.0; multiband::BANDS],
+        mix: [1.0; multiband::BANDS],
+    });
+    let (mut wow_rate, mut wow_depth) = base.wow_flutter.unwrap_or((0.8, 0.0));
+    let mut tape_on = base.tape.is_some();
+    let (mut tape_drive, mut tape_bias) = base.tape.unwrap_or((0.5, 0.5));
+    let mut transformer_on = base.transformer.is_some();
+    let (mut transformer_drive, mut transformer_core) = base.transformer.unwrap_or((0.3, 0.5));
+    let mut spacing_ms = base.tap_spacing_ms.is_some();
+    let mut tap_spacing = base.tap_spacing_ms.unwrap_or(1000.0 / 44100.0);
+    let mut quality_mode = quality::QualityMode::Manual;
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (id, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("Line {}: expected `param_id = value`", number + 1))?;
+        let (id, value) = (id.trim(), value.trim());
+        let number_value = || {
+            value
+                .parse::<f32>()
+                .map_err(|_| format!("Line {}: `{value}` is not a number", number + 1))
+        };
+
+        let console = &mut settings.console;
+        match id {
+            "free_gain" => console.input_gain = number_value()?,
+            "Push" => console.push = number_value()?.clamp(0.0, 1.0),
+            "Multiplier" => console.multiplier = number_value()?.clamp(1.0, 10.0),
+            "output_gain" => console.output_gain = number_value()?,
+            "dry_wet" => console.wet = number_value()?.clamp(0.0, 1.0),
+            "hq_shaper" => console.hq_shaper = parse_bool(value, number)?,
+            "bias" => console.bias = number_value()?.clamp(0.0, 0.5),
+            "even_odd" => console.even_odd = number_value()?.clamp(0.0, 1.0),
+            "harmonic_mode" => {
+                console.harmonic_mode = match value {
+                    "Off" => engine::HarmonicMode::Off,
+                    "After" | "After Taps" => engine::HarmonicMode::After,
+                    "Replace" | "Replace Taps" => engine::HarmonicMode::Replace,
+                    _ => {
+                        return Err(format!(
+                            "Line {}: harmonic_mode is Off, After or Replace",
+                            number + 1
+                        ))
+                    }
+                }
+            }
+            _ if id.starts_with("harmonic_") => {
+                let harmonic = id["harmonic_".len()..]
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|harmonic| (2..=engine::HARMONICS + 1).contains(harmonic))
+                    .ok_or_else(|| format!("Line {}: unknown param `{id}`", number + 1))?;
+                console.harmonics[harmonic - 2] = number_value()?.clamp(0.0, 1.0);
+            }
+            "push_curve" => {
+                console.push_curve = match value {
+                    "Sine" => engine::PushCurve::Sine,
+                    "Tanh" => engine::PushCurve::Tanh,
+                    "Arctan" => engine::PushCurve::Arctan,
+                    "Cubic" => engine::PushCurve::Cubic,
+                    "Fold" => engine::PushCurve::Fold,
+                    _ => {
+                        return Err(format!(
+                            "Line {}: push_curve is Sine, Tanh, Arctan, Cubic or Fold",
+                            number + 1
+                        ))
+                    }
+                }
+            }
+            "tap_count" => {
+                console.tap_count = value
+                    .parse::<usize>()
+                    .map_err(|_| format!("Line {}: `{value}` is not a whole number", number + 1))?
+                    .clamp(engine::MIN_TAPS, engine::MAX_TAPS)
+            }
+            "feedback" => console.feedback = number_value()?.clamp(0.0, 0.9),
+            "feedback_damping" => console.feedback_damping = number_value()?.clamp(0.0, 0.95),
+            "tap_damping" => console.tap_damping = number_value()?.clamp(0.0, 0.95),
+            "wow_depth" => wow_depth = number_value()?.clamp(0.0, 1.0),
+            "wow_rate" => wow_rate = number_value()?.clamp(0.1, 5.0),
+            "tape" => tape_on = parse_bool(value, number)?,
+            "tape_drive" => tape_drive = number_value()?.clamp(0.0, 1.0),
+            "tape_bias" => tape_bias = number_value()?.clamp(0.0, 1.0),
+            // Channel strip labels only name the routing, nothing to render
+            "routing_from" | "routing_to" => {}
+            "transformer" => transformer_on = parse_bool(value, number)?,
+            "transformer_drive" => transformer_drive = number_value()?.clamp(0.0, 1.0),
+            "transformer_core" => transformer_core = number_value()?.clamp(0.0, 1.0),
+            "spacing_mode" => {
+                spacing_ms = match value {
+                    "Samples" => false,
+                    "ms" | "Milliseconds" => true,
+                    _ => return Err(format!("Line {}: spacing_mode is Samples or ms", number + 1)),
+                }
+            }
+            "tap_spacing" => {
+                tap_spacing = number_value()?.clamp(0.01, engine::MAX_TAP_SPACING_MS)
+            }
+            "dual_engine" => dual = parse_bool(value, number)?,
+            "engine_balance" => balance = number_value()?.clamp(0.0, 1.0),
+            "crush_bits" => settings.crush_bits = number_value()?.clamp(2.0, 16.0),
+            "crush_mix" => settings.crush_mix = number_value()?.clamp(0.0, 1.0),
+            "link" => link = parse_bool(value, number)?,
+            "dc_block" => dc_block = parse_bool(value, number)?,
+            "dc_block_cutoff" => dc_block_cutoff = number_value()?.clamp(5.0, 40.0),
+            "auto_gain" => settings.auto_gain = parse_bool(value, number)?,
+            "output_invert" => settings.output_invert = parse_bool(value, number)?,
+            "delta" => settings.delta = parse_bool(value, number)?,
+            "quality_mode" => {
+                quality_mode = match value {
+                    "Manual" => quality::QualityMode::Manual,
+                    "Auto" => quality::QualityMode::Auto,
+                    "Tracking" => quality::QualityMode::Tracking,
+                    "Mixing" => quality::QualityMode::Mixing,
+                    "Render" => quality::QualityMode::Render,
+                    _ => {
+                        return Err(format!(
+                            "Line {}: quality_mode is Manual, Auto, Tracking, Mixing or Render",
+                            number + 1
+                        ))
+                    }
+                }
+            }
+            "true_peak" => true_peak_on = parse_bool(value, number)?,
+            "true_peak_ceiling" => true_peak_ceiling = number_value()?.clamp(-12.0, 0.0),
+            "true_peak_release" => true_peak_release = number_value()?.clamp(10.0, 1000.0),
+            "multiband" => multiband_on = parse_bool(value, number)?,
+            "crossover_low" => bands.crossovers.0 = number_value()?.clamp(40.0, 1000.0),
+            "crossover_high" => bands.crossovers.1 = number_value()?.clamp(1000.0, 12000.0),
+            "low_drive" => bands.drive[0] = number_value()?.clamp(0.0, 1.0),
+            "mid_drive" => bands.drive[1] = number_value()?.clamp(0.0, 1.0),
+            "high_drive" => bands.drive[2] = number_value()?.clamp(0.0, 1.0),
+            "low_mix" => bands.mix[0] = number_value()?.clamp(0.0, 1.0),
+            "mid_mix" => bands.mix[1] = number_value()?.clamp(0.0, 1.0),
+            "high_mix" => bands.mix[2] = number_value()?.clamp(0.0, 1.0),
+            "dc_trim_l" => settings.dc_trim[0] = number_value()?.clamp(-0.01, 0.01),
+            "dc_trim_r" => settings.dc_trim[1] = number_value()?.clamp(-0.01, 0.01),
+            "oversampling" => {
+                settings.oversampling = match value {
+                    "1" | "1x" => oversampling::Oversampling::X1,
+                    "2" | "2x" => oversampling::Oversampling::X2,
+                    "4" | "4x" => oversampling::Oversampling::X4,
+                    "8" | "8x" => oversampling::Oversampling::X8,
+                    _ => return Err(format!("Line {}: oversampling is 1, 2, 4 or 8", number + 1)),
+                }
+            }
+            "lofi_ratio" => {
+                settings.lofi_ratio = match value {
+                    "1" | "Off" => lofi::LofiRatio::Off,
+                    "2" | "1/2" => lofi::LofiRatio::Half,
+                    "4" | "1/4" => lofi::LofiRatio::Quarter,
+                    "8" | "1/8" => lofi::LofiRatio::Eighth,
+                    "16" | "1/16" => lofi::LofiRatio::Sixteenth,
+                    _ => return Err(format!("Line {}: lofi_ratio is 1, 2, 4, 8 or 16", number + 1)),
+                }
+            }
+            "calibration" => {
+                settings.calibration = match value.trim_end_matches(" dBFS") {
+                    "-18" => engine::Calibration::Minus18,
+                    "-14" => engine::Calibration::Minus14,
+                    "-12" => engine::Calibration::Minus12,
+                    _ => {
+                        return Err(format!(
+                            "Line {}: calibration is -18, -14 or -12 dBFS",
+                            number + 1
+                        ))
+                    }
+                };
+            }
+            "console_mode" => {
+                settings.console_mode = match value {
+                    "Single" => engine::ConsoleMode::Single,
+                    "Channel" => engine::ConsoleMode::Channel,
+                    "Bus" => engine::ConsoleMode::Bus,
+                    _ => {
+                        return Err(format!(
+                            "Line {}: console_mode is Single, Channel or Bus",
+                            number + 1
+                        ))
+                    }
+                };
+            }
+            "mix_law" => {
+                settings.mix_law = match value {
+                    "Additive" => engine::MixLaw::Additive,
+                    "Linear" => engine::MixLaw::Linear,
+                    "Equal Power" | "EqualPower" => engine::MixLaw::EqualPower,
+                    _ => {
+                        return Err(format!(
+                            "Line {}: mix_law is Additive, Linear or Equal Power",
+                            number + 1
+                        ))
+                    }
+                }
+            }
+            "hpf_slope" => settings.hpf.0 = parse_slope(value, "hpf_slope", number)?,
+            "hpf_cutoff" => settings.hpf.1 = number_value()?.clamp(10.0, 1000.0),
+            "lpf_slope" => settings.lpf.0 = parse_slope(value, "lpf_slope", number)?,
+            "lpf_cutoff" => settings.lpf.1 = number_value()?.clamp(1000.0, 22000.0),
+            "tilt" => settings.tilt.0 = number_value()?.clamp(-6.0, 6.0),
+            "tilt_pivot" => settings.tilt.1 = number_value()?.clamp(200.0, 5000.0),
+            "tilt_placement" => {
+                settings.tilt.2 = match value {
+                    "Pre" | "Pre Console" => filters::TiltPlacement::Pre,
+                    "Post" | "Post Console" => filters::TiltPlacement::Post,
+                    _ => {
+                        return Err(format!(
+                            "Line {}: tilt_placement is Pre or Post",
+                            number + 1
+                        ))
+                    }
+                }
+            }
+            // Nothing switches scenes during a render, accepted so saved presets still load
+            "scene_fade" => {
+                number_value()?;
+            }
+            // Channels render on their own here so there's nothing to bleed between
+            "crosstalk" | "crosstalk_dynamics" => {
+                number_value()?;
+            }
+            "bank_morph" => {
+                settings.bank_morph = match value {
+                    "Linear" => engine::BankMorph::Linear,
+                    "Min Change" | "MinimumChange" => engine::BankMorph::MinimumChange,
+                    _ => {
+                        return Err(format!(
+                            "Line {}: bank_morph is Linear or Min Change",
+                            number + 1
+                        ))
+                    }
+                }
+            }
+            "lofi_interp" => {
+                settings.lofi_interp = match value {
+                    "Hold" => lofi::LofiInterp::Hold,
+                    "Linear" => lofi::LofiInterp::Linear,
+                    _ => return Err(format!("Line {}: lofi_interp is Hold or Linear", number + 1)),
+                }
+            }
+            _ if id.starts_with("Polarity_") => {
+                let tap = id["Polarity_".len()..]
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|tap| (1..=engine::TAPS).contains(tap))
+                    .ok_or_else(|| format!("Line {}: unknown param `{id}`", number + 1))?;
+                console.polarity[tap - 1] = match value {
+                    "+" | "1" | "Positive" => 1.0,
+                    "-" | "-1" | "Negative" => -1.0,
+                    _ => return Err(format!("Line {}: polarity is + or -", number + 1)),
+                };
+            }
+            _ if id.starts_with('R') && id.ends_with("_Offset") => {
+                // Right channel offsets: R1_Coeff_Offset .. R8_Skew_Offset
+                let (tap, kind) = id[1..id.len() - "_Offset".len()]
+                    .split_once('_')
+                    .and_then(|(tap, kind)| Some((tap.parse::<usize>().ok()?, kind)))
+                    .filter(|(tap, _)| (1..=engine::TAPS).contains(tap))
+                    .ok_or_else(|| format!("Line {}: unknown param `{id}`", number + 1))?;
+                let value = number_value()?.clamp(-0.05, 0.05);
+                match kind {
+                    "Coeff" => r_coeff_offsets[tap - 1] = value,
+                    "Skew" => r_skew_offsets[tap - 1] = value,
+                    _ => return Err(format!("Line {}: unknown param `{id}`", number + 1)),
+                }
+            }
+            _ => {
+                // Bank sliders: 1_Coeff .. 8_Skew and B1_Coeff .. B8_Skew
+                let (bank_b, slider) = match id.strip_prefix('B') {
+                    Some(rest) => (true, rest),
+                    None => (false, id),
+                };
+                let (tap, kind) = slider
+                    .split_once('_')
+                    .and_then(|(tap, kind)| Some((tap.parse::<usize>().ok()?, kind)))
+                    .filter(|(tap, _)| (1..=engine::TAPS).contains(tap))
+                    .ok_or_else(|| format!("Line {}: unknown param `{id}`", number + 1))?;
+                let target = if bank_b { &mut console_b } else { console };
+                let value = number_value()?.clamp(-0.5, 0.5);
+                match kind {
+                    "Coeff" => target.coeffs[tap - 1] = value,
+                    "Skew" => target.skews[tap - 1] = value,
+                    _ => return Err(format!("Line {}: unknown param `{id}`", number + 1)),
+                }
+            }
+        }
+    }
+
+    settings.dc_block_cutoff = dc_block.then_some(dc_block_cutoff);
+    settings.true_peak = true_peak_on.then_some((true_peak_ceiling, true_peak_release));
+    settings.right_offsets = (!link).then_some((r_coeff_offsets, r_skew_offsets));
+    settings.multiband = multiband_on.then_some(bands);
+    settings.tap_spacing_ms = spacing_ms.then_some(tap_spacing);
+    settings.wow_flutter = (wow_depth > 0.0).then_some((wow_rate, wow_depth));
+    settings.tape = tape_on.then_some((tape_drive, tape_bias));
+    settings.transformer = transformer_on.then_some((transformer_drive, transformer_core));
+
+    // Bank B follows bank A for everything but its sliders
+    settings.console_b = dual.then_some((
+        engine::ConsoleParams {
+            coeffs: console_b.coeffs,
+            skews: console_b.skews,
+            ..settings.console
+        },
+        balance,
+    ));
+    // Everything here is a render, presets don't carry the profile settings so these use the
+    // defaults
+    if let Some(profile) = quality_mode.profile(true, false) {
+        settings = settings.with_quality(quality::QualityProfiles::default().get(profile));
+    }
+    Ok(settings)
+}
+
+fn parse_bool(value: &str, number: usize) -> Result<bool, String> {
+    match value {
+        "1" | "true" | "On" => Ok(true),
+        "0" | "false" | "Off" => Ok(false),
+        _ => Err(format!("Line {}: `{value}` is not true or false", number + 1)),
+    }
+}
+
+fn parse_slope(value: &str, key: &str, number: usize) -> Result<filters::FilterSlope, String> {
+    match value.trim_end_matches(" dB/oct").trim_end_matches(" dB") {
+        "Off" | "0" => Ok(filters::FilterSlope::Off),
+        "12" => Ok(filters::FilterSlope::Db12),
+        "24" => Ok(filters::FilterSlope::Db24),
+        _ => Err(format!("Line {}: {key} is Off, 12 or 24", number + 1)),
+    }
+}
+
+/// `--process in.wav out.wav [--preset x.gladepreset]`
+pub fn run_cli(base: OfflineSettings, args: &[String]) -> Result<String, String> {
+    const USAGE: &str = "Usage: --process in.wav out.wav [--preset x.gladepreset]";
+    let mut input = None;
+    let mut output = None;
+    let mut preset = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--process" => {
+                input = args.next().map(PathBuf::from);
+                output = args.next().map(PathBuf::from);
+            }
+            "--preset" => preset = args.next().map(PathBuf::from),
+            _ => return Err(format!("Unknown argument `{arg}`\n{USAGE}")),
+        }
+    }
+    let (Some(input), Some(output)) = (input, output) else {
+        return Err(USAGE.to_string());
+    };
+
+    let settings = match preset {
+        Some(path) => apply_preset(base, &read_preset(&path)?)?,
+        None => base,
+    };
+    run_render_job(&RenderJob {
+        input: Some(input),
+        output,
+        settings,
+    })
+}
+
+fn read_preset(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {e}", path.display()))
+}
@@ -0,0 +1,196 @@
+// oversampling.rs - Ardura 2024
+// Cascaded 2x halfband oversampling for the console stage so the Push shaper and the skew terms
+// don't fold their harmonics back down
+
+use nih_plug::prelude::Enum;
+
+/// Halfband FIR length per 2x stage. 33 taps keeps every stage's delay a whole number of samples.
+const HALFBAND_TAPS: usize = 33;
+const HALFBAND_CENTER: usize = (HALFBAND_TAPS - 1) / 2;
+
+/// Highest number of 2x stages (8x)
+const MAX_STAGES: usize = 3;
+const MAX_FACTOR: usize = 1 << MAX_STAGES;
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Oversampling {
+    #[name = "1x"]
+    X1,
+    #[name = "2x"]
+    X2,
+    #[name = "4x"]
+    X4,
+    #[name = "8x"]
+    X8,
+}
+
+impl Oversampling {
+    fn stages(self) -> usize {
+        match self {
+            Oversampling::X1 => 0,
+            Oversampling::X2 => 1,
+            Oversampling::X4 => 2,
+            Oversampling::X8 => 3,
+        }
+    }
+
+    /// Internal samples per host sample
+    pub fn factor(self) -> usize {
+        1 << self.stages()
+    }
+
+    /// Delay of the up + down filters in host samples
+    pub fn latency_samples(self) -> u32 {
+        // Each stage's up and down filter both delay by HALFBAND_CENTER samples at its own rate
+        (1..=self.stages())
+            .map(|stage| ((2 * HALFBAND_CENTER) >> stage) as u32)
+            .sum()
+    }
+}
+
+struct HalfbandFilter {
+    // Doubled so the convolution can always read one contiguous slice
+    buffer: [f32; HALFBAND_TAPS * 2],
+    pos: usize,
+}
+
+impl HalfbandFilter {
+    fn new() -> Self {
+        Self {
+            buffer: [0.0; HALFBAND_TAPS * 2],
+            pos: 0,
+        }
+    }
+
+    fn process(&mut self, input: f32, coeffs: &[f32; HALFBAND_TAPS]) -> f32 {
+        self.pos = if self.pos == 0 { HALFBAND_TAPS - 1 } else { self.pos - 1 };
+        self.buffer[self.pos] = input;
+        self.buffer[self.pos + HALFBAND_TAPS] = input;
+        self.buffer[self.pos..self.pos + HALFBAND_TAPS]
+            .iter()
+            .zip(coeffs.iter())
+            .map(|(sample, coeff)| sample * coeff)
+            .sum()
+    }
+}
+
+struct Stage {
+    up: HalfbandFilter,
+    down: HalfbandFilter,
+}
+
+/// One channel worth of oversampling
+pub struct Oversampler {
+    coeffs: [f32; HALFBAND_TAPS],
+    stages: [Stage; MAX_STAGES],
+    current: Oversampling,
+    // Delay line so the dry signal lines up with the filtered wet signal
+    dry_delay: [f32; MAX_FACTOR * HALFBAND_CENTER],
+    dry_pos: usize,
+}
+
+impl Oversampler {
+    pub fn new() -> Self {
+        Self {
+            coeffs: halfband_coeffs(),
+            stages: std::array::from_fn(|_| Stage {
+                up: HalfbandFilter::new(),
+                down: HalfbandFilter::new(),
+            }),
+            current: Oversampling::X1,
+            dry_delay: [0.0; MAX_FACTOR * HALFBAND_CENTER],
+            dry_pos: 0,
+        }
+    }
+
+    /// Clears the filters and the dry delay, keeps the current rate
+    pub fn reset(&mut self) {
+        for stage in self.stages.iter_mut() {
+            stage.up = HalfbandFilter::new();
+            stage.down = HalfbandFilter::new();
+        }
+        self.dry_delay = [0.0; MAX_FACTOR * HALFBAND_CENTER];
+        self.dry_pos = 0;
+    }
+
+    /// Switch rates, clearing the filters if it changed. Call once per block.
+    pub fn set_oversampling(&mut self, oversampling: Oversampling) {
+        if oversampling != self.current {
+            self.reset();
+            self.current = oversampling;
+        }
+    }
+
+    /// Delay the dry signal by the current oversampling latency. Call once per host sample.
+    pub fn delay_dry(&mut self, input: f32) -> f32 {
+        let latency = self.current.latency_samples() as usize;
+        if latency == 0 {
+            return input;
+        }
+        self.dry_delay[self.dry_pos] = input;
+        let read = (self.dry_pos + self.dry_delay.len() - latency) % self.dry_delay.len();
+        self.dry_pos = (self.dry_pos + 1) % self.dry_delay.len();
+        self.dry_delay[read]
+    }
+
+    /// Run `stage` at the oversampled rate for one host sample
+    pub fn process(&mut self, input: f32, mut stage: impl FnMut(f32) -> f32) -> f32 {
+        let stages = self.current.stages();
+        if stages == 0 {
+            return stage(input);
+        }
+
+        let mut samples = [0.0f32; MAX_FACTOR];
+        samples[0] = input;
+        let mut len = 1;
+
+        // Zero stuff and filter, doubled to make up for the inserted zeros
+        for filters in self.stages[..stages].iter_mut() {
+            let mut upsampled = [0.0f32; MAX_FACTOR];
+            for (pair, &sample) in upsampled.chunks_exact_mut(2).zip(samples[..len].iter()) {
+                pair[0] = filters.up.process(sample * 2.0, &self.coeffs);
+                pair[1] = filters.up.process(0.0, &self.coeffs);
+            }
+            samples = upsampled;
+            len *= 2;
+        }
+
+        for sample in samples[..len].iter_mut() {
+            *sample = stage(*sample);
+        }
+
+        // Filter and keep every other sample on the way back down
+        for filters in self.stages[..stages].iter_mut().rev() {
+            let mut downsampled = [0.0f32; MAX_FACTOR];
+            for (out, pair) in downsampled.iter_mut().zip(samples[..len].chunks_exact(2)) {
+                // Keeping the first of each pair keeps the delay a whole number of samples
+                *out = filters.down.process(pair[0], &self.coeffs);
+                filters.down.process(pair[1], &self.coeffs);
+            }
+            samples = downsampled;
+            len /= 2;
+        }
+
+        samples[0]
+    }
+}
+
+/// Blackman windowed sinc cutting off at a quarter of the (oversampled) rate
+fn halfband_coeffs() -> [f32; HALFBAND_TAPS] {
+    let mut coeffs = [0.0f32; HALFBAND_TAPS];
+    for (n, coeff) in coeffs.iter_mut().enumerate() {
+        let offset = n as f32 - HALFBAND_CENTER as f32;
+        let sinc = if offset == 0.0 {
+            0.5
+        } else {
+            (std::f32::consts::PI * offset * 0.5).sin() / (std::f32::consts::PI * offset)
+        };
+        let phase = std::f32::consts::TAU * n as f32 / (HALFBAND_TAPS - 1) as f32;
+        let window = 0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos();
+        *coeff = sinc * window;
+    }
+    // Unity gain at DC
+    let sum: f32 = coeffs.iter().sum();
+    coeffs.iter_mut().for_each(|coeff| *coeff /= sum);
+    coeffs
+}
@@ -0,0 +1,115 @@
+// presence.rs - Ardura 2024
+// Signal present LEDs per input channel, and the auto standby that winds down everything that
+// moves on its own (the random modulation) while no input is coming in
+
+use crate::channel_meters::{BlockPeaks, MAX_METER_CHANNELS};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Anything above -60 dBFS counts as signal
+const PRESENCE_THRESHOLD: f32 = 0.001;
+
+/// LEDs stay lit this long after the signal drops away so short gaps don't flicker
+const LED_HOLD_SECONDS: f32 = 0.25;
+
+/// Silence on every channel for this long goes into standby
+const STANDBY_AFTER_SECONDS: f32 = 0.5;
+
+/// Time to ramp fully in or out of standby
+const STANDBY_RAMP_SECONDS: f32 = 0.05;
+
+/// What the GUI draws, written once per block
+pub struct SignalPresence {
+    present: [AtomicBool; MAX_METER_CHANNELS],
+    standby: AtomicBool,
+}
+
+impl SignalPresence {
+    pub fn new() -> Self {
+        Self {
+            present: std::array::from_fn(|_| AtomicBool::new(false)),
+            standby: AtomicBool::new(false),
+        }
+    }
+
+    pub fn present(&self, channel: usize) -> bool {
+        self.present
+            .get(channel)
+            .is_some_and(|present| present.load(Ordering::Relaxed))
+    }
+
+    pub fn standby(&self) -> bool {
+        self.standby.load(Ordering::Relaxed)
+    }
+}
+
+pub struct PresenceDetector {
+    silent_for: [u32; MAX_METER_CHANNELS],
+    led_hold: u32,
+    standby_after: u32,
+    ramp_step: f32,
+    standby: bool,
+    gain: f32,
+}
+
+impl PresenceDetector {
+    pub fn new() -> Self {
+        Self {
+            silent_for: [u32::MAX; MAX_METER_CHANNELS],
+            led_hold: 11025,
+            standby_after: 22050,
+            ramp_step: 1.0 / 2205.0,
+            standby: false,
+            gain: 1.0,
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.led_hold = (sample_rate * LED_HOLD_SECONDS) as u32;
+        self.standby_after = (sample_rate * STANDBY_AFTER_SECONDS) as u32;
+        self.ramp_step = 1.0 / (sample_rate * STANDBY_RAMP_SECONDS);
+    }
+
+    /// Once per block with the input peaks of the block
+    pub fn update(
+        &mut self,
+        peaks: &BlockPeaks,
+        channels: usize,
+        block_len: usize,
+        auto_standby: bool,
+        shared: &SignalPresence,
+    ) {
+        for (channel, silent_for) in self.silent_for.iter_mut().enumerate().take(channels) {
+            *silent_for = if peaks.peak(channel) > PRESENCE_THRESHOLD {
+                0
+            } else {
+                silent_for.saturating_add(block_len as u32)
+            };
+            shared.present[channel].store(*silent_for < self.led_hold, Ordering::Relaxed);
+        }
+        self.standby = auto_standby
+            && self
+                .silent_for
+                .iter()
+                .take(channels)
+                .all(|silent_for| *silent_for >= self.standby_after);
+        shared.standby.store(self.standby, Ordering::Relaxed);
+    }
+
+    /// Per sample gain for the free running modulation, ramps to 0 while standing by. Follows
+    /// the previous block's decision.
+    pub fn next_gain(&mut self) -> f32 {
+        let target = if self.standby { 0.0 } else { 1.0 };
+        if self.gain < target {
+            self.gain = (self.gain + self.ramp_step).min(target);
+        } else if self.gain > target {
+            self.gain = (self.gain - self.ramp_step).max(target);
+        }
+        self.gain
+    }
+
+    pub fn reset(&mut self) {
+        self.silent_for = [u32::MAX; MAX_METER_CHANNELS];
+        self.standby = false;
+        self.gain = 1.0;
+    }
+}
@@ -0,0 +1,76 @@
+// preset_import.rs - Ardura 2024
+// Rough importer for saved states of related Ardura plugins (Subhoofer-style saturators) so
+// sounds can be carried over. Only the broad strokes map, anything unknown is skipped.
+
+use serde_json::Value;
+
+/// GladeDesk values an import landed on, None means the source had nothing for it
+#[derive(Default)]
+pub struct ImportedSettings {
+    pub input_gain: Option<f32>,
+    pub push: Option<f32>,
+    pub multiplier: Option<f32>,
+    pub output_gain: Option<f32>,
+    pub dry_wet: Option<f32>,
+    /// Source param ids that were used, for the status line
+    pub used: Vec<String>,
+}
+
+// Source ids that read as a drive or saturation amount. Assumed 0-1, anything bigger gets
+// scaled down by its likely range.
+const DRIVE_IDS: [&str; 5] = ["sub_drive", "drive", "saturation", "harmonics", "sat_amount"];
+// Source ids for the harshness of the curve, mapped onto the multiplier
+const HARDNESS_IDS: [&str; 3] = ["hardness", "hoof_hardness", "sat_hardness"];
+
+/// Parse a nih-plug style state (`{"params": {"id": value, ...}}`, plain values) and map it
+pub fn import_state(text: &str) -> Result<ImportedSettings, String> {
+    let root: Value =
+        serde_json::from_str(text).map_err(|e| format!("Not a plugin state file: {e}"))?;
+    let params = root
+        .get("params")
+        .and_then(Value::as_object)
+        .ok_or_else(|| "State has no params section".to_string())?;
+
+    let mut imported = ImportedSettings::default();
+    for (id, value) in params {
+        let Some(value) = value.as_f64().map(|value| value as f32) else {
+            continue;
+        };
+        let id_lower = id.to_lowercase();
+        let mapped = match id_lower.as_str() {
+            // Gains are linear in every Ardura plugin so they carry straight over
+            "free_gain" | "input_gain" => {
+                imported.input_gain = Some(value.clamp(0.25, 4.0));
+                true
+            }
+            "output_gain" => {
+                imported.output_gain = Some(value.clamp(0.25, 4.0));
+                true
+            }
+            "dry_wet" => {
+                imported.dry_wet = Some(value.clamp(0.0, 1.0));
+                true
+            }
+            id if DRIVE_IDS.contains(&id) => {
+                let amount = if value > 1.0 { value / 10.0 } else { value };
+                imported.push = Some(amount.clamp(0.0, 1.0));
+                true
+            }
+            id if HARDNESS_IDS.contains(&id) => {
+                let amount = if value > 1.0 { value / 10.0 } else { value };
+                imported.multiplier = Some(1.0 + amount.clamp(0.0, 1.0) * 4.0);
+                true
+            }
+            _ => false,
+        };
+        if mapped {
+            imported.used.push(id.clone());
+        }
+    }
+
+    if imported.used.is_empty() {
+        Err("Nothing in that state maps onto Glade Desk".to_string())
+    } else {
+        Ok(imported)
+    }
+}
@@ -0,0 +1,156 @@
+// quality.rs - Ardura 2024
+// Quality profiles: oversampling and shaper settings for Tracking, Mixing and Render, picked by
+// hand or switched automatically from the host's process mode
+
+use nih_plug::prelude::Enum;
+use serde::{Deserialize, Serialize};
+
+use crate::oversampling::Oversampling;
+
+/// What the Quality param asks for
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QualityMode {
+    /// The Oversampling and HQ Shaper params as they are
+    #[name = "Manual"]
+    Manual,
+    /// Render while the host bounces offline, Tracking with Zero Latency on, Mixing otherwise
+    #[name = "Auto"]
+    Auto,
+    #[name = "Tracking"]
+    Tracking,
+    #[name = "Mixing"]
+    Mixing,
+    #[name = "Render"]
+    Render,
+}
+
+impl QualityMode {
+    /// The profile in use, None when manual
+    pub fn profile(self, offline_render: bool, zero_latency: bool) -> Option<QualityProfile> {
+        match self {
+            QualityMode::Manual => None,
+            QualityMode::Auto if offline_render => Some(QualityProfile::Render),
+            QualityMode::Auto if zero_latency => Some(QualityProfile::Tracking),
+            QualityMode::Auto => Some(QualityProfile::Mixing),
+            QualityMode::Tracking => Some(QualityProfile::Tracking),
+            QualityMode::Mixing => Some(QualityProfile::Mixing),
+            QualityMode::Render => Some(QualityProfile::Render),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum QualityProfile {
+    Tracking,
+    Mixing,
+    Render,
+}
+
+impl QualityProfile {
+    pub const ALL: [QualityProfile; 3] = [
+        QualityProfile::Tracking,
+        QualityProfile::Mixing,
+        QualityProfile::Render,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            QualityProfile::Tracking => "Tracking",
+            QualityProfile::Mixing => "Mixing",
+            QualityProfile::Render => "Render",
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            QualityProfile::Tracking => 0,
+            QualityProfile::Mixing => 1,
+            QualityProfile::Render => 2,
+        }
+    }
+}
+
+/// What one profile runs with
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ProfileSettings {
+    /// 1, 2, 4 or 8
+    pub oversampling: u32,
+    /// First order ADAA on the Push shaper
+    pub hq_shaper: bool,
+}
+
+impl ProfileSettings {
+    pub const OVERSAMPLING_FACTORS: [u32; 4] = [1, 2, 4, 8];
+
+    pub fn oversampling(self) -> Oversampling {
+        match self.oversampling {
+            0 | 1 => Oversampling::X1,
+            2 | 3 => Oversampling::X2,
+            4..=7 => Oversampling::X4,
+            _ => Oversampling::X8,
+        }
+    }
+
+    // Two bits of oversampling stages and one for the shaper
+    fn pack(self) -> u32 {
+        self.oversampling().factor().trailing_zeros() | ((self.hq_shaper as u32) << 2)
+    }
+
+    fn unpack(bits: u32) -> Self {
+        Self {
+            oversampling: 1 << (bits & 0b11),
+            hq_shaper: bits & 0b100 != 0,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QualityProfiles {
+    /// Tracking, Mixing, Render
+    pub profiles: [ProfileSettings; 3],
+}
+
+impl Default for QualityProfiles {
+    fn default() -> Self {
+        Self {
+            profiles: [
+                ProfileSettings {
+                    oversampling: 1,
+                    hq_shaper: false,
+                },
+                ProfileSettings {
+                    oversampling: 2,
+                    hq_shaper: true,
+                },
+                ProfileSettings {
+                    oversampling: 8,
+                    hq_shaper: true,
+                },
+            ],
+        }
+    }
+}
+
+impl QualityProfiles {
+    pub fn get(&self, profile: QualityProfile) -> ProfileSettings {
+        self.profiles[profile.index()]
+    }
+
+    pub fn get_mut(&mut self, profile: QualityProfile) -> &mut ProfileSettings {
+        &mut self.profiles[profile.index()]
+    }
+
+    /// Three bits per profile so the audio thread can read them from an atomic
+    pub fn pack(&self) -> u32 {
+        QualityProfile::ALL
+            .iter()
+            .map(|profile| self.get(*profile).pack() << (profile.index() * 3))
+            .fold(0, |packed, bits| packed | bits)
+    }
+}
+
+/// One profile's settings out of `QualityProfiles::pack`
+pub fn unpack(packed: u32, profile: QualityProfile) -> ProfileSettings {
+    ProfileSettings::unpack((packed >> (profile.index() * 3)) & 0b111)
+}
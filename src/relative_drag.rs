@@ -0,0 +1,92 @@
+// relative_drag.rs - Ardura 2024
+// Alt+dragging one slider of a bank shifts the rest of the bank by the same amount so the
+// relationship between the sliders is kept while the overall character moves
+
+use nih_plug::prelude::{FloatParam, Param, ParamSetter};
+use nih_plug_egui::egui::{self, Response, Ui};
+
+#[derive(Clone)]
+struct RelativeDrag {
+    dragged: usize,
+    start_values: Vec<f32>,
+}
+
+/// The normalized value the slider sets for the pointer position, the same mapping and step
+/// rounding it uses. Reading the param back would lag in hosts that apply GUI changes later.
+/// Shift's fine drag and the reset click don't follow the pointer, those leave the bank alone.
+fn written_value(ui: &Ui, response: &Response, param: &FloatParam) -> Option<f32> {
+    if ui.input(|i| i.modifiers.shift || i.modifiers.command) {
+        return None;
+    }
+    let pointer = response.interact_pointer_pos()?;
+    let proportion = egui::emath::remap_clamp(pointer.x, response.rect.x_range(), 0.0..=1.0);
+    Some(param.preview_normalized(param.preview_plain(proportion)))
+}
+
+/// Call this right after adding the slider at `dragged` in `bank`, with `value_before` read from
+/// the dragged param before the slider was added. The rest of the bank follows the value the
+/// slider writes, so the click jump and drag scaling carry over exactly.
+pub fn bank_relative_drag(
+    ui: &Ui,
+    response: &Response,
+    bank: &[&FloatParam],
+    dragged: usize,
+    value_before: f32,
+    setter: &ParamSetter,
+    bank_name: &str,
+) {
+    let id = egui::Id::new(("relative_drag", bank_name));
+    let alt_held = ui.input(|i| i.modifiers.alt);
+    let active: Option<RelativeDrag> = ui.memory(|mem| mem.data.get_temp(id));
+
+    // Another slider in this bank owns the current drag
+    if let Some(state) = &active {
+        if state.dragged != dragged {
+            return;
+        }
+    }
+
+    if response.dragged() && alt_held {
+        let mut state = match active {
+            Some(state) => state,
+            None => {
+                for (index, param) in bank.iter().enumerate() {
+                    if index != dragged {
+                        setter.begin_set_parameter(*param);
+                    }
+                }
+                let mut start_values: Vec<f32> = bank
+                    .iter()
+                    .map(|param| param.unmodulated_normalized_value())
+                    .collect();
+                start_values[dragged] = value_before;
+                RelativeDrag {
+                    dragged,
+                    start_values,
+                }
+            }
+        };
+
+        if let Some(value) = written_value(ui, response, bank[dragged]) {
+            let delta = value - state.start_values[dragged];
+            for (index, param) in bank.iter().enumerate() {
+                if index != dragged {
+                    setter.set_parameter_normalized(
+                        *param,
+                        (state.start_values[index] + delta).clamp(0.0, 1.0),
+                    );
+                }
+            }
+        }
+
+        ui.memory_mut(|mem| mem.data.insert_temp(id, state));
+    } else if active.is_some() {
+        // Either the drag ended or alt was let go
+        for (index, param) in bank.iter().enumerate() {
+            if index != dragged {
+                setter.end_set_parameter(*param);
+            }
+        }
+        ui.memory_mut(|mem| mem.data.remove::<RelativeDrag>(id));
+    }
+}
@@ -0,0 +1,90 @@
+// safety_limiter.rs - Ardura 2024
+// Protective output limiter that engages by itself when the output starts clipping hard,
+// mostly to save monitors from runaway coefficient/multiplier settings
+
+/// Consecutive samples over 0 dBFS before the limiter engages
+pub const CLIP_STORM_SAMPLES: u32 = 64;
+
+/// A single sample over this (+6 dBFS) engages the limiter right away
+const HOT_SAMPLE_LEVEL: f32 = 2.0;
+
+/// Output ceiling while engaged, just under 0 dBFS
+const CEILING: f32 = 0.977;
+
+const RELEASE_MS: f32 = 80.0;
+
+pub struct SafetyLimiter {
+    engaged: bool,
+    over_count: u32,
+    gain: f32,
+    release_coeff: f32,
+}
+
+impl SafetyLimiter {
+    pub fn new() -> Self {
+        Self {
+            engaged: false,
+            over_count: 0,
+            gain: 1.0,
+            release_coeff: 0.0,
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.release_coeff = (-1.0 / (RELEASE_MS * 0.001 * sample_rate)).exp();
+    }
+
+    /// Drop the latch, called when the user dismisses the warning
+    pub fn disengage(&mut self) {
+        self.engaged = false;
+        self.over_count = 0;
+        self.gain = 1.0;
+    }
+
+    /// Watch one stereo frame for a clip storm and limit it when engaged.
+    /// Returns true on the sample the limiter engaged.
+    pub fn process(&mut self, frame: &mut [f32; 2]) -> bool {
+        let mut peak: f32 = 0.0;
+        let mut finite = true;
+        for sample in frame.iter() {
+            finite &= sample.is_finite();
+            peak = peak.max(sample.abs());
+        }
+
+        let mut just_engaged = false;
+        if !self.engaged {
+            if peak > 1.0 {
+                self.over_count += 1;
+            } else {
+                self.over_count = 0;
+            }
+            if !finite || peak > HOT_SAMPLE_LEVEL || self.over_count > CLIP_STORM_SAMPLES {
+                self.engaged = true;
+                just_engaged = true;
+            }
+        }
+
+        if !self.engaged {
+            return false;
+        }
+
+        // Never pass garbage on to the speakers
+        if !finite {
+            *frame = [0.0; 2];
+            return just_engaged;
+        }
+
+        // Instant attack, smooth release, stereo linked
+        let target = if peak > CEILING { CEILING / peak } else { 1.0 };
+        if target < self.gain {
+            self.gain = target;
+        } else {
+            self.gain = target + (self.gain - target) * self.release_coeff;
+        }
+        for sample in frame.iter_mut() {
+            *sample = (*sample * self.gain).clamp(-CEILING, CEILING);
+        }
+
+        just_engaged
+    }
+}
@@ -0,0 +1,35 @@
+// status_bar.rs - Ardura 2024
+// Strip along the bottom of the editor describing whatever param control is under the mouse
+
+use nih_plug::prelude::Param;
+use nih_plug_egui::egui::{Context, Id, Response};
+
+fn status_id() -> Id {
+    Id::new("gladedesk_status_bar")
+}
+
+/// Pass a param widget's response through this so the status bar can describe it when hovered
+pub fn track<P: Param>(response: Response, param: &P) -> Response {
+    track_labeled(response, param, param.name())
+}
+
+/// Same as `track()` for params whose name is too short to stand on its own, like the bank
+/// sliders named after their tap number
+pub fn track_labeled<P: Param>(response: Response, param: &P, label: &str) -> Response {
+    if response.hovered() {
+        let text = format!(
+            "{}: {}  (range {} to {})",
+            label,
+            param.normalized_value_to_string(param.modulated_normalized_value(), true),
+            param.normalized_value_to_string(0.0, true),
+            param.normalized_value_to_string(1.0, true),
+        );
+        response.ctx.data_mut(|data| data.insert_temp(status_id(), text));
+    }
+    response
+}
+
+/// Description of the control hovered last frame, cleared so it only lasts while hovered
+pub fn take(ctx: &Context) -> Option<String> {
+    ctx.data_mut(|data| data.remove_temp::<String>(status_id()))
+}
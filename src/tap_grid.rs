@@ -0,0 +1,170 @@
+// tap_grid.rs - Ardura 2024
+// Compact grid showing each tap's sign and weight. Click a cell to flip the sign of its
+// coefficient, drag vertically to change the weight. Works on the same params as the sliders.
+
+use nih_plug::prelude::{FloatParam, Param, ParamSetter};
+use nih_plug_egui::egui::{
+    vec2, Align2, Color32, FontId, Pos2, Rect, Response, Rounding, Sense, Stroke, Ui, Widget,
+};
+
+/// One pixel of vertical drag changes the tap weight by this much
+const WEIGHT_DRAG_MULTIPLIER: f32 = 0.002;
+
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct TapGrid<'a> {
+    bank: &'a [&'a FloatParam],
+    param_setter: &'a ParamSetter<'a>,
+    polarity: &'a [f32],
+    cell_size: f32,
+    positive_color: Color32,
+    negative_color: Color32,
+    background_color: Color32,
+    text_scale: f32,
+}
+
+impl<'a> TapGrid<'a> {
+    /// `polarity` is the fixed sign each tap is summed with in the console stage
+    pub fn for_bank(
+        bank: &'a [&'a FloatParam],
+        polarity: &'a [f32],
+        param_setter: &'a ParamSetter,
+    ) -> Self {
+        Self {
+            bank,
+            param_setter,
+            polarity,
+            cell_size: 40.0,
+            positive_color: Color32::GREEN,
+            negative_color: Color32::RED,
+            background_color: Color32::GRAY,
+            text_scale: 1.0,
+        }
+    }
+
+    pub fn cell_size(mut self, cell_size: f32) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    pub fn text_scale(mut self, text_scale: f32) -> Self {
+        self.text_scale = text_scale;
+        self
+    }
+
+    pub fn colors(mut self, positive: Color32, negative: Color32, background: Color32) -> Self {
+        self.positive_color = positive;
+        self.negative_color = negative;
+        self.background_color = background;
+        self
+    }
+}
+
+impl<'a> Widget for TapGrid<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let spacing = 4.0;
+        let count = self.bank.len() as f32;
+        let desired_size = vec2(
+            count * self.cell_size + (count - 1.0) * spacing,
+            self.cell_size,
+        );
+        let (outer_rect, mut response) = ui.allocate_exact_size(desired_size, Sense::hover());
+        let grid_id = ui.id().with("tap_grid");
+
+        for (index, param) in self.bank.iter().enumerate() {
+            let cell_rect = Rect::from_min_size(
+                Pos2::new(
+                    outer_rect.left() + index as f32 * (self.cell_size + spacing),
+                    outer_rect.top(),
+                ),
+                vec2(self.cell_size, self.cell_size),
+            );
+            let cell_response =
+                ui.interact(cell_rect, grid_id.with(index), Sense::click_and_drag());
+
+            let value = param.modulated_plain_value();
+            if cell_response.clicked() {
+                self.param_setter.begin_set_parameter(*param);
+                self.param_setter.set_parameter(*param, -value);
+                self.param_setter.end_set_parameter(*param);
+                response.mark_changed();
+            }
+            if cell_response.drag_started() {
+                self.param_setter.begin_set_parameter(*param);
+            }
+            if cell_response.dragged() {
+                let sign = if value < 0.0 { -1.0 } else { 1.0 };
+                let weight = (value.abs()
+                    - cell_response.drag_delta().y * WEIGHT_DRAG_MULTIPLIER)
+                    .clamp(0.0, 0.5);
+                self.param_setter.set_parameter(*param, sign * weight);
+                response.mark_changed();
+            }
+            if cell_response.drag_stopped() {
+                self.param_setter.end_set_parameter(*param);
+            }
+
+            if ui.is_rect_visible(cell_rect) {
+                // The effective tap is the coefficient times the fixed console polarity
+                let effective = value * self.polarity.get(index).copied().unwrap_or(1.0);
+                let color = if effective < 0.0 {
+                    self.negative_color
+                } else {
+                    self.positive_color
+                };
+
+                ui.painter().rect(
+                    cell_rect,
+                    Rounding::from(4.0),
+                    self.background_color,
+                    Stroke::new(1.0, Color32::BLACK),
+                );
+
+                // Bar grows up for positive taps and down for negative ones
+                let half = cell_rect.height() / 2.0;
+                let bar_height = (effective.abs() / 0.5).clamp(0.0, 1.0) * (half - 2.0);
+                let bar_rect = if effective < 0.0 {
+                    Rect::from_min_max(
+                        Pos2::new(cell_rect.left() + 4.0, cell_rect.center().y),
+                        Pos2::new(cell_rect.right() - 4.0, cell_rect.center().y + bar_height),
+                    )
+                } else {
+                    Rect::from_min_max(
+                        Pos2::new(cell_rect.left() + 4.0, cell_rect.center().y - bar_height),
+                        Pos2::new(cell_rect.right() - 4.0, cell_rect.center().y),
+                    )
+                };
+                ui.painter().rect_filled(bar_rect, Rounding::ZERO, color);
+                ui.painter().line_segment(
+                    [
+                        Pos2::new(cell_rect.left(), cell_rect.center().y),
+                        Pos2::new(cell_rect.right(), cell_rect.center().y),
+                    ],
+                    Stroke::new(1.0, Color32::BLACK),
+                );
+
+                ui.painter().text(
+                    cell_rect.center_top() + vec2(0.0, 2.0),
+                    Align2::CENTER_TOP,
+                    if effective < 0.0 { "-" } else { "+" },
+                    FontId::monospace(11.0 * self.text_scale),
+                    Color32::BLACK,
+                );
+                ui.painter().text(
+                    cell_rect.center_bottom() - vec2(0.0, 2.0),
+                    Align2::CENTER_BOTTOM,
+                    format!("{:.2}", effective.abs()),
+                    FontId::monospace(9.0 * self.text_scale),
+                    Color32::BLACK,
+                );
+            }
+
+            cell_response.on_hover_text(format!(
+                "Tap {}: {:+.6}\nClick to flip sign, drag up/down for weight",
+                index + 1,
+                value
+            ));
+        }
+
+        response
+    }
+}
@@ -0,0 +1,103 @@
+// tap_heatmap.rs - Ardura 2024
+// Rolling heat map of how the signal energy is spread across the taps, newest column on the right
+
+use std::collections::VecDeque;
+
+use nih_plug_egui::egui::{
+    vec2, Color32, Pos2, Rect, Response, Rounding, Sense, Stroke, Ui, Vec2, Widget,
+};
+
+use crate::engine::TAPS;
+
+/// How many columns of history the heat map keeps
+pub const HISTORY_LENGTH: usize = 120;
+
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct TapHeatmap<'a> {
+    history: &'a VecDeque<[f32; TAPS]>,
+    desired_size: Vec2,
+    color: Color32,
+    background_color: Color32,
+}
+
+impl<'a> TapHeatmap<'a> {
+    /// Each entry in `history` holds the energy per tap for one update
+    pub fn new(history: &'a VecDeque<[f32; TAPS]>) -> Self {
+        Self {
+            history,
+            desired_size: vec2(256.0, 64.0),
+            color: Color32::YELLOW,
+            background_color: Color32::BLACK,
+        }
+    }
+
+    pub fn desired_size(mut self, desired_size: Vec2) -> Self {
+        self.desired_size = desired_size;
+        self
+    }
+
+    /// Color for a tap holding all of the energy
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn background_color(mut self, color: Color32) -> Self {
+        self.background_color = color;
+        self
+    }
+}
+
+impl<'a> Widget for TapHeatmap<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let (rect, response) = ui.allocate_exact_size(self.desired_size, Sense::hover());
+
+        if ui.is_rect_visible(rect) {
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, Rounding::from(2.0), self.background_color);
+
+            // A single column (reduced motion) gets the whole width
+            let column_width = if self.history.len() <= 1 {
+                rect.width()
+            } else {
+                rect.width() / HISTORY_LENGTH as f32
+            };
+            let row_height = rect.height() / TAPS as f32;
+
+            for (age, column) in self.history.iter().rev().enumerate() {
+                let right = rect.right() - age as f32 * column_width;
+                if right <= rect.left() {
+                    break;
+                }
+
+                let total: f32 = column.iter().sum();
+                if total <= f32::EPSILON || !total.is_finite() {
+                    continue;
+                }
+
+                for (tap, energy) in column.iter().enumerate() {
+                    // Square root so quieter taps still show up
+                    let share = (energy / total).sqrt().clamp(0.0, 1.0);
+                    let cell = Rect::from_min_max(
+                        Pos2::new(right - column_width, rect.top() + tap as f32 * row_height),
+                        Pos2::new(right, rect.top() + (tap + 1) as f32 * row_height),
+                    );
+                    painter.rect_filled(
+                        cell,
+                        Rounding::ZERO,
+                        lerp_color(self.background_color, self.color, share),
+                    );
+                }
+            }
+
+            painter.rect_stroke(rect, Rounding::from(2.0), Stroke::new(1.0, Color32::BLACK));
+        }
+
+        response
+    }
+}
+
+fn lerp_color(from: Color32, to: Color32, t: f32) -> Color32 {
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t) as u8;
+    Color32::from_rgb(mix(from.r(), to.r()), mix(from.g(), to.g()), mix(from.b(), to.b()))
+}
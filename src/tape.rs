@@ -0,0 +1,114 @@
+// tape.rs - Ardura 2024
+// Tape hysteresis stage for after the console: a simplified Jiles-Atherton magnetisation model
+// stepped once per sample with second order Runge-Kutta
+
+// Fixed parts of the model, saturation magnetisation and the usual coercivity and coupling
+const MS: f64 = 1.0;
+const K: f64 = 0.47875;
+const ALPHA: f64 = 1.6e-3;
+// Damping on the field derivative, pure trapezoidal (1.0) rings at high frequencies
+const DERIVATIVE_ALPHA: f64 = 0.75;
+
+pub struct TapeHysteresis {
+    t: f64,
+    // Shape of the magnetisation curve, smaller is harder
+    a: f64,
+    // Reversible share of the magnetisation, the tape bias
+    c: f64,
+    m: f64,
+    h: f64,
+    h_deriv: f64,
+    // Small signal gain of the model, divided back out so drive doesn't jump the level
+    makeup: f64,
+}
+
+impl TapeHysteresis {
+    pub fn new() -> Self {
+        let mut tape = Self {
+            t: 1.0 / 44100.0,
+            a: 1.0,
+            c: 0.5,
+            m: 0.0,
+            h: 0.0,
+            h_deriv: 0.0,
+            makeup: 1.0,
+        };
+        tape.set_params(0.5, 0.5);
+        tape
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.t = 1.0 / sample_rate as f64;
+    }
+
+    /// `drive` 0-1 hardens the curve, `bias` 0-1 goes from wide hysteresis to nearly linear
+    pub fn set_params(&mut self, drive: f32, bias: f32) {
+        self.a = MS / (1.0 + 6.0 * drive as f64);
+        self.c = 0.1 + 0.85 * bias as f64;
+        // Slope at rest is only the reversible part, Langevin'(0) is 1/3
+        let slope = self.c * MS / (3.0 * self.a);
+        let gain = slope / (1.0 - ALPHA * slope);
+        self.makeup = 1.0 / gain.max(0.05);
+    }
+
+    pub fn reset(&mut self) {
+        self.m = 0.0;
+        self.h = 0.0;
+        self.h_deriv = 0.0;
+    }
+
+    pub fn process(&mut self, input: f32) -> f32 {
+        let h = input as f64;
+        let h_deriv = (1.0 + DERIVATIVE_ALPHA) / self.t * (h - self.h)
+            - DERIVATIVE_ALPHA * self.h_deriv;
+
+        let k1 = self.t * self.dm_dt(self.m, self.h, self.h_deriv);
+        let k2 = self.t
+            * self.dm_dt(
+                self.m + 0.5 * k1,
+                0.5 * (h + self.h),
+                0.5 * (h_deriv + self.h_deriv),
+            );
+        let m = self.m + k2;
+        // Start over rather than let a bad step stick
+        self.m = if m.is_finite() {
+            m.clamp(-MS, MS)
+        } else {
+            0.0
+        };
+        self.h = h;
+        self.h_deriv = h_deriv;
+
+        (self.m * self.makeup) as f32
+    }
+
+    fn dm_dt(&self, m: f64, h: f64, h_deriv: f64) -> f64 {
+        let q = (h + ALPHA * m) / self.a;
+        let (langevin, langevin_deriv) = langevin(q);
+        let m_diff = MS * langevin - m;
+        let delta = if h_deriv >= 0.0 { 1.0 } else { -1.0 };
+        // Irreversible part only moves while the field pushes the same way as the difference
+        let delta_m = if delta * m_diff > 0.0 { 1.0 } else { 0.0 };
+
+        let irreversible_denominator = (1.0 - self.c) * delta * K - ALPHA * m_diff;
+        let irreversible = if irreversible_denominator.abs() > 1.0e-9 {
+            (1.0 - self.c) * delta_m * m_diff / irreversible_denominator
+        } else {
+            0.0
+        };
+        let reversible = self.c * MS / self.a * langevin_deriv;
+        let denominator = 1.0 - self.c * ALPHA * MS / self.a * langevin_deriv;
+        (irreversible + reversible) * h_deriv / denominator
+    }
+}
+
+// Langevin function coth(x) - 1/x and its derivative, with the series near zero where both
+// terms blow up
+fn langevin(x: f64) -> (f64, f64) {
+    if x.abs() < 1.0e-4 {
+        (x / 3.0, 1.0 / 3.0)
+    } else {
+        let coth = 1.0 / x.tanh();
+        (coth - 1.0 / x, 1.0 / (x * x) - coth * coth + 1.0)
+    }
+}
@@ -0,0 +1,78 @@
+// transformer.rs - Ardura 2024
+// Output transformer emulation: the lows saturate the core long before the highs do, which is
+// the thump desk emulations are known for
+
+use crate::filters::{Biquad, BUTTERWORTH_Q};
+
+// Corner of the low split for the smallest and the largest core
+const SMALL_CORE_HZ: f32 = 400.0;
+const LARGE_CORE_HZ: f32 = 60.0;
+
+// Share of the low band drive the highs get
+const HIGH_DRIVE_SHARE: f32 = 0.2;
+
+pub struct Transformer {
+    lowpass: Biquad,
+    sample_rate: f32,
+    core: f32,
+    drive: f32,
+}
+
+impl Transformer {
+    pub fn new() -> Self {
+        let mut transformer = Self {
+            lowpass: Biquad::new(),
+            sample_rate: 44100.0,
+            core: 0.5,
+            drive: 0.5,
+        };
+        transformer.design();
+        transformer
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        if sample_rate != self.sample_rate {
+            self.sample_rate = sample_rate;
+            self.design();
+        }
+    }
+
+    /// `core` 0-1 from a small core (saturates up into the low mids, and early) to a large one
+    /// (only the deep lows, and later). Only redesigns the split when it actually moved.
+    pub fn set_core(&mut self, core: f32) {
+        if core != self.core {
+            self.core = core;
+            self.design();
+        }
+    }
+
+    /// `drive` 0-1
+    pub fn set_drive(&mut self, drive: f32) {
+        self.drive = drive;
+    }
+
+    pub fn reset(&mut self) {
+        self.lowpass.reset();
+    }
+
+    pub fn process(&mut self, input: f32) -> f32 {
+        let low = self.lowpass.process(input);
+        // Complementary split so it sums back to the input while nothing saturates
+        let high = input - low;
+        // Smaller cores run out of room sooner
+        let low_gain = 1.0 + self.drive * (4.0 + 8.0 * (1.0 - self.core));
+        let high_gain = 1.0 + (low_gain - 1.0) * HIGH_DRIVE_SHARE;
+        saturate(low, low_gain) + saturate(high, high_gain)
+    }
+
+    fn design(&mut self) {
+        let cutoff = SMALL_CORE_HZ * (LARGE_CORE_HZ / SMALL_CORE_HZ).powf(self.core);
+        self.lowpass
+            .set_lowpass(cutoff, BUTTERWORTH_Q, self.sample_rate);
+    }
+}
+
+// Unity small signal gain, compresses once `gain` pushes the signal into the knee
+fn saturate(x: f32, gain: f32) -> f32 {
+    (x * gain).tanh() / gain
+}
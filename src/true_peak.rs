@@ -0,0 +1,161 @@
+// true_peak.rs - Ardura 2024
+// Oversampled true-peak limiter for the very end of the chain, so the desk can sit on a master
+// bus without another limiter after it. Stereo linked with a short lookahead.
+
+/// Lookahead in ms, reported to the host as latency while the limiter is on
+const LOOKAHEAD_MS: f32 = 1.0;
+
+/// Inter-sample peaks are checked at 4x
+const PHASES: usize = 4;
+const PHASE_TAPS: usize = 8;
+
+pub struct TruePeakLimiter {
+    // Interpolation filters for the three in between phases, the fourth is the sample itself
+    phase_coeffs: [[f32; PHASE_TAPS]; PHASES - 1],
+    // Recent input per channel for the interpolation
+    history: [[f32; PHASE_TAPS]; 2],
+    // Lookahead delay per channel and the gain each frame wants
+    delay: [Vec<f32>; 2],
+    targets: Vec<f32>,
+    pos: usize,
+    lookahead: usize,
+    max_lookahead: usize,
+    gain: f32,
+    attack_coeff: f32,
+    release_coeff: f32,
+    sample_rate: f32,
+}
+
+impl TruePeakLimiter {
+    pub fn new() -> Self {
+        let mut limiter = Self {
+            phase_coeffs: interpolation_coeffs(),
+            history: [[0.0; PHASE_TAPS]; 2],
+            delay: [Vec::new(), Vec::new()],
+            targets: Vec::new(),
+            pos: 0,
+            lookahead: 0,
+            max_lookahead: 0,
+            gain: 1.0,
+            attack_coeff: 0.0,
+            release_coeff: 0.0,
+            sample_rate: 44100.0,
+        };
+        limiter.set_sample_rate(44100.0);
+        limiter
+    }
+
+    /// Allocates the lookahead, call from initialize()
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        self.max_lookahead = Self::lookahead_samples(sample_rate) as usize;
+        self.delay = [
+            vec![0.0; self.max_lookahead + 1],
+            vec![0.0; self.max_lookahead + 1],
+        ];
+        self.targets = vec![1.0; self.max_lookahead + 1];
+        self.set_lookahead(true);
+        self.reset();
+    }
+
+    /// Host samples of lookahead at this rate
+    pub fn lookahead_samples(sample_rate: f32) -> u32 {
+        (LOOKAHEAD_MS * 0.001 * sample_rate).ceil() as u32
+    }
+
+    /// Zero Latency runs the limiter without lookahead, the final clamp still holds the ceiling
+    pub fn set_lookahead(&mut self, on: bool) {
+        let lookahead = if on { self.max_lookahead } else { 0 };
+        if lookahead != self.lookahead {
+            self.lookahead = lookahead;
+            self.reset();
+        }
+        // Reaches the target within the lookahead
+        self.attack_coeff = (-5.0 / self.lookahead.max(1) as f32).exp();
+    }
+
+    pub fn set_release(&mut self, release_ms: f32) {
+        self.release_coeff = (-1.0 / (release_ms * 0.001 * self.sample_rate)).exp();
+    }
+
+    pub fn reset(&mut self) {
+        self.history = [[0.0; PHASE_TAPS]; 2];
+        for delay in self.delay.iter_mut() {
+            delay.fill(0.0);
+        }
+        self.targets.fill(1.0);
+        self.pos = 0;
+        self.gain = 1.0;
+    }
+
+    /// Limit one stereo frame to `ceiling` (linear), delayed by the lookahead
+    pub fn process(&mut self, frame: &mut [f32; 2], ceiling: f32) {
+        let mut peak: f32 = 0.0;
+        for (history, &sample) in self.history.iter_mut().zip(frame.iter()) {
+            history.copy_within(0..PHASE_TAPS - 1, 1);
+            history[0] = sample;
+            peak = peak.max(true_peak(history, &self.phase_coeffs));
+        }
+        let target = if peak > ceiling { ceiling / peak } else { 1.0 };
+
+        // Delay the audio and keep the lowest gain any frame inside the lookahead asks for
+        let len = self.lookahead + 1;
+        self.targets[self.pos] = target;
+        for (delay, &sample) in self.delay.iter_mut().zip(frame.iter()) {
+            delay[self.pos] = sample;
+        }
+        let wanted = self.targets[..len]
+            .iter()
+            .fold(1.0f32, |lowest, &target| lowest.min(target));
+        // The oldest slot is now the frame from `lookahead` frames ago
+        self.pos = (self.pos + 1) % len;
+
+        let coeff = if wanted < self.gain {
+            self.attack_coeff
+        } else {
+            self.release_coeff
+        };
+        self.gain = wanted + (self.gain - wanted) * coeff;
+
+        for (sample, delay) in frame.iter_mut().zip(self.delay.iter()) {
+            *sample = (delay[self.pos] * self.gain).clamp(-ceiling, ceiling);
+        }
+    }
+}
+
+// Largest of the sample and the three interpolated points before it
+fn true_peak(history: &[f32; PHASE_TAPS], phase_coeffs: &[[f32; PHASE_TAPS]; PHASES - 1]) -> f32 {
+    phase_coeffs
+        .iter()
+        .map(|coeffs| {
+            coeffs
+                .iter()
+                .zip(history.iter())
+                .map(|(coeff, sample)| coeff * sample)
+                .sum::<f32>()
+                .abs()
+        })
+        .fold(history[PHASE_TAPS / 2].abs(), f32::max)
+}
+
+// Windowed sinc taps for each fractional position between history[3] and history[4]
+fn interpolation_coeffs() -> [[f32; PHASE_TAPS]; PHASES - 1] {
+    std::array::from_fn(|phase| {
+        let fraction = (phase + 1) as f32 / PHASES as f32;
+        let mut coeffs: [f32; PHASE_TAPS] = std::array::from_fn(|tap| {
+            // history[0] is the newest sample
+            let offset = (PHASE_TAPS / 2) as f32 - tap as f32 - fraction;
+            let sinc = if offset == 0.0 {
+                1.0
+            } else {
+                (std::f32::consts::PI * offset).sin() / (std::f32::consts::PI * offset)
+            };
+            let window =
+                0.5 + 0.5 * (std::f32::consts::PI * offset / (PHASE_TAPS / 2) as f32).cos();
+            sinc * window
+        });
+        let sum: f32 = coeffs.iter().sum();
+        coeffs.iter_mut().for_each(|coeff| *coeff /= sum);
+        coeffs
+    })
+}
@@ -87,6 +87,13 @@ impl<'a, P: Param> SliderRegion<'a, P> {
             .set_parameter(self.param, self.param.default_plain_value());
     }
 
+    /// Reset as its own gesture so the host records it as a single automation point
+    fn reset_param_gesture(&self) {
+        self.param_setter.begin_set_parameter(self.param);
+        self.reset_param();
+        self.param_setter.end_set_parameter(self.param);
+    }
+
     fn granular_drag(&self, ui: &Ui, drag_delta: Vec2) {
         // Remember the intial position when we started with the granular drag. This value gets
         // reset whenever we have a normal itneraction with the slider.
@@ -134,12 +141,11 @@ impl<'a, P: Param> SliderRegion<'a, P> {
             self.param_setter.begin_set_parameter(self.param);
             Self::set_drag_amount_memory(ui, 0.0);
         }
-        if let Some(_clicked_pos) = response.interact_pointer_pos() {
-            if ui.input(|mem| mem.modifiers.command) {
-                // Like double clicking, Ctrl+Click should reset the parameter
-                self.reset_param();
-                response.mark_changed();
-            } else if ui.input(|mem| mem.modifiers.shift) {
+        // Only move the value inside the gesture begun above, a plain press used to set the
+        // parameter before the host was told anything was being edited
+        let command = ui.input(|mem| mem.modifiers.command);
+        if response.dragged_by(egui::PointerButton::Primary) && !command {
+            if ui.input(|mem| mem.modifiers.shift) {
                 // And shift dragging should switch to a more granular input method
                 self.granular_drag(ui, response.drag_delta());
                 response.mark_changed();
@@ -149,8 +155,9 @@ impl<'a, P: Param> SliderRegion<'a, P> {
                 //Self::set_drag_amount_memory(ui, 0.0);
             }
         }
-        if response.double_clicked() {
-            self.reset_param();
+        // Like double clicking, Ctrl+Click should reset the parameter
+        if response.double_clicked() || (response.clicked() && command) {
+            self.reset_param_gesture();
             response.mark_changed();
         }
         if response.drag_stopped() {
@@ -998,6 +1005,16 @@ fn get_arc_points(
         .collect()
 }
 
+/// Parameter entries for a right click menu. nih-plug doesn't expose the host's own parameter
+/// menu to plugin GUIs, so the common entries are drawn here instead.
+pub fn param_menu<P: Param>(ui: &mut Ui, param: &P, param_setter: &ParamSetter) {
+    ui.label(format!("{}: {}", param.name(), param));
+    if ui.button("Reset to default").clicked() {
+        SliderRegion::new(param, param_setter).reset_param_gesture();
+        ui.close_menu();
+    }
+}
+
 // Moved lerp to this file to reduce dependencies - Ardura
 pub fn lerp<T>(start: T, end: T, t: f32) -> T
 where
@@ -0,0 +1,118 @@
+// user_config.rs - Ardura 2024
+// Per-user settings shared by every instance and session, kept as JSON in the user's config
+// folder: the favorite values stored from a control's right click menu and whether presets get
+// loudness normalized.
+
+use nih_plug::prelude::{Param, ParamSetter};
+use nih_plug_egui::egui::{Button, Ui};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf, sync::Mutex, sync::OnceLock};
+
+/// Favorites kept per control
+pub const MAX_FAVORITES: usize = 3;
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserConfig {
+    /// Normalized values by param id, oldest first
+    pub favorites: BTreeMap<String, Vec<f32>>,
+    /// Trim every instance by the loudness its state was measured at
+    pub normalize_presets: bool,
+}
+
+// Loaded on first use, every open editor in the process shares it
+static CONFIG: OnceLock<Mutex<UserConfig>> = OnceLock::new();
+
+fn config_path() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| {
+            PathBuf::from(home)
+                .join("Library")
+                .join("Application Support")
+        })
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    base.map(|base| base.join("GladeDesk").join("user.json"))
+}
+
+fn config() -> &'static Mutex<UserConfig> {
+    CONFIG.get_or_init(|| {
+        // A missing or broken file just starts over with nothing stored
+        let loaded = config_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        Mutex::new(loaded)
+    })
+}
+
+// Best effort, a read only config folder only costs the favorites next session
+fn save(config: &UserConfig) {
+    let Some(path) = config_path() else {
+        return;
+    };
+    if let Some(folder) = path.parent() {
+        let _ = std::fs::create_dir_all(folder);
+    }
+    if let Ok(text) = serde_json::to_string_pretty(config) {
+        let _ = std::fs::write(path, text);
+    }
+}
+
+pub fn normalize_presets() -> bool {
+    config().lock().unwrap().normalize_presets
+}
+
+pub fn set_normalize_presets(normalize: bool) {
+    let mut config = config().lock().unwrap();
+    config.normalize_presets = normalize;
+    save(&config);
+}
+
+/// Context menu section listing a control's stored values, plus storing and clearing them.
+/// `id` is the param's `#[id]`, names aren't unique enough to key on.
+pub fn favorites_menu<P: Param>(ui: &mut Ui, param: &P, id: &str, setter: &ParamSetter) {
+    let mut config = config().lock().unwrap();
+    let key = id.to_string();
+    let stored = config.favorites.get(&key).cloned().unwrap_or_default();
+    ui.label("Favorites");
+    for normalized in stored.iter() {
+        if ui
+            .button(param.normalized_value_to_string(*normalized, true))
+            .clicked()
+        {
+            setter.begin_set_parameter(param);
+            setter.set_parameter_normalized(param, *normalized);
+            setter.end_set_parameter(param);
+            ui.close_menu();
+        }
+    }
+
+    let current = param.unmodulated_normalized_value();
+    let already_stored = stored
+        .iter()
+        .any(|normalized| (normalized - current).abs() < 1.0e-6);
+    if ui
+        .add_enabled(!already_stored, Button::new("Store current"))
+        .on_hover_text("Keeps the three most recent, the oldest goes first")
+        .clicked()
+    {
+        let favorites = config.favorites.entry(key.clone()).or_default();
+        favorites.push(current);
+        if favorites.len() > MAX_FAVORITES {
+            favorites.remove(0);
+        }
+        save(&config);
+        ui.close_menu();
+    }
+    if !stored.is_empty() && ui.button("Clear favorites").clicked() {
+        config.favorites.remove(&key);
+        save(&config);
+        ui.close_menu();
+    }
+}
@@ -0,0 +1,51 @@
+// viz_buffers.rs - Ardura 2024
+// Analyzer and scope histories that only exist while an editor is open. The editor allocates
+// them when its window opens and the background executor drops them once it closes, so
+// headless instances carry none and the audio thread never frees memory.
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::{engine, mod_scope, tap_heatmap};
+
+pub struct VizBuffers {
+    pub tap_energy_history: VecDeque<[f32; engine::TAPS]>,
+    pub mod_history: VecDeque<f32>,
+}
+
+impl VizBuffers {
+    pub fn new() -> Self {
+        Self {
+            tap_energy_history: VecDeque::with_capacity(tap_heatmap::HISTORY_LENGTH),
+            mod_history: VecDeque::with_capacity(mod_scope::HISTORY_LENGTH),
+        }
+    }
+}
+
+/// Shared between the editor and the background executor, the audio thread never locks it
+pub struct VizStore {
+    buffers: Mutex<Option<VizBuffers>>,
+}
+
+impl VizStore {
+    pub fn new() -> Self {
+        Self {
+            buffers: Mutex::new(None),
+        }
+    }
+
+    /// Editor window opened
+    pub fn open(&self) {
+        self.lock().get_or_insert_with(VizBuffers::new);
+    }
+
+    /// Editor closed, call from the background executor
+    pub fn release(&self) {
+        let released = self.lock().take();
+        drop(released);
+    }
+
+    pub fn lock(&self) -> MutexGuard<'_, Option<VizBuffers>> {
+        self.buffers.lock().unwrap()
+    }
+}
@@ -0,0 +1,122 @@
+// wav.rs - Ardura 2024
+// Just enough WAV reading/writing for offline renders: PCM 16/24/32 bit and 32 bit float in,
+// 32 bit float out
+
+use std::{fs, path::Path};
+
+const FORMAT_PCM: u16 = 1;
+const FORMAT_FLOAT: u16 = 3;
+const FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// Deinterleaved audio, one Vec per channel
+pub struct WavData {
+    pub sample_rate: u32,
+    pub channels: Vec<Vec<f32>>,
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+pub fn read_wav(path: &Path) -> Result<WavData, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Couldn't read {}: {e}", path.display()))?;
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(format!("{} is not a WAV file", path.display()));
+    }
+
+    let mut format = None;
+    let mut data = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let size = read_u32(&bytes, pos + 4) as usize;
+        let body = pos + 8;
+        let end = (body + size).min(bytes.len());
+        match id {
+            b"fmt " if size >= 16 => {
+                if body + size > bytes.len() {
+                    return Err(format!("{} has a truncated fmt chunk", path.display()));
+                }
+                let mut tag = read_u16(&bytes, body);
+                // Extensible keeps the real format in the first two bytes of the sub format GUID
+                if tag == FORMAT_EXTENSIBLE && size >= 26 {
+                    tag = read_u16(&bytes, body + 24);
+                }
+                format = Some((
+                    tag,
+                    read_u16(&bytes, body + 2) as usize,
+                    read_u32(&bytes, body + 4),
+                    read_u16(&bytes, body + 14),
+                ));
+            }
+            b"data" => data = Some(&bytes[body..end]),
+            _ => (),
+        }
+        // Chunks are padded to an even length
+        pos = body + size + (size & 1);
+    }
+
+    let (tag, channel_count, sample_rate, bits) =
+        format.ok_or_else(|| "WAV file has no fmt chunk".to_string())?;
+    let data = data.ok_or_else(|| "WAV file has no data chunk".to_string())?;
+    if channel_count == 0 {
+        return Err("WAV file has no channels".to_string());
+    }
+    if sample_rate == 0 {
+        return Err("WAV file has a sample rate of 0".to_string());
+    }
+
+    let decode: fn(&[u8]) -> f32 = match (tag, bits) {
+        (FORMAT_PCM, 16) => |b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0,
+        (FORMAT_PCM, 24) => |b| (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f32 / 8388608.0,
+        (FORMAT_PCM, 32) => |b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2147483648.0,
+        (FORMAT_FLOAT, 32) => |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+        _ => return Err(format!("Unsupported WAV format {tag} at {bits} bits")),
+    };
+
+    let frame_bytes = channel_count * bits as usize / 8;
+    let frames = data.len() / frame_bytes;
+    let mut channels = vec![Vec::with_capacity(frames); channel_count];
+    for frame in data.chunks_exact(frame_bytes) {
+        for (channel, sample) in channels.iter_mut().zip(frame.chunks_exact(bits as usize / 8)) {
+            channel.push(decode(sample));
+        }
+    }
+
+    Ok(WavData {
+        sample_rate,
+        channels,
+    })
+}
+
+pub fn write_wav(path: &Path, wav: &WavData) -> Result<(), String> {
+    let channel_count = wav.channels.len();
+    let frames = wav.channels.first().map_or(0, |channel| channel.len());
+    let data_size = frames * channel_count * 4;
+
+    let mut bytes = Vec::with_capacity(44 + data_size);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_size as u32).to_le_bytes());
+    bytes.extend_from_slice(b"WAVE");
+    bytes.extend_from_slice(b"fmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&FORMAT_FLOAT.to_le_bytes());
+    bytes.extend_from_slice(&(channel_count as u16).to_le_bytes());
+    bytes.extend_from_slice(&wav.sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(wav.sample_rate * channel_count as u32 * 4).to_le_bytes());
+    bytes.extend_from_slice(&(channel_count as u16 * 4).to_le_bytes());
+    bytes.extend_from_slice(&32u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&(data_size as u32).to_le_bytes());
+    for frame in 0..frames {
+        for channel in wav.channels.iter() {
+            bytes.extend_from_slice(&channel[frame].to_le_bytes());
+        }
+    }
+
+    fs::write(path, bytes).map_err(|e| format!("Couldn't write {}: {e}", path.display()))
+}
@@ -0,0 +1,87 @@
+// wow_flutter.rs - Ardura 2024
+// Tape transport instability: a short delay line read at a position swung by a slow wow sine
+// with a faster, smaller flutter riding on top, which bends the pitch a little either way
+
+use std::f32::consts::TAU;
+
+/// Largest swing of the read position either side of the centre at full depth
+pub const MAX_DEPTH_MS: f32 = 2.0;
+
+// Flutter runs this many times faster than the wow and takes this share of the depth
+const FLUTTER_RATIO: f32 = 7.3;
+const FLUTTER_SHARE: f32 = 0.15;
+
+pub struct WowFlutter {
+    buffer: Vec<f32>,
+    write: usize,
+    wow_phase: f32,
+    flutter_phase: f32,
+    sample_rate: f32,
+    // Centre of the read position in whole samples, the delay the stage adds
+    center: f32,
+}
+
+impl WowFlutter {
+    pub fn new() -> Self {
+        let mut wow_flutter = Self {
+            buffer: Vec::new(),
+            write: 0,
+            wow_phase: 0.0,
+            flutter_phase: 0.0,
+            sample_rate: 44100.0,
+            center: 0.0,
+        };
+        wow_flutter.set_sample_rate(44100.0);
+        wow_flutter
+    }
+
+    /// Allocates the delay line, call outside of process()
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        // Two samples of headroom keep the interpolation taps inside the written history, whole
+        // samples so the delay can be reported as latency
+        self.center = (MAX_DEPTH_MS * 0.001 * sample_rate).ceil() + 2.0;
+        self.buffer = vec![0.0; (self.center * 2.0).ceil() as usize + 4];
+        self.write = 0;
+    }
+
+    /// Delay the read position swings around, the output is this late on average
+    pub fn latency_samples(&self) -> u32 {
+        self.center as u32
+    }
+
+    pub fn reset(&mut self) {
+        self.buffer.fill(0.0);
+        self.write = 0;
+        self.wow_phase = 0.0;
+        self.flutter_phase = 0.0;
+    }
+
+    /// `rate_hz` is the wow speed, `depth` 0-1 scales the swing up to `MAX_DEPTH_MS`
+    pub fn process(&mut self, input: f32, rate_hz: f32, depth: f32) -> f32 {
+        let len = self.buffer.len();
+        self.buffer[self.write] = input;
+
+        let wow = (self.wow_phase * TAU).sin();
+        let flutter = (self.flutter_phase * TAU).sin();
+        let swing = (1.0 - FLUTTER_SHARE) * wow + FLUTTER_SHARE * flutter;
+        let delay = self.center + swing * depth * (self.center - 2.0);
+
+        self.wow_phase = (self.wow_phase + rate_hz / self.sample_rate).fract();
+        self.flutter_phase =
+            (self.flutter_phase + rate_hz * FLUTTER_RATIO / self.sample_rate).fract();
+
+        // Four point Hermite around the read position, newest sample first
+        let whole = delay.floor() as usize;
+        let frac = delay - whole as f32;
+        let tap = |back: usize| self.buffer[(self.write + len - back) % len];
+        let (newer, current, older, oldest) =
+            (tap(whole - 1), tap(whole), tap(whole + 1), tap(whole + 2));
+        self.write = (self.write + 1) % len;
+
+        let c1 = 0.5 * (older - newer);
+        let c2 = newer - 2.5 * current + 2.0 * older - 0.5 * oldest;
+        let c3 = 0.5 * (oldest - newer) + 1.5 * (current - older);
+        ((c3 * frac + c2) * frac + c1) * frac + current
+    }
+}
@@ -0,0 +1,239 @@
+// fuzz.rs - Ardura 2024
+// Parameter fuzzing: random settings and random automation trajectories through the engine,
+// the output has to stay finite and bounded. Seeds are fixed so a failure always reproduces.
+
+use GladeDesk::{
+    default_console_params, render_test_signal, render_trajectory, ConsoleParams, PushCurve,
+    TestSignal,
+};
+
+/// Far above anything the param ranges can reach, only a runaway gets here
+const BOUND: f32 = 1.0e5;
+
+struct Rng(u32);
+
+impl Rng {
+    fn unit(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0 as f32 / u32::MAX as f32
+    }
+
+    fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.unit()
+    }
+
+    fn pick<'a>(&mut self, options: &[&'a str]) -> &'a str {
+        options[((self.unit() * options.len() as f32) as usize).min(options.len() - 1)]
+    }
+}
+
+fn assert_bounded(output: &[f32], what: &str) {
+    for (i, sample) in output.iter().enumerate() {
+        assert!(
+            sample.is_finite() && sample.abs() < BOUND,
+            "sample {i} was {sample} for {what}"
+        );
+    }
+}
+
+// A preset with every range pushed anywhere it's allowed to go
+fn random_preset(rng: &mut Rng) -> String {
+    let mut preset = String::new();
+    let mut line = |id: &str, value: String| preset.push_str(&format!("{id} = {value}\n"));
+    line("free_gain", rng.range(0.25, 4.0).to_string());
+    line("output_gain", rng.range(0.25, 4.0).to_string());
+    line("Push", rng.unit().to_string());
+    line("Multiplier", rng.range(1.0, 10.0).to_string());
+    line("dry_wet", rng.unit().to_string());
+    line("bias", rng.range(0.0, 0.5).to_string());
+    line("even_odd", rng.unit().to_string());
+    line(
+        "harmonic_mode",
+        rng.pick(&["Off", "After", "Replace"]).to_string(),
+    );
+    for harmonic in 2..=8 {
+        line(&format!("harmonic_{harmonic}"), rng.unit().to_string());
+    }
+    line("feedback", rng.range(0.0, 0.9).to_string());
+    line("feedback_damping", rng.range(0.0, 0.95).to_string());
+    line("tap_damping", rng.range(0.0, 0.95).to_string());
+    line(
+        "tap_count",
+        ((rng.range(4.0, 33.0) as usize).min(32)).to_string(),
+    );
+    line("hq_shaper", rng.pick(&["0", "1"]).to_string());
+    line(
+        "push_curve",
+        rng.pick(&["Sine", "Tanh", "Arctan", "Cubic", "Fold"])
+            .to_string(),
+    );
+    line("oversampling", rng.pick(&["1", "2", "4", "8"]).to_string());
+    line(
+        "quality_mode",
+        rng.pick(&["Manual", "Auto", "Tracking", "Mixing", "Render"])
+            .to_string(),
+    );
+    line("spacing_mode", rng.pick(&["Samples", "ms"]).to_string());
+    line("tap_spacing", rng.range(0.01, 0.5).to_string());
+    line("multiband", rng.pick(&["0", "1"]).to_string());
+    line("hpf_slope", rng.pick(&["Off", "12", "24"]).to_string());
+    line("hpf_cutoff", rng.range(10.0, 1000.0).to_string());
+    line("lpf_slope", rng.pick(&["Off", "12", "24"]).to_string());
+    line("lpf_cutoff", rng.range(1000.0, 22000.0).to_string());
+    line("tilt", rng.range(-6.0, 6.0).to_string());
+    line("tilt_pivot", rng.range(200.0, 5000.0).to_string());
+    line("tilt_placement", rng.pick(&["Pre", "Post"]).to_string());
+    line("auto_gain", rng.pick(&["0", "1"]).to_string());
+    line("output_invert", rng.pick(&["0", "1"]).to_string());
+    line("delta", rng.pick(&["0", "1"]).to_string());
+    line("true_peak", rng.pick(&["0", "1"]).to_string());
+    line("true_peak_ceiling", rng.range(-12.0, 0.0).to_string());
+    line("true_peak_release", rng.range(10.0, 1000.0).to_string());
+    line("routing_from", rng.pick(&["Drums", "Vocals"]).to_string());
+    line("routing_to", rng.pick(&["Mix Bus", "Master"]).to_string());
+    line("crosstalk_dynamics", rng.unit().to_string());
+    line("wow_depth", rng.unit().to_string());
+    line("wow_rate", rng.range(0.1, 5.0).to_string());
+    line("tape", rng.pick(&["0", "1"]).to_string());
+    line("tape_drive", rng.unit().to_string());
+    line("tape_bias", rng.unit().to_string());
+    line("transformer", rng.pick(&["0", "1"]).to_string());
+    line("transformer_drive", rng.unit().to_string());
+    line("transformer_core", rng.unit().to_string());
+    line("dual_engine", rng.pick(&["0", "1"]).to_string());
+    line(
+        "bank_morph",
+        rng.pick(&["Linear", "Min Change"]).to_string(),
+    );
+    line(
+        "calibration",
+        rng.pick(&["-18", "-14 dBFS", "-12"]).to_string(),
+    );
+    line(
+        "console_mode",
+        rng.pick(&["Single", "Channel", "Bus"]).to_string(),
+    );
+    line(
+        "mix_law",
+        rng.pick(&["Additive", "Linear", "Equal Power"]).to_string(),
+    );
+    for tap in 1..=8 {
+        line(&format!("{tap}_Coeff"), rng.range(-0.5, 0.5).to_string());
+        line(&format!("{tap}_Skew"), rng.range(-0.5, 0.5).to_string());
+        line(
+            &format!("Polarity_{tap}"),
+            rng.pick(&["+", "-"]).to_string(),
+        );
+    }
+    preset
+}
+
+#[test]
+fn random_presets_stay_bounded() {
+    let mut rng = Rng(0x1763_2001);
+    for case in 0..48 {
+        let preset = random_preset(&mut rng);
+        let signal = match case % 3 {
+            0 => TestSignal::Impulse,
+            1 => TestSignal::Sine(rng.range(20.0, 18000.0)),
+            _ => TestSignal::Noise(case + 1),
+        };
+        let rendered = render_test_signal(&preset, signal, 48000, 2, 1024)
+            .unwrap_or_else(|e| panic!("fuzz preset didn't parse: {e}\n{preset}"));
+        for channel in rendered {
+            assert_bounded(&channel, &format!("case {case}, {signal:?}\n{preset}"));
+        }
+    }
+}
+
+// Moves every param to a new random target every `hold` samples, gliding like a smoother
+fn random_trajectory(rng: &mut Rng, length: usize, hold: usize) -> Vec<(ConsoleParams, f32)> {
+    let random_params = |rng: &mut Rng| {
+        let mut params = default_console_params();
+        params.input_gain = rng.range(0.25, 4.0);
+        params.output_gain = rng.range(0.25, 4.0);
+        params.push = rng.unit();
+        params.multiplier = rng.range(1.0, 10.0);
+        params.wet = rng.unit();
+        params.bias = rng.range(0.0, 0.5);
+        params.even_odd = rng.unit();
+        params.feedback = rng.range(0.0, 0.9);
+        params.feedback_damping = rng.range(0.0, 0.95);
+        params.tap_damping = rng.range(0.0, 0.95);
+        params.hq_shaper = rng.unit() > 0.5;
+        params.push_curve = [
+            PushCurve::Sine,
+            PushCurve::Tanh,
+            PushCurve::Arctan,
+            PushCurve::Cubic,
+            PushCurve::Fold,
+        ][((rng.unit() * 5.0) as usize).min(4)];
+        params.tap_count = (rng.range(4.0, 33.0) as usize).min(32);
+        for coeff in params.coeffs.iter_mut() {
+            *coeff = rng.range(-0.5, 0.5);
+        }
+        for skew in params.skews.iter_mut() {
+            *skew = rng.range(-0.5, 0.5);
+        }
+        for sign in params.polarity.iter_mut() {
+            *sign = if rng.unit() > 0.5 { 1.0 } else { -1.0 };
+        }
+        (params, rng.range(1.0, 96.0))
+    };
+
+    let mut from = random_params(rng);
+    let mut to = random_params(rng);
+    (0..length)
+        .map(|i| {
+            if i % hold == 0 {
+                from = to;
+                to = random_params(rng);
+            }
+            let t = (i % hold) as f32 / hold as f32;
+            let glide = |a: f32, b: f32| a + (b - a) * t;
+            let mut params = from.0;
+            params.input_gain = glide(from.0.input_gain, to.0.input_gain);
+            params.push = glide(from.0.push, to.0.push);
+            params.multiplier = glide(from.0.multiplier, to.0.multiplier);
+            params.bias = glide(from.0.bias, to.0.bias);
+            params.feedback = glide(from.0.feedback, to.0.feedback);
+            for ((coeff, a), b) in params.coeffs.iter_mut().zip(from.0.coeffs).zip(to.0.coeffs) {
+                *coeff = glide(a, b);
+            }
+            for ((skew, a), b) in params.skews.iter_mut().zip(from.0.skews).zip(to.0.skews) {
+                *skew = glide(a, b);
+            }
+            (params, glide(from.1, to.1))
+        })
+        .collect()
+}
+
+#[test]
+fn random_automation_stays_bounded() {
+    let mut rng = Rng(0x1764_2002);
+    for case in 0..32 {
+        let length = 4096;
+        // Full scale noise, the worst thing a user can feed it
+        let input: Vec<f32> = (0..length).map(|_| rng.range(-1.0, 1.0)).collect();
+        let hold = [1, 16, 256][case % 3];
+        let trajectory = random_trajectory(&mut rng, length, hold);
+        let output = render_trajectory(&input, |i| trajectory[i]);
+        assert_bounded(&output, &format!("trajectory case {case}, hold {hold}"));
+    }
+}
+
+#[test]
+fn max_feedback_never_runs_away() {
+    let mut params = default_console_params();
+    params.multiplier = 10.0;
+    params.feedback = 0.9;
+    params.feedback_damping = 0.0;
+    params.coeffs = [0.5; 8];
+    params.skews = [0.5; 8];
+    params.polarity = [1.0; 8];
+    let input: Vec<f32> = (0..8192).map(|i| if i == 0 { 1.0 } else { 0.0 }).collect();
+    let output = render_trajectory(&input, |_| (params, 1.0));
+    assert_bounded(&output, "maximum feedback");
+}
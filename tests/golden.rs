@@ -0,0 +1,149 @@
+// golden.rs - Ardura 2024
+// Golden output regression tests: known signals through fixed settings have to render the same
+// as the files in tests/golden. A missing file is a failure, set GLADE_BLESS=1 to write them
+// the first time and to rewrite them after a sound change that was meant to happen.
+
+use std::{fs, path::PathBuf};
+
+use GladeDesk::{render_test_signal, TestSignal};
+
+const LENGTH: usize = 256;
+const TOLERANCE: f32 = 1.0e-5;
+
+// Pushed hard with the newer features on, so changes to any of them show up
+const COLORED: &str = "
+Push = 0.6
+Multiplier = 3
+tap_count = 12
+feedback = 0.3
+mix_law = Linear
+link = 0
+R1_Coeff_Offset = 0.1
+";
+
+fn check(name: &str, preset: &str, signal: TestSignal, sample_rate: u32, channels: usize) {
+    let rendered = render_test_signal(preset, signal, sample_rate, channels, LENGTH)
+        .expect("preset should parse");
+    let text: String = rendered
+        .iter()
+        .enumerate()
+        .flat_map(|(channel, samples)| {
+            samples
+                .iter()
+                .map(move |sample| format!("{channel} {sample:e}\n"))
+        })
+        .collect();
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(format!("{name}.txt"));
+    if std::env::var_os("GLADE_BLESS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).expect("golden dir should be creatable");
+        fs::write(&path, text).expect("golden file should be writable");
+        eprintln!("wrote {}", path.display());
+        return;
+    }
+
+    assert!(
+        path.exists(),
+        "{name}: {} is missing, run with GLADE_BLESS=1 to write it",
+        path.display()
+    );
+    let expected = fs::read_to_string(&path).expect("golden file should be readable");
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let rendered_lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        expected_lines.len(),
+        rendered_lines.len(),
+        "{name}: sample count changed"
+    );
+    for (line, (expected, rendered)) in expected_lines.iter().zip(rendered_lines).enumerate() {
+        let parse = |text: &str| -> (usize, f32) {
+            let (channel, sample) = text
+                .split_once(' ')
+                .expect("golden line is `channel sample`");
+            (channel.parse().unwrap(), sample.parse().unwrap())
+        };
+        let (expected_channel, expected_sample) = parse(expected);
+        let (channel, sample) = parse(rendered);
+        assert_eq!(expected_channel, channel, "{name}: channel layout changed");
+        assert!(
+            (expected_sample - sample).abs() <= TOLERANCE * expected_sample.abs().max(1.0),
+            "{name}: line {} was {sample}, golden is {expected_sample}",
+            line + 1
+        );
+    }
+}
+
+#[test]
+fn default_impulse_mono() {
+    check(
+        "default_impulse_mono_44100",
+        "",
+        TestSignal::Impulse,
+        44100,
+        1,
+    );
+}
+
+#[test]
+fn default_sine_stereo() {
+    check(
+        "default_sine_stereo_48000",
+        "",
+        TestSignal::Sine(1000.0),
+        48000,
+        2,
+    );
+}
+
+#[test]
+fn default_noise_stereo_96k() {
+    check(
+        "default_noise_stereo_96000",
+        "",
+        TestSignal::Noise(1),
+        96000,
+        2,
+    );
+}
+
+#[test]
+fn colored_impulse_stereo() {
+    check(
+        "colored_impulse_stereo_48000",
+        COLORED,
+        TestSignal::Impulse,
+        48000,
+        2,
+    );
+}
+
+#[test]
+fn colored_sine_mono_96k() {
+    check(
+        "colored_sine_mono_96000",
+        COLORED,
+        TestSignal::Sine(440.0),
+        96000,
+        1,
+    );
+}
+
+#[test]
+fn colored_noise_stereo_44k() {
+    check(
+        "colored_noise_stereo_44100",
+        COLORED,
+        TestSignal::Noise(0x2545_F491),
+        44100,
+        2,
+    );
+}
+
+#[test]
+fn renders_are_deterministic() {
+    let render = || render_test_signal(COLORED, TestSignal::Noise(7), 48000, 2, LENGTH).unwrap();
+    assert_eq!(render(), render());
+}
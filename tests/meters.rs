@@ -0,0 +1,130 @@
+// meters.rs - Ardura 2024
+// Meter math against signals with known levels
+
+use GladeDesk::{meter_level, BlockPeaks, ChannelMeters, MeterClock};
+
+const SAMPLE_RATE: f32 = 48000.0;
+
+// Same per sample decay the plugin works out in initialize(), 12 dB down after 100 ms
+fn decay_per_sample() -> f32 {
+    0.25f64.powf((SAMPLE_RATE as f64 * 100.0 / 1000.0).recip()) as f32
+}
+
+fn sine(amplitude: f32, hz: f32, length: usize) -> Vec<f32> {
+    (0..length)
+        .map(|i| amplitude * (std::f32::consts::TAU * hz * i as f32 / SAMPLE_RATE).sin())
+        .collect()
+}
+
+#[test]
+fn sine_peak_matches_amplitude() {
+    let mut peaks = BlockPeaks::default();
+    for sample in sine(0.5, 1000.0, 480) {
+        peaks.add(0, sample);
+    }
+    assert!(
+        (peaks.peak(0) - 0.5).abs() < 1.0e-4,
+        "peak was {}",
+        peaks.peak(0)
+    );
+}
+
+#[test]
+fn out_of_phase_channels_keep_their_level() {
+    // Summing L+R used to read this as silence
+    let mut peaks = BlockPeaks::default();
+    for sample in sine(0.5, 1000.0, 480) {
+        peaks.add(0, sample);
+        peaks.add(1, -sample);
+    }
+    assert!((peaks.peak(0) - 0.5).abs() < 1.0e-4);
+    assert!((peaks.peak(1) - 0.5).abs() < 1.0e-4);
+}
+
+#[test]
+fn channels_are_independent() {
+    let mut peaks = BlockPeaks::default();
+    peaks.add(1, 0.8);
+    peaks.add(5, -0.3);
+    assert_eq!(peaks.peak(0), 0.0);
+    assert_eq!(peaks.peak(1), 0.8);
+    assert_eq!(peaks.peak(5), 0.3);
+    // Past the last channel is ignored rather than panicking
+    peaks.add(64, 1.0);
+    assert_eq!(peaks.peak(64), 0.0);
+}
+
+#[test]
+fn negative_dc_reads_as_its_magnitude() {
+    let mut peaks = BlockPeaks::default();
+    for _ in 0..256 {
+        peaks.add(0, -0.25);
+    }
+    assert_eq!(peaks.peak(0), 0.25);
+}
+
+#[test]
+fn louder_block_jumps_straight_up() {
+    assert_eq!(meter_level(0.1, 0.9, decay_per_sample(), 512), 0.9);
+}
+
+#[test]
+fn silence_falls_12_db_in_100_ms() {
+    let level = meter_level(1.0, 0.0, decay_per_sample(), (SAMPLE_RATE * 0.1) as usize);
+    assert!((level - 0.25).abs() < 1.0e-3, "level was {level}");
+}
+
+#[test]
+fn decay_doesnt_depend_on_block_size() {
+    let decay = decay_per_sample();
+    let one_block = meter_level(1.0, 0.0, decay, 4800);
+    let many_blocks = (0..100).fold(1.0, |level, _| meter_level(level, 0.0, decay, 48));
+    assert!(
+        (one_block - many_blocks).abs() < 1.0e-4,
+        "{one_block} vs {many_blocks}"
+    );
+}
+
+// One 4096 sample block pushed as 16 sub-blocks: a burst in the middle then silence
+fn burst_block(meters: &ChannelMeters) {
+    for sub_block in 0..16u64 {
+        let mut peaks = BlockPeaks::default();
+        if sub_block == 8 {
+            peaks.add(0, 0.9);
+        }
+        meters.push(&peaks, decay_per_sample(), 256, (sub_block + 1) * 256);
+    }
+    meters.set_block_len(4096);
+}
+
+#[test]
+fn sub_blocks_keep_their_place_in_a_huge_block() {
+    let meters = ChannelMeters::new();
+    burst_block(&meters);
+    // Before the burst the meter is still silent, right after it reads the burst
+    assert_eq!(meters.level_at(0, 8 * 256), 0.0);
+    assert_eq!(meters.level_at(0, 9 * 256), 0.9);
+    // By the end of the block it has already started falling
+    let end = meters.level_at(0, 16 * 256);
+    assert!(end > 0.0 && end < 0.9, "level was {end}");
+}
+
+#[test]
+fn meter_clock_plays_a_block_out_in_real_time() {
+    let meters = ChannelMeters::new();
+    burst_block(&meters);
+    let (newest, block_len) = meters.newest();
+    assert_eq!((newest, block_len), (4096, 4096));
+
+    let mut clock = MeterClock::default();
+    // Starts one block behind the newest level and moves on with the GUI time
+    assert_eq!(clock.position(newest, block_len, 10.0, SAMPLE_RATE), 0);
+    let halfway = clock.position(
+        newest,
+        block_len,
+        10.0 + 2400.0 / SAMPLE_RATE as f64,
+        SAMPLE_RATE,
+    );
+    assert!((2399..=2400).contains(&halfway), "position was {halfway}");
+    assert_eq!(meters.level_at(0, halfway), 0.9);
+}
@@ -0,0 +1,77 @@
+// mix_law.rs - Ardura 2024
+// Mix law endpoints and levels: 0% has to be the aligned dry, 100% the taps alone, and the
+// equal power crossfade can't bump the level around the middle
+
+use GladeDesk::MixLaw;
+
+const LENGTH: usize = 480;
+
+// Quarter cycle apart so the two sides are uncorrelated over whole cycles
+fn dry(i: usize) -> f32 {
+    (std::f32::consts::TAU * i as f32 / 48.0).sin()
+}
+
+fn taps(i: usize) -> f32 {
+    (std::f32::consts::TAU * i as f32 / 48.0).cos()
+}
+
+fn rms(law: MixLaw, amount: f32) -> f32 {
+    let sum: f32 = (0..LENGTH)
+        .map(|i| law.mix(dry(i), taps(i), amount).powi(2))
+        .sum();
+    (sum / LENGTH as f32).sqrt()
+}
+
+#[test]
+fn zero_is_the_dry() {
+    for law in [MixLaw::Additive, MixLaw::Linear, MixLaw::EqualPower] {
+        for i in 0..LENGTH {
+            assert!(
+                (law.mix(dry(i), taps(i), 0.0) - dry(i)).abs() < 1.0e-6,
+                "{law:?} at 0% wasn't the dry at sample {i}"
+            );
+        }
+    }
+}
+
+#[test]
+fn full_is_the_taps_alone() {
+    for law in [MixLaw::Linear, MixLaw::EqualPower] {
+        for i in 0..LENGTH {
+            assert!(
+                (law.mix(dry(i), taps(i), 1.0) - taps(i)).abs() < 1.0e-6,
+                "{law:?} at 100% still had dry in it at sample {i}"
+            );
+        }
+    }
+}
+
+#[test]
+fn additive_keeps_the_dry_under_the_taps() {
+    for i in 0..LENGTH {
+        assert!((MixLaw::Additive.mix(dry(i), taps(i), 1.0) - (dry(i) + taps(i))).abs() < 1.0e-6);
+    }
+}
+
+#[test]
+fn crossfades_differ_from_additive() {
+    // Silent taps at 50%, the additive law leaves the dry alone while the crossfades turn it down
+    assert_eq!(MixLaw::Additive.mix(1.0, 0.0, 0.5), 1.0);
+    assert!((MixLaw::Linear.mix(1.0, 0.0, 0.5) - 0.5).abs() < 1.0e-6);
+    assert!(
+        (MixLaw::EqualPower.mix(1.0, 0.0, 0.5) - std::f32::consts::FRAC_1_SQRT_2).abs() < 1.0e-6
+    );
+}
+
+#[test]
+fn equal_power_holds_level_through_the_middle() {
+    let reference = rms(MixLaw::EqualPower, 0.0);
+    for step in 0..=20 {
+        let amount = step as f32 / 20.0;
+        let level = rms(MixLaw::EqualPower, amount);
+        assert!(
+            (20.0 * (level / reference).log10()).abs() < 0.05,
+            "level moved to {level} at {amount}"
+        );
+    }
+}